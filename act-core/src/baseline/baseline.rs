@@ -0,0 +1,193 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Diagnostic;
+
+/// The version of the baseline schema, it changes when a field is removed or changes meaning.
+pub const VERSION: u32 = 1;
+
+/// Known problems that are not reported, to adopt act-lint on existing workflows and only
+/// fail on new problems.
+///
+/// Problems are recognized by their [fingerprint](Diagnostic::fingerprint), which does not
+/// change when lines are added or removed above them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// The [VERSION] of the schema.
+    pub version: u32,
+
+    pub entries: Vec<Entry>,
+}
+
+/// A known problem.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// The id of the rule that found the problem, e.g. `AL002`.
+    pub rule: String,
+
+    /// The path of the file, with `/` separators.
+    pub file: String,
+
+    pub fingerprint: String,
+
+    /// The message of the problem when it was recorded, for readers of the baseline.
+    pub message: String,
+
+    /// How many problems have the fingerprint, e.g. for identical lines.
+    #[serde(default = "one", skip_serializing_if = "is_one")]
+    pub count: usize,
+}
+
+fn one() -> usize {
+    1
+}
+
+fn is_one(count: &usize) -> bool {
+    *count == 1
+}
+
+impl Baseline {
+    pub fn new() -> Self {
+        Self {
+            version: VERSION,
+            entries: vec![],
+        }
+    }
+
+    /// Record the problems of a file.
+    pub fn add(&mut self, file: &str, source: &str, diagnostics: &[Diagnostic]) {
+        for diagnostic in diagnostics {
+            let fingerprint = diagnostic.fingerprint(file, source);
+            match self
+                .entries
+                .iter_mut()
+                .find(|entry| entry.fingerprint == fingerprint)
+            {
+                Some(entry) => entry.count += 1,
+                None => self.entries.push(Entry {
+                    rule: diagnostic.rule.to_string(),
+                    file: file.to_string(),
+                    fingerprint,
+                    message: diagnostic.message.clone(),
+                    count: 1,
+                }),
+            }
+        }
+    }
+
+    /// Match the problems of linted files against the baseline.
+    pub fn matcher(&self) -> Matcher<'_> {
+        Matcher {
+            baseline: self,
+            remaining: self
+                .entries
+                .iter()
+                .map(|entry| (entry.fingerprint.as_str(), entry.count))
+                .collect(),
+            files: HashSet::new(),
+        }
+    }
+}
+
+/// Leaves out the problems of a [Baseline], each entry matches as many problems as its count.
+#[derive(Debug)]
+pub struct Matcher<'a> {
+    baseline: &'a Baseline,
+    remaining: HashMap<&'a str, usize>,
+    files: HashSet<String>,
+}
+
+impl<'a> Matcher<'a> {
+    /// The problems of a file that are not in the baseline.
+    pub fn filter(
+        &mut self,
+        file: &str,
+        source: &str,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Vec<Diagnostic> {
+        self.files.insert(file.to_string());
+        diagnostics
+            .into_iter()
+            .filter(|diagnostic| {
+                match self
+                    .remaining
+                    .get_mut(diagnostic.fingerprint(file, source).as_str())
+                {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        false
+                    }
+                    _ => true,
+                }
+            })
+            .collect()
+    }
+
+    /// The entries of the filtered files that matched fewer problems than their count, the
+    /// problems were solved or changed. Files that were not filtered are not checked.
+    pub fn stale(&self) -> Vec<&'a Entry> {
+        self.baseline
+            .entries
+            .iter()
+            .filter(|entry| {
+                self.files.contains(&entry.file)
+                    && self.remaining.get(entry.fingerprint.as_str()) > Some(&0)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Registry;
+
+    const SOURCE: &str = "on: push\nenv:\n  GITHUB_A: 1\n  GITHUB_B: 2\n";
+
+    fn lint(source: &str) -> Vec<Diagnostic> {
+        Registry::builtin().lint(source, None).into_iter().collect()
+    }
+
+    #[test]
+    fn filter() {
+        let mut baseline = Baseline::new();
+        baseline.add("ci.yml", SOURCE, &lint(SOURCE));
+        assert_eq!(baseline.entries.len(), 2);
+        let json = serde_json::to_value(&baseline).unwrap();
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["entries"][0].get("count"), None);
+
+        // moved down, one problem solved and a new one
+        let source = "on: push\n\nenv:\n  GITHUB_B: 2\n  GITHUB_C: 3\n";
+        let mut matcher = baseline.matcher();
+        let new = matcher.filter("ci.yml", source, lint(source));
+        assert_eq!(new.len(), 1);
+        assert!(new[0].message.starts_with("env.GITHUB_C"));
+        let stale = matcher.stale();
+        assert_eq!(stale.len(), 1);
+        assert!(stale[0].message.starts_with("env.GITHUB_A"));
+    }
+
+    #[test]
+    fn count() {
+        let source = "on: push\njobs:\n  a:\n    runs-on: ubuntu-latest\n    env:\n      GITHUB_A: 1\n  b:\n    runs-on: ubuntu-latest\n    env:\n      GITHUB_A: 1\n";
+        let mut baseline = Baseline::new();
+        baseline.add("ci.yml", source, &lint(source));
+        // same rule, file and line
+        assert_eq!(baseline.entries.len(), 1);
+        assert_eq!(baseline.entries[0].count, 2);
+        let mut matcher = baseline.matcher();
+        let source = format!("{}  c:\n    runs-on: ubuntu-latest\n    env:\n      GITHUB_A: 1\n", source);
+        assert_eq!(matcher.filter("ci.yml", &source, lint(&source)).len(), 1);
+    }
+
+    #[test]
+    fn stale_only_for_filtered_files() {
+        let mut baseline = Baseline::new();
+        baseline.add("ci.yml", SOURCE, &lint(SOURCE));
+        let mut matcher = baseline.matcher();
+        matcher.filter("cd.yml", SOURCE, lint(SOURCE));
+        assert!(matcher.stale().is_empty());
+    }
+}
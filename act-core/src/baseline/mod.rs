@@ -0,0 +1,4 @@
+pub use baseline::{Baseline, Entry, Matcher, VERSION};
+
+#[allow(clippy::module_inception)]
+pub mod baseline;
@@ -0,0 +1,241 @@
+use std::path::{Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ValidationError;
+use crate::{Document, Error, Registry, Severity};
+
+/// The configuration of act-lint for a repository, read from `.act-lint.yml`.
+///
+/// ```yaml
+/// rules:
+///   AL005: error
+///   expression-syntax: off
+/// ignore:
+///   - .github/workflows/generated-*.yml
+/// self-hosted-runner:
+///   labels: [gpu, arm64-large]
+/// secrets: [DEPLOY_KEY]
+/// vars: [ENVIRONMENT]
+/// format: sarif
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    /// The level of rules, by id or name, the other rules keep their default severity.
+    pub rules: IndexMap<String, RuleLevel>,
+
+    /// Glob patterns of workflow files not to lint, relative to the directory of the
+    /// configuration file.
+    pub ignore: Vec<String>,
+
+    /// The self-hosted runners of the repository.
+    pub self_hosted_runner: SelfHostedRunner,
+
+    /// The names of the secrets defined for the repository, besides `GITHUB_TOKEN`, `AL010`
+    /// reports references to other secrets when there is any.
+    pub secrets: Vec<String>,
+
+    /// The names of the configuration variables defined for the repository, `AL010`
+    /// reports references to other variables when there is any.
+    pub vars: Vec<String>,
+
+    /// The output format used unless `--format` is given.
+    pub format: Option<String>,
+}
+
+/// Whether a rule runs, and the severity of its diagnostics.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    Off,
+    Note,
+    Warning,
+    Error,
+}
+
+/// The self-hosted runners of a repository.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SelfHostedRunner {
+    /// The custom labels of the runners, e.g. `gpu`.
+    pub labels: Vec<String>,
+}
+
+impl Config {
+    /// The names of configuration files, in order of preference.
+    pub const FILE_NAMES: &'static [&'static str] = &[".act-lint.yml", ".act-lint.yaml"];
+
+    /// The configuration file in a directory or the closest of its ancestors.
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .flat_map(|dir| Self::FILE_NAMES.iter().map(move |name| dir.join(name)))
+            .find(|path| path.is_file())
+    }
+
+    /// Deserialize a configuration and check its rules and globs, every error reports its
+    /// location in the document.
+    pub fn load(document: &Document, registry: &Registry) -> Result<Config, Vec<Error>> {
+        match Self::load_all(document, registry) {
+            (Some(config), errors) if errors.is_empty() => Ok(config),
+            (_, errors) => Err(errors),
+        }
+    }
+
+    /// Deserialize a configuration and check its rules and globs, reporting every error
+    /// instead of failing at the first one.
+    pub fn load_all(document: &Document, registry: &Registry) -> (Option<Config>, Vec<Error>) {
+        // an empty file is an empty configuration
+        if document.root().is_null() {
+            return (Some(Config::default()), vec![]);
+        }
+        let (config, mut errors) = document.deserialize_all::<Config>();
+        if let Some(config) = &config {
+            errors.extend(config.validate(registry).into_iter().map(|err| {
+                let span = document.path_span(err.path());
+                Error::Validation(
+                    err.with_location(document.location(span.start))
+                        .with_span(span),
+                )
+            }));
+        }
+        (config, errors)
+    }
+
+    fn validate(&self, registry: &Registry) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        for key in self.rules.keys() {
+            if registry.find(key).is_none() {
+                errors.push(ValidationError::new(
+                    format!("rules.{}", key),
+                    format!("unknown rule `{}`", key),
+                    None,
+                ));
+            }
+        }
+        for (i, glob) in self.ignore.iter().enumerate() {
+            if let Err(err) = Pattern::new(glob) {
+                errors.push(ValidationError::new(
+                    format!("ignore[{}]", i),
+                    format!("invalid glob `{}`: {}", glob, err.msg),
+                    None,
+                ));
+            }
+        }
+        errors
+    }
+
+    /// Set the level of the configured rules of a registry, unknown rules are skipped.
+    pub fn apply(&self, registry: &mut Registry) {
+        for (key, level) in &self.rules {
+            let Some(id) = registry.find(key).map(|rule| rule.id()) else {
+                continue;
+            };
+            let severity = match level {
+                RuleLevel::Off => {
+                    registry.set_enabled(id, false);
+                    continue;
+                }
+                RuleLevel::Note => Severity::Note,
+                RuleLevel::Warning => Severity::Warning,
+                RuleLevel::Error => Severity::Error,
+            };
+            registry.set_enabled(id, true);
+            registry.set_severity(id, severity);
+        }
+    }
+
+    /// Whether a path relative to the directory of the configuration file matches an
+    /// `ignore` glob, `*` does not match `/`.
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::default()
+        };
+        let path = path.strip_prefix("./").unwrap_or(path);
+        self.ignore.iter().any(|glob| {
+            Pattern::new(glob.trim_start_matches("./"))
+                .is_ok_and(|pattern| pattern.matches_path_with(path, options))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(source: &str) -> Result<Config, Vec<String>> {
+        let document = Document::parse(source).unwrap();
+        Config::load(&document, &Registry::builtin()).map_err(|errors| {
+            errors
+                .iter()
+                .map(|err| format!("{}: {}", err.location().unwrap(), err))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn load_ok() {
+        let config = load(
+            "rules:\n  AL005: error\n  schema: off\nignore: [gen-*.yml]\nself-hosted-runner:\n  labels: [gpu]\nsecrets: [DEPLOY_KEY]\nvars: [ENVIRONMENT]\nformat: sarif\n",
+        )
+        .unwrap();
+        assert_eq!(config.rules["AL005"], RuleLevel::Error);
+        assert_eq!(config.rules["schema"], RuleLevel::Off);
+        assert_eq!(config.self_hosted_runner.labels, vec!["gpu"]);
+        assert_eq!(config.secrets, vec!["DEPLOY_KEY"]);
+        assert_eq!(config.vars, vec!["ENVIRONMENT"]);
+        assert_eq!(config.format.as_deref(), Some("sarif"));
+    }
+
+    #[test]
+    fn load_empty() {
+        assert_eq!(load("").unwrap(), Config::default());
+        assert_eq!(load("# nothing yet\n").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn load_err() {
+        assert_eq!(
+            load("rules:\n  AL999: error\n  AL001: loud\nignore: ['[']\nself-hosted-runner:\n  label: [gpu]\n")
+                .unwrap_err(),
+            vec![
+                "3:10: rules.AL001: unknown variant `loud`, expected one of `off`, `note`, `warning`, `error`",
+                "6:3: self-hosted-runner.label: unknown field `label`, expected `labels`",
+                "2:10: rules.AL999: unknown rule `AL999`",
+                "4:10: ignore[0]: invalid glob `[`: invalid range pattern",
+            ]
+        );
+    }
+
+    #[test]
+    fn apply() {
+        let config = load("rules:\n  AL005: error\n  schema: off\n").unwrap();
+        let mut registry = Registry::builtin();
+        config.apply(&mut registry);
+        assert_eq!(registry.severity("AL005"), Some(Severity::Error));
+        assert!(!registry.is_enabled("AL002"));
+        assert!(registry.is_enabled("AL001"));
+    }
+
+    #[test]
+    fn is_ignored() {
+        let config = load("ignore: ['.github/workflows/gen-*.yml']\n").unwrap();
+        assert!(config.is_ignored(Path::new(".github/workflows/gen-a.yml")));
+        assert!(config.is_ignored(Path::new("./.github/workflows/gen-a.yml")));
+        assert!(!config.is_ignored(Path::new(".github/workflows/ci.yml")));
+    }
+
+    #[test]
+    fn find() {
+        let root = std::env::temp_dir().join("act-lint-config-find");
+        let nested = root.join("a/b");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(root.join(".act-lint.yml"), "").unwrap();
+        let found = Config::find(&nested);
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(found, Some(root.join(".act-lint.yml")));
+    }
+}
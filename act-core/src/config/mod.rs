@@ -0,0 +1,4 @@
+pub use config::{Config, RuleLevel, SelfHostedRunner};
+
+#[allow(clippy::module_inception)]
+pub mod config;
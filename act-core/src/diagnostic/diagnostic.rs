@@ -0,0 +1,217 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::expression::Span;
+
+/// How serious a problem reported by a [Diagnostic] is.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Something worth knowing that is not a problem.
+    Note,
+
+    /// A likely mistake that does not make the workflow invalid.
+    Warning,
+
+    /// A problem that makes the workflow invalid.
+    Error,
+}
+
+/// A problem found in a YAML source, with the rule that found it and where it is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// The id of the rule that found the problem, e.g. `AL002`.
+    pub rule: &'static str,
+
+    pub severity: Severity,
+
+    pub message: String,
+
+    /// The byte offsets of the offending source, may span no bytes for a position.
+    pub span: Span,
+
+    /// Other parts of the source that explain the problem.
+    pub labels: Vec<Label>,
+
+    /// How to solve the problem.
+    pub help: Option<String>,
+
+    /// A change of the source that solves the problem.
+    pub fix: Option<Fix>,
+}
+
+/// A part of the source that explains a [Diagnostic].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+/// A change of the source that solves the problem of a [Diagnostic].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fix {
+    /// What the change does, e.g. "rename the variable".
+    pub message: String,
+
+    /// Replacements of non-overlapping spans of the source.
+    pub edits: Vec<Edit>,
+}
+
+/// The replacement of a span of the source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edit {
+    pub span: Span,
+    pub replacement: String,
+}
+
+impl Diagnostic {
+    pub fn new(
+        rule: &'static str,
+        severity: Severity,
+        message: impl Into<String>,
+        span: Span,
+    ) -> Self {
+        Self {
+            rule,
+            severity,
+            message: message.into(),
+            span,
+            labels: vec![],
+            help: None,
+            fix: None,
+        }
+    }
+
+    pub fn error(rule: &'static str, message: impl Into<String>, span: Span) -> Self {
+        Self::new(rule, Severity::Error, message, span)
+    }
+
+    pub fn warning(rule: &'static str, message: impl Into<String>, span: Span) -> Self {
+        Self::new(rule, Severity::Warning, message, span)
+    }
+
+    /// Add a part of the source that explains the problem.
+    pub fn with_label(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.labels.push(Label {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+
+    /// Set how to solve the problem.
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Set a change of the source that solves the problem.
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
+    /// A hex SHA-256 of the rule, the file and the source lines of the problem with
+    /// whitespace collapsed, it does not change when lines are added above the problem.
+    pub fn fingerprint(&self, file: &str, source: &str) -> String {
+        let start = source[..self.span.start.min(source.len())]
+            .rfind('\n')
+            .map_or(0, |i| i + 1);
+        let end = source[self.span.end.min(source.len())..]
+            .find('\n')
+            .map_or(source.len(), |i| self.span.end.min(source.len()) + i);
+        let snippet = source
+            .get(start..end)
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut hasher = Sha256::new();
+        for part in [self.rule, file, &snippet] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+impl Fix {
+    pub fn new(message: impl Into<String>, edits: Vec<Edit>) -> Self {
+        Self {
+            message: message.into(),
+            edits,
+        }
+    }
+}
+
+impl Edit {
+    pub fn new(span: Span, replacement: impl Into<String>) -> Self {
+        Self {
+            span,
+            replacement: replacement.into(),
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Note => write!(f, "note"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder() {
+        let diagnostic = Diagnostic::warning("AL005", "reserved name", Span::new(4, 14))
+            .with_label(Span::new(0, 3), "in this map")
+            .with_help("rename the variable")
+            .with_fix(Fix::new(
+                "rename",
+                vec![Edit::new(Span::new(4, 14), "MY_TOKEN")],
+            ));
+        assert_eq!(diagnostic.labels[0].message, "in this map");
+        assert_eq!(diagnostic.help.as_deref(), Some("rename the variable"));
+        assert_eq!(diagnostic.fix.unwrap().edits[0].replacement, "MY_TOKEN");
+    }
+
+    #[test]
+    fn fingerprint() {
+        let diagnostic = Diagnostic::warning("AL005", "reserved name", Span::new(11, 21));
+        let fingerprint = diagnostic.fingerprint("ci.yml", "env:\n      GITHUB_SHA: x\n");
+        assert_eq!(fingerprint.len(), 64);
+        // moved down a line and re-indented
+        let moved = Diagnostic::warning("AL005", "reserved name", Span::new(8, 18));
+        assert_eq!(
+            moved.fingerprint("ci.yml", "\nenv:\n  GITHUB_SHA:   x\n"),
+            fingerprint
+        );
+        assert_ne!(
+            diagnostic.fingerprint("cd.yml", "env:\n      GITHUB_SHA: x\n"),
+            fingerprint
+        );
+        assert_ne!(
+            diagnostic.fingerprint("ci.yml", "env:\n      GITHUB_SHA: y\n"),
+            fingerprint
+        );
+    }
+
+    #[test]
+    fn severity_order() {
+        assert!(Severity::Error > Severity::Warning);
+        assert!(Severity::Warning > Severity::Note);
+        assert_eq!(Severity::Warning.to_string(), "warning");
+    }
+}
@@ -0,0 +1,103 @@
+use crate::diagnostic::{Diagnostic, Severity};
+
+/// Collects the diagnostics of a source, so that one pass reports every problem.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Diagnostics {
+    items: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        self.items.push(diagnostic);
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Diagnostic> {
+        self.items.iter()
+    }
+
+    /// The number of diagnostics with the given severity.
+    pub fn count(&self, severity: Severity) -> usize {
+        self.items
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    }
+
+    /// Whether a diagnostic reports a problem that makes the source invalid.
+    pub fn has_errors(&self) -> bool {
+        self.count(Severity::Error) > 0
+    }
+
+    /// Order the diagnostics by where they are in the source, then by rule.
+    pub fn sort(&mut self) {
+        self.items.sort_by(|a, b| {
+            (a.span.start, a.span.end, a.rule).cmp(&(b.span.start, b.span.end, b.rule))
+        });
+    }
+}
+
+impl Extend<Diagnostic> for Diagnostics {
+    fn extend<I: IntoIterator<Item = Diagnostic>>(&mut self, iter: I) {
+        self.items.extend(iter);
+    }
+}
+
+impl FromIterator<Diagnostic> for Diagnostics {
+    fn from_iter<I: IntoIterator<Item = Diagnostic>>(iter: I) -> Self {
+        Self {
+            items: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for Diagnostics {
+    type Item = Diagnostic;
+    type IntoIter = std::vec::IntoIter<Diagnostic>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Diagnostics {
+    type Item = &'a Diagnostic;
+    type IntoIter = std::slice::Iter<'a, Diagnostic>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expression::Span;
+
+    #[test]
+    fn collect() {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push(Diagnostic::warning("AL005", "b", Span::new(10, 12)));
+        diagnostics.push(Diagnostic::error("AL002", "a", Span::new(3, 5)));
+        diagnostics.extend([Diagnostic::error("AL001", "c", Span::new(3, 5))]);
+        assert_eq!(diagnostics.len(), 3);
+        assert!(diagnostics.has_errors());
+        assert_eq!(diagnostics.count(Severity::Warning), 1);
+        diagnostics.sort();
+        assert_eq!(
+            diagnostics.iter().map(|d| d.rule).collect::<Vec<_>>(),
+            vec!["AL001", "AL002", "AL005"]
+        );
+    }
+}
@@ -0,0 +1,6 @@
+pub use diagnostic::{Diagnostic, Edit, Fix, Label, Severity};
+pub use diagnostics::Diagnostics;
+
+#[allow(clippy::module_inception)]
+pub mod diagnostic;
+pub mod diagnostics;
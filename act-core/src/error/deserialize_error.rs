@@ -0,0 +1,96 @@
+use std::fmt;
+
+use crate::expression::Span;
+use crate::yaml::Location;
+
+/// An error that occurs when a YAML node does not have the expected shape, e.g. an unknown
+/// field or a string where a map is expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeserializeError {
+    /// The path of the offending node, e.g. `jobs.build.steps[0]`.
+    path: String,
+
+    /// What is wrong with the node.
+    message: String,
+
+    /// Where the node is in the source, if known.
+    location: Option<Location>,
+
+    /// The byte offsets the node spans in the source, if known.
+    span: Option<Span>,
+}
+
+impl DeserializeError {
+    /// Create a new deserialize error for the node at the given path.
+    pub fn new(
+        path: impl Into<String>,
+        message: impl Into<String>,
+        location: Option<Location>,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+            location,
+            span: None,
+        }
+    }
+
+    /// The path of the offending node, empty for the root node.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// What is wrong with the node.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Where the node is in the source, if known.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    /// Set the byte offsets the node spans in the source.
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// The byte offsets the node spans in the source, if known.
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            format!(
+                "{}",
+                DeserializeError::new("jobs.build", "unknown field `step`", None)
+            ),
+            "jobs.build: unknown field `step`"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                DeserializeError::new("", "invalid type: string \"x\"", None)
+            ),
+            "invalid type: string \"x\""
+        );
+    }
+}
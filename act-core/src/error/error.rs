@@ -1,4 +1,8 @@
-use crate::error::ExpressionError;
+use crate::error::{
+    DeserializeError, EvaluationError, ExpressionError, JobIdError, ValidationError, YamlError,
+};
+use crate::expression::Span;
+use crate::yaml::Location;
 
 /// An error that occurred during parsing of workflow or action definitions.
 #[non_exhaustive]
@@ -6,12 +10,67 @@ use crate::error::ExpressionError;
 pub enum Error {
     /// An syntax error occurred during parsing string expression.
     Expression(ExpressionError),
+
+    /// An expression could not be evaluated against a context.
+    Evaluation(EvaluationError),
+
+    /// A job id that does not follow the job id syntax.
+    JobId(JobIdError),
+
+    /// A source that is not valid YAML.
+    Yaml(YamlError),
+
+    /// A YAML node that does not have the expected shape.
+    Deserialize(DeserializeError),
+
+    /// A value that breaks a validation rule.
+    Validation(ValidationError),
+}
+
+impl Error {
+    /// Where the error is in a YAML source, if known.
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            Error::Expression(err) => err.location(),
+            Error::Evaluation(err) => err.location(),
+            Error::JobId(err) => err.location(),
+            Error::Yaml(err) => Some(err.location()),
+            Error::Deserialize(err) => err.location(),
+            Error::Validation(err) => err.location(),
+        }
+    }
+
+    /// The byte offsets the error spans in a YAML source, if known.
+    ///
+    /// Errors located by a single position, e.g. YAML syntax errors, span no bytes.
+    pub fn span(&self) -> Option<Span> {
+        // expression spans are relative to the expression, the location is the start of the span
+        let expression_span = |location: Option<&Location>, span: Option<Span>| {
+            let start = location?.offset;
+            let len = span.map_or(0, |span| span.end - span.start);
+            Some(Span::new(start, start + len))
+        };
+        match self {
+            Error::Expression(err) => expression_span(err.location(), err.span()),
+            Error::Evaluation(err) => expression_span(err.location(), Some(err.span())),
+            Error::Deserialize(err) => err.span(),
+            Error::Validation(err) => err.span(),
+            err => err
+                .location()
+                .map(|location| Span::new(location.offset, location.offset)),
+        }
+    }
 }
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match *self {
             Error::Expression(ref err) => write!(f, "{}", err),
+            Error::Evaluation(ref err) => write!(f, "{}", err),
+            Error::JobId(ref err) => write!(f, "{}", err),
+            Error::Yaml(ref err) => write!(f, "{}", err),
+            Error::Deserialize(ref err) => write!(f, "{}", err),
+            Error::Validation(ref err) => write!(f, "{}", err),
         }
     }
 }
@@ -24,10 +83,24 @@ macro_rules! error_expression {
     };
 }
 
+/// A macro to create an [error for an expression that failed to evaluate](Error::Evaluation).
+#[macro_export]
+macro_rules! error_evaluation {
+    ($($arg:tt)+) => {
+        $crate::error::Error::Evaluation($crate::error::EvaluationError::new($($arg)+))
+    };
+}
+
+/// A macro to create an [error for an invalid job id](Error::JobId).
+#[macro_export]
+macro_rules! error_job_id {
+    ($($arg:tt)+) => {
+        $crate::error::Error::JobId($crate::error::JobIdError::new($($arg)+))
+    };
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::error_expression;
-
     #[test]
     fn display() {
         assert_eq!(
@@ -43,4 +116,27 @@ mod tests {
             r#"not a valid string expression: "x""#
         );
     }
+
+    #[test]
+    fn error_evaluation() {
+        assert_eq!(
+            format!(
+                "{}",
+                error_evaluation!(
+                    "${{ x }}",
+                    "unknown context `x`",
+                    crate::expression::Span::new(4, 5)
+                )
+            ),
+            r#"failed to evaluate expression: "${{ x }}", unknown context `x` at position 4"#
+        );
+    }
+
+    #[test]
+    fn error_job_id() {
+        assert_eq!(
+            format!("{}", error_job_id!("1x")),
+            r#"not a valid job id: "1x", a job id must start with a letter or `_` and contain only alphanumeric characters, `-` or `_`"#
+        );
+    }
 }
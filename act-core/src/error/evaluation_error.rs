@@ -0,0 +1,84 @@
+use std::fmt;
+
+use crate::expression::Span;
+use crate::yaml::Location;
+
+/// An error that occurs when evaluating an expression against a context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvaluationError {
+    /// The expression that failed to evaluate.
+    expression: String,
+
+    /// What went wrong.
+    message: String,
+
+    /// Where in the expression the error is.
+    span: Span,
+
+    /// Where the expression is in a YAML source, if known.
+    location: Option<Location>,
+}
+
+impl EvaluationError {
+    /// Create a new evaluation error at the given span of the expression.
+    pub fn new(expression: &str, message: impl Into<String>, span: Span) -> Self {
+        Self {
+            expression: expression.to_string(),
+            message: message.into(),
+            span,
+            location: None,
+        }
+    }
+
+    /// The expression that failed to evaluate.
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// What went wrong.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The byte offsets of the error in the expression.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Set where the error is in a YAML source.
+    pub fn with_location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// Where the error is in a YAML source, if known.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+}
+
+impl fmt::Display for EvaluationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            r#"failed to evaluate expression: "{}", {} at position {}"#,
+            self.expression, self.message, self.span.start
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            format!(
+                "{}",
+                EvaluationError::new("${{ foo() }}", "unknown function `foo`", Span::new(4, 9))
+            ),
+            r#"failed to evaluate expression: "${{ foo() }}", unknown function `foo` at position 4"#
+        );
+    }
+}
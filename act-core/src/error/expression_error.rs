@@ -1,10 +1,22 @@
 use std::fmt;
 
+use crate::expression::Span;
+use crate::yaml::Location;
+
 /// An error that occurs when parsing a expression string.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExpressionError {
     /// The expression that failed to parse.
     expression: String,
+
+    /// What is wrong with the expression, if known.
+    message: Option<String>,
+
+    /// Where in the expression the error is, if known.
+    span: Option<Span>,
+
+    /// Where the expression is in a YAML source, if known.
+    location: Option<Location>,
 }
 
 impl ExpressionError {
@@ -12,13 +24,57 @@ impl ExpressionError {
     pub fn new(expression: &str) -> Self {
         Self {
             expression: expression.to_string(),
+            message: None,
+            span: None,
+            location: None,
         }
     }
+
+    /// Create a new syntax error at the given span of the expression.
+    pub fn syntax(expression: &str, message: impl Into<String>, span: Span) -> Self {
+        Self {
+            expression: expression.to_string(),
+            message: Some(message.into()),
+            span: Some(span),
+            location: None,
+        }
+    }
+
+    /// The expression that failed to parse.
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// What is wrong with the expression, if known.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// The byte offsets of the error in the expression, if known.
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    /// Set where the error is in a YAML source.
+    pub fn with_location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// Where the error is in a YAML source, if known.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
 }
 
 impl fmt::Display for ExpressionError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, r#"not a valid string expression: "{}""#, self.expression)
+        write!(f, r#"not a valid string expression: "{}""#, self.expression)?;
+        match (&self.message, &self.span) {
+            (Some(message), Some(span)) => write!(f, ", {} at position {}", message, span.start),
+            (Some(message), None) => write!(f, ", {}", message),
+            _ => Ok(()),
+        }
     }
 }
 
@@ -33,4 +89,15 @@ mod tests {
             r#"not a valid string expression: "x""#
         );
     }
+
+    #[test]
+    fn display_syntax() {
+        assert_eq!(
+            format!(
+                "{}",
+                ExpressionError::syntax("${{ a == }}", "unexpected `}}`", Span::new(9, 11))
+            ),
+            r#"not a valid string expression: "${{ a == }}", unexpected `}}` at position 9"#
+        );
+    }
 }
@@ -0,0 +1,57 @@
+use std::fmt;
+
+use crate::yaml::Location;
+
+/// An error that occurs when a job id does not follow the job id syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobIdError {
+    /// The job id that failed to parse.
+    id: String,
+
+    /// Where the job id is in a YAML source, if known.
+    location: Option<Location>,
+}
+
+impl JobIdError {
+    /// Create a new job id error for the given id.
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            location: None,
+        }
+    }
+
+    /// Set where the error is in a YAML source.
+    pub fn with_location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// Where the error is in a YAML source, if known.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+}
+
+impl fmt::Display for JobIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            r#"not a valid job id: "{}", a job id must start with a letter or `_` and contain only alphanumeric characters, `-` or `_`"#,
+            self.id
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            format!("{}", JobIdError::new("1x")),
+            r#"not a valid job id: "1x", a job id must start with a letter or `_` and contain only alphanumeric characters, `-` or `_`"#
+        );
+    }
+}
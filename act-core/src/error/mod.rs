@@ -1,5 +1,16 @@
+pub mod deserialize_error;
+#[allow(clippy::module_inception)]
 pub mod error;
+pub mod evaluation_error;
 pub mod expression_error;
+pub mod job_id_error;
+pub mod validation_error;
+pub mod yaml_error;
 
+pub use deserialize_error::DeserializeError;
 pub use error::Error;
+pub use evaluation_error::EvaluationError;
 pub use expression_error::ExpressionError;
+pub use job_id_error::JobIdError;
+pub use validation_error::ValidationError;
+pub use yaml_error::YamlError;
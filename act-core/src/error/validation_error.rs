@@ -0,0 +1,158 @@
+use std::fmt;
+
+use serde_valid::validation::{ArrayErrors, Error, Errors, ObjectErrors};
+
+use crate::expression::Span;
+use crate::yaml::Location;
+
+/// An error that occurs when a deserialized value breaks a rule, e.g. an empty `concurrency`
+/// group or two exclusive filters used together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The path of the offending value, e.g. `concurrency.group`.
+    path: String,
+
+    /// The rule that is broken.
+    message: String,
+
+    /// Where the value is in the source, if known.
+    location: Option<Location>,
+
+    /// The byte offsets the value spans in the source, if known.
+    span: Option<Span>,
+}
+
+impl ValidationError {
+    /// Create a new validation error for the value at the given path.
+    pub fn new(
+        path: impl Into<String>,
+        message: impl Into<String>,
+        location: Option<Location>,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+            location,
+            span: None,
+        }
+    }
+
+    /// Flatten nested validation errors into one error per broken rule, without locations.
+    pub fn flatten(errors: &Errors) -> Vec<ValidationError> {
+        let mut out = vec![];
+        flatten(errors, "", &mut out);
+        out
+    }
+
+    /// The path of the offending value, empty for the root value.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The rule that is broken.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Set where the value is in the source.
+    pub fn with_location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// Where the value is in the source, if known.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    /// Set the byte offsets the value spans in the source.
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// The byte offsets the value spans in the source, if known.
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+}
+
+fn flatten(errors: &Errors, path: &str, out: &mut Vec<ValidationError>) {
+    match errors {
+        Errors::Object(object) => flatten_object(object, path, out),
+        Errors::Array(array) => flatten_array(array, path, out),
+        Errors::NewType(errors) => flatten_errors(errors, path, out),
+    }
+}
+
+fn flatten_object(object: &ObjectErrors, path: &str, out: &mut Vec<ValidationError>) {
+    flatten_errors(&object.errors, path, out);
+    for (key, errors) in &object.properties {
+        let path = if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        };
+        flatten(errors, &path, out);
+    }
+}
+
+fn flatten_array(array: &ArrayErrors, path: &str, out: &mut Vec<ValidationError>) {
+    flatten_errors(&array.errors, path, out);
+    for (index, errors) in &array.items {
+        flatten(errors, &format!("{}[{}]", path, index), out);
+    }
+}
+
+/// Nested fields report their errors as properties or items of an error of their parent.
+fn flatten_errors(errors: &[Error], path: &str, out: &mut Vec<ValidationError>) {
+    for error in errors {
+        match error {
+            Error::Properties(object) => flatten_object(object, path, out),
+            Error::Items(array) => flatten_array(array, path, out),
+            error => out.push(ValidationError::new(path, error.to_string(), None)),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_valid::Validate;
+
+    use super::*;
+    use crate::workflow::Concurrency;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            format!(
+                "{}",
+                ValidationError::new("concurrency.group", "must not be empty", None)
+            ),
+            "concurrency.group: must not be empty"
+        );
+    }
+
+    #[test]
+    fn flatten() {
+        let concurrency: Concurrency = serde_yaml::from_str("group: ''").unwrap();
+        assert_eq!(
+            ValidationError::flatten(&concurrency.validate().unwrap_err()),
+            vec![ValidationError::new(
+                "group",
+                "The length of the value must be `>= 1`.",
+                None
+            )]
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+use std::path::Path;
+
+use crate::yaml::Location;
+
+/// An error that occurs when a source is not valid YAML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YamlError {
+    /// What is wrong with the source.
+    message: String,
+
+    /// Where the error is in the source.
+    location: Location,
+}
+
+impl YamlError {
+    /// Create a new YAML error at the given location.
+    pub fn new(message: impl Into<String>, location: Location) -> Self {
+        Self {
+            message: message.into(),
+            location,
+        }
+    }
+
+    /// Set the file the source was read from.
+    pub fn with_file(mut self, file: &Path) -> Self {
+        self.location.file = Some(file.to_path_buf());
+        self
+    }
+
+    /// What is wrong with the source.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Where the error is in the source.
+    pub fn location(&self) -> &Location {
+        &self.location
+    }
+}
+
+impl fmt::Display for YamlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid YAML: {}", self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            format!(
+                "{}",
+                YamlError::new(
+                    "mapping values are not allowed in this context",
+                    Location::default()
+                )
+            ),
+            "invalid YAML: mapping values are not allowed in this context"
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use std::fmt;
+
+/// A range of byte offsets in the string an expression was parsed from.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct Span {
+    /// Offset of the first byte.
+    pub start: usize,
+
+    /// Offset one past the last byte.
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// The smallest span covering both spans.
+    pub fn to(self, other: Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// A node of the expression syntax tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+impl Expr {
+    pub fn new(kind: ExprKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+/// The kind of an expression syntax tree node.
+///
+/// <https://docs.github.com/en/actions/learn-github-actions/expressions>
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprKind {
+    /// `null`
+    Null,
+
+    /// `true` or `false`
+    Bool(bool),
+
+    /// A number literal, e.g. `42`, `-9.2` or `0xff`.
+    Number(f64),
+
+    /// A single quoted string literal, e.g. `'it''s'`.
+    String(String),
+
+    /// A context name, e.g. `github`.
+    Context(String),
+
+    /// A property dereference, e.g. `github.ref`.
+    Property { object: Box<Expr>, name: String },
+
+    /// An index dereference, e.g. `github['ref']` or `matrix.os[0]`.
+    Index { object: Box<Expr>, index: Box<Expr> },
+
+    /// An object filter, e.g. `github.event.issue.labels.*`.
+    Filter { object: Box<Expr> },
+
+    /// A function call, e.g. `contains(github.ref, 'main')`.
+    Call { name: String, args: Vec<Expr> },
+
+    /// A unary operation, e.g. `!success()`.
+    Unary { op: UnaryOp, operand: Box<Expr> },
+
+    /// A binary operation, e.g. `github.ref == 'refs/heads/main'`.
+    Binary {
+        op: BinaryOp,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+}
+
+/// A unary operator.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum UnaryOp {
+    /// `!`
+    Not,
+}
+
+/// A binary operator.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BinaryOp {
+    /// `&&`
+    And,
+    /// `||`
+    Or,
+    /// `==`
+    Eq,
+    /// `!=`
+    Ne,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+}
+
+impl fmt::Display for UnaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnaryOp::Not => write!(f, "!"),
+        }
+    }
+}
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self {
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+            BinaryOp::Eq => "==",
+            BinaryOp::Ne => "!=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Le => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Ge => ">=",
+        };
+        write!(f, "{}", op)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn span_to() {
+        assert_eq!(Span::new(4, 6).to(Span::new(1, 2)), Span::new(1, 6));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", Span::new(1, 3)), "1..3");
+        assert_eq!(format!("{}", BinaryOp::Le), "<=");
+        assert_eq!(format!("{}", UnaryOp::Not), "!");
+    }
+}
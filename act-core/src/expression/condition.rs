@@ -0,0 +1,151 @@
+use std::fmt;
+
+use serde::de::{Error, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::expression::{evaluator, parser, Context, Expr, Value};
+use crate::ExpressionError;
+
+/// The condition of an `if:`, an expression with or without `${{ }}` around it, e.g.
+/// `github.ref == 'refs/heads/main'` or `${{ always() }}`.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idif>
+#[derive(Debug, Clone)]
+pub struct Condition {
+    source: String,
+    ast: Expr,
+}
+
+impl Condition {
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// The syntax tree of the condition, spans are byte offsets into [Condition::as_str].
+    pub fn ast(&self) -> &Expr {
+        &self.ast
+    }
+
+    /// Evaluate the condition against a context, GitHub runs the job or step when the value
+    /// is truthy.
+    pub fn evaluate(&self, context: &Context) -> Result<Value, crate::Error> {
+        evaluator::evaluate(&self.source, &self.ast, context).map_err(crate::Error::Evaluation)
+    }
+
+    pub fn new(s: &str) -> Result<Self, crate::Error> {
+        Self::parse(s).map_err(crate::Error::Expression)
+    }
+
+    fn parse(s: &str) -> Result<Self, ExpressionError> {
+        let trimmed = s.trim_end();
+        let ast = match trimmed
+            .strip_prefix("${{")
+            .and_then(|rest| rest.strip_suffix("}}"))
+        {
+            Some(_) => parser::parse_range(s, 3, trimmed.len() - 2)?,
+            None => parser::parse(s)?,
+        };
+        Ok(Condition {
+            source: s.to_string(),
+            ast,
+        })
+    }
+}
+
+impl PartialEq for Condition {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for Condition {}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+struct ConditionVisitor;
+
+impl<'de> Visitor<'de> for ConditionVisitor {
+    type Value = Condition;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a boolean or an expression")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Condition::parse(v).map_err(Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Condition {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ConditionVisitor)
+    }
+}
+
+impl Serialize for Condition {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::from_str;
+
+    use super::*;
+    use crate::expression::{ExprKind, Span};
+
+    #[test]
+    fn deserialize() {
+        let c: Condition = from_str("github.ref == 'refs/heads/main'").unwrap();
+        assert!(matches!(c.ast().kind, ExprKind::Binary { .. }));
+        let c: Condition = from_str("${{ always() }}").unwrap();
+        assert_eq!(c.as_str(), "${{ always() }}");
+        assert_eq!(c.ast().span, Span::new(4, 12));
+        let c: Condition = from_str("[true]")
+            .map(|c: Vec<Condition>| c[0].clone())
+            .unwrap();
+        assert_eq!(c.evaluate(&Context::default()).unwrap(), Value::from(true));
+    }
+
+    #[test]
+    fn deserialize_err() {
+        assert_eq!(
+            from_str::<Condition>("github.ref = 'main'")
+                .unwrap_err()
+                .to_string(),
+            Condition::new("github.ref = 'main'")
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(from_str::<Condition>("${{ a == }}").is_err());
+        assert!(from_str::<Condition>("${{ a }} && ${{ b }}").is_err());
+        assert!(from_str::<Condition>("[a]").is_err());
+    }
+
+    #[test]
+    fn evaluate() {
+        let c = Condition::new("${{ startsWith('refs/tags/v1', 'refs/tags/') }}").unwrap();
+        assert_eq!(c.evaluate(&Context::default()).unwrap(), Value::from(true));
+    }
+}
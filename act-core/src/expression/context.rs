@@ -0,0 +1,152 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::expression::Value;
+
+/// The contexts an expression is evaluated against.
+///
+/// A context can be loaded from a JSON or YAML document with a key per context, e.g. a sample
+/// `github` event payload to dry-run `if:` conditions.
+///
+/// <https://docs.github.com/en/actions/learn-github-actions/contexts>
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Context {
+    /// Information about the workflow run and the event that triggered it.
+    pub github: Value,
+
+    /// Variables set in the workflow, job or step.
+    pub env: Value,
+
+    /// Configuration variables set at the repository, organization or environment level.
+    pub vars: Value,
+
+    /// Inputs of a reusable or manually triggered workflow.
+    pub inputs: Value,
+
+    /// The matrix combination of the current job.
+    pub matrix: Value,
+
+    /// Outputs and results of the jobs the current job depends on.
+    pub needs: Value,
+
+    /// Outputs and outcomes of the steps of the current job that have an id.
+    pub steps: Value,
+
+    /// Information about the current job.
+    pub job: Value,
+
+    /// Information about the runner executing the current job.
+    pub runner: Value,
+
+    /// Secrets available to the workflow run.
+    pub secrets: Value,
+
+    /// The matrix execution strategy of the current job.
+    pub strategy: Value,
+
+    /// The status of the previous steps, used by the status check functions.
+    pub status: JobStatus,
+
+    /// The directory `hashFiles` resolves its patterns against.
+    #[serde(skip)]
+    pub workspace: PathBuf,
+}
+
+impl Context {
+    /// The context with the given name, context names are case-insensitive.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        let context = match name.to_ascii_lowercase().as_str() {
+            "github" => &self.github,
+            "env" => &self.env,
+            "vars" => &self.vars,
+            "inputs" => &self.inputs,
+            "matrix" => &self.matrix,
+            "needs" => &self.needs,
+            "steps" => &self.steps,
+            "job" => &self.job,
+            "runner" => &self.runner,
+            "secrets" => &self.secrets,
+            "strategy" => &self.strategy,
+            _ => return None,
+        };
+        Some(context)
+    }
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self {
+            github: Value::default(),
+            env: Value::default(),
+            vars: Value::default(),
+            inputs: Value::default(),
+            matrix: Value::default(),
+            needs: Value::default(),
+            steps: Value::default(),
+            job: Value::default(),
+            runner: Value::default(),
+            secrets: Value::default(),
+            strategy: Value::default(),
+            status: JobStatus::default(),
+            workspace: PathBuf::from("."),
+        }
+    }
+}
+
+/// The status of the previous steps of a job.
+///
+/// <https://docs.github.com/en/actions/learn-github-actions/expressions#status-check-functions>
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    /// All previous steps succeeded.
+    #[default]
+    Success,
+
+    /// A previous step failed.
+    Failure,
+
+    /// The workflow run was canceled.
+    Cancelled,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::from_str;
+
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let context: Context = from_str(
+            r#"
+        github:
+          ref: refs/heads/main
+        status: failure
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            context.get("GitHub").and_then(|github| github.get("ref")),
+            Some(&Value::from("refs/heads/main"))
+        );
+        assert_eq!(context.status, JobStatus::Failure);
+        assert_eq!(context.env, Value::Null);
+        assert_eq!(context.workspace, PathBuf::from("."));
+    }
+
+    #[test]
+    fn deserialize_err() {
+        assert_eq!(
+            from_str::<Context>("gihub: {}").unwrap_err().to_string(),
+            "unknown field `gihub`, expected one of `github`, `env`, `vars`, `inputs`, `matrix`, `needs`, `steps`, `job`, `runner`, `secrets`, `strategy`, `status`"
+        );
+    }
+
+    #[test]
+    fn get_unknown() {
+        assert_eq!(Context::default().get("foo"), None);
+    }
+}
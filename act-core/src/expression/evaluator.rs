@@ -0,0 +1,251 @@
+use std::cmp::Ordering;
+
+use crate::expression::{functions, BinaryOp, Context, Expr, ExprKind, Span, UnaryOp, Value};
+use crate::EvaluationError;
+
+/// Evaluate the syntax tree of an expression against a context.
+///
+/// `source` is the string the tree was parsed from, it is used for error messages.
+///
+/// <https://docs.github.com/en/actions/learn-github-actions/expressions>
+pub fn evaluate(source: &str, expr: &Expr, context: &Context) -> Result<Value, EvaluationError> {
+    Evaluator { source, context }
+        .operand(expr)
+        .map(Operand::into_value)
+}
+
+/// The result of evaluating a node, an object filter yields the matching values so that
+/// further dereferences apply to each of them, e.g. `labels.*.name`.
+enum Operand {
+    Value(Value),
+    Filtered(Vec<Value>),
+}
+
+impl Operand {
+    fn into_value(self) -> Value {
+        match self {
+            Operand::Value(value) => value,
+            Operand::Filtered(values) => Value::Array(values),
+        }
+    }
+}
+
+pub(crate) struct Evaluator<'a> {
+    source: &'a str,
+    pub(crate) context: &'a Context,
+}
+
+impl<'a> Evaluator<'a> {
+    pub(crate) fn error(&self, message: impl Into<String>, span: Span) -> EvaluationError {
+        EvaluationError::new(self.source, message, span)
+    }
+
+    pub(crate) fn value(&self, expr: &Expr) -> Result<Value, EvaluationError> {
+        self.operand(expr).map(Operand::into_value)
+    }
+
+    fn operand(&self, expr: &Expr) -> Result<Operand, EvaluationError> {
+        let value = match &expr.kind {
+            ExprKind::Null => Value::Null,
+            ExprKind::Bool(b) => Value::Bool(*b),
+            ExprKind::Number(n) => Value::Number(*n),
+            ExprKind::String(s) => Value::String(s.clone()),
+            ExprKind::Context(name) => match self.context.get(name) {
+                Some(value) => value.clone(),
+                None => return Err(self.error(format!("unknown context `{}`", name), expr.span)),
+            },
+            ExprKind::Property { object, name } => {
+                return self.operand(object).map(|object| match object {
+                    Operand::Value(value) => Operand::Value(property(&value, name)),
+                    Operand::Filtered(values) => Operand::Filtered(
+                        values
+                            .iter()
+                            .map(|value| property(value, name))
+                            .filter(|value| *value != Value::Null)
+                            .collect(),
+                    ),
+                })
+            }
+            ExprKind::Index { object, index } => {
+                let object = self.operand(object)?;
+                let index = self.value(index)?;
+                return Ok(match object {
+                    Operand::Value(value) => Operand::Value(self::index(&value, &index)),
+                    Operand::Filtered(values) => Operand::Filtered(
+                        values
+                            .iter()
+                            .map(|value| self::index(value, &index))
+                            .filter(|value| *value != Value::Null)
+                            .collect(),
+                    ),
+                });
+            }
+            ExprKind::Filter { object } => {
+                return self.operand(object).map(|object| match object {
+                    Operand::Value(value) => Operand::Filtered(filter(value)),
+                    Operand::Filtered(values) => {
+                        Operand::Filtered(values.into_iter().flat_map(filter).collect())
+                    }
+                })
+            }
+            ExprKind::Call { name, args } => functions::call(self, name, args, expr.span)?,
+            ExprKind::Unary {
+                op: UnaryOp::Not,
+                operand,
+            } => Value::Bool(!self.value(operand)?.is_truthy()),
+            ExprKind::Binary { op, left, right } => {
+                let left = self.value(left)?;
+                match op {
+                    BinaryOp::And if !left.is_truthy() => left,
+                    BinaryOp::Or if left.is_truthy() => left,
+                    BinaryOp::And | BinaryOp::Or => self.value(right)?,
+                    BinaryOp::Eq => Value::Bool(left.loose_eq(&self.value(right)?)),
+                    BinaryOp::Ne => Value::Bool(!left.loose_eq(&self.value(right)?)),
+                    BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
+                        let ordering = left.loose_cmp(&self.value(right)?);
+                        Value::Bool(matches!(
+                            (op, ordering),
+                            (BinaryOp::Lt, Some(Ordering::Less))
+                                | (BinaryOp::Le, Some(Ordering::Less | Ordering::Equal))
+                                | (BinaryOp::Gt, Some(Ordering::Greater))
+                                | (BinaryOp::Ge, Some(Ordering::Greater | Ordering::Equal))
+                        ))
+                    }
+                }
+            }
+        };
+        Ok(Operand::Value(value))
+    }
+}
+
+/// `object.name`, `null` if the object has no such property.
+fn property(object: &Value, name: &str) -> Value {
+    object.get(name).cloned().unwrap_or_default()
+}
+
+/// `object[index]`, arrays are indexed by number and objects by property name.
+fn index(object: &Value, index: &Value) -> Value {
+    match object {
+        Value::Array(values) => {
+            let i = index.to_number();
+            if i.is_nan() || i < 0.0 {
+                return Value::Null;
+            }
+            values.get(i as usize).cloned().unwrap_or_default()
+        }
+        Value::Object(_) => property(object, &index.to_string()),
+        _ => Value::Null,
+    }
+}
+
+/// `object.*`, the elements of an array or the property values of an object.
+fn filter(object: Value) -> Vec<Value> {
+    match object {
+        Value::Array(values) => values,
+        Value::Object(map) => map.into_values().collect(),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::expression::parser;
+
+    fn context() -> Context {
+        serde_json::from_value(json!({
+            "github": {
+                "ref": "refs/heads/main",
+                "event_name": "push",
+                "event": {
+                    "issue": {
+                        "labels": [{"name": "bug"}, {"name": "help wanted"}, {}]
+                    }
+                }
+            },
+            "matrix": {"os": ["ubuntu-latest", "windows-latest"], "node": 18},
+            "steps": {"build": {"outputs": {"result": "ok"}}}
+        }))
+        .unwrap()
+    }
+
+    fn eval(source: &str) -> Result<Value, EvaluationError> {
+        let expr = parser::parse(source).unwrap();
+        evaluate(source, &expr, &context())
+    }
+
+    #[test]
+    fn literals() {
+        assert_eq!(eval("null").unwrap(), Value::Null);
+        assert_eq!(eval("0xff").unwrap(), Value::from(255.0));
+        assert_eq!(eval("'it''s'").unwrap(), Value::from("it's"));
+    }
+
+    #[test]
+    fn dereference() {
+        assert_eq!(eval("github.ref").unwrap(), Value::from("refs/heads/main"));
+        assert_eq!(
+            eval("GITHUB['Ref']").unwrap(),
+            Value::from("refs/heads/main")
+        );
+        assert_eq!(eval("matrix.os[1]").unwrap(), Value::from("windows-latest"));
+        assert_eq!(eval("matrix.os[2]").unwrap(), Value::Null);
+        assert_eq!(
+            eval("steps.build.outputs.result").unwrap(),
+            Value::from("ok")
+        );
+        assert_eq!(eval("steps.test.outputs.result").unwrap(), Value::Null);
+    }
+
+    #[test]
+    fn filter() {
+        assert_eq!(
+            eval("github.event.issue.labels.*.name").unwrap(),
+            Value::from(vec![Value::from("bug"), Value::from("help wanted")])
+        );
+        assert_eq!(
+            eval("matrix.*").unwrap(),
+            Value::from(vec![
+                Value::from(vec![
+                    Value::from("ubuntu-latest"),
+                    Value::from("windows-latest")
+                ]),
+                Value::from(18.0)
+            ])
+        );
+    }
+
+    #[test]
+    fn logical() {
+        assert_eq!(eval("github.ref && 'yes'").unwrap(), Value::from("yes"));
+        assert_eq!(eval("'' && 'yes'").unwrap(), Value::from(""));
+        assert_eq!(eval("null || 'default'").unwrap(), Value::from("default"));
+        assert_eq!(eval("!github.ref").unwrap(), Value::from(false));
+    }
+
+    #[test]
+    fn comparison() {
+        assert_eq!(
+            eval("github.ref == 'REFS/HEADS/MAIN'").unwrap(),
+            Value::from(true)
+        );
+        assert_eq!(eval("matrix.node >= '18'").unwrap(), Value::from(true));
+        assert_eq!(eval("matrix.node < 18").unwrap(), Value::from(false));
+        assert_eq!(eval("'abc' < 1").unwrap(), Value::from(false));
+        assert_eq!(eval("github.event != null").unwrap(), Value::from(true));
+    }
+
+    #[test]
+    fn unknown_context_err() {
+        assert_eq!(
+            eval("github.ref == foo.bar").unwrap_err(),
+            EvaluationError::new(
+                "github.ref == foo.bar",
+                "unknown context `foo`",
+                Span::new(14, 17)
+            )
+        );
+    }
+}
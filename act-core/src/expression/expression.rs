@@ -5,7 +5,8 @@ use regex::Regex;
 use serde::de::{Error, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
 
-use crate::error_expression;
+use crate::expression::{evaluator, parser, Context, Expr, Value};
+use crate::{error_expression, ExpressionError};
 
 pub static VALID_EXPRESSION_PATTERN: &str = r#"^\$\{\{(.|[\r\n])*\}\}$"#;
 
@@ -13,26 +14,58 @@ lazy_static! {
     static ref RE: Regex = Regex::new(VALID_EXPRESSION_PATTERN).unwrap();
 }
 
-/// a string that should match `^\$\{\{(.|[\r\n])*\}\}$`
-#[derive(Debug, Eq, PartialEq)]
-pub struct Expression(String);
+/// A string that should match `^\$\{\{(.|[\r\n])*\}\}$`, parsed into an expression syntax tree.
+///
+/// <https://docs.github.com/en/actions/learn-github-actions/expressions>
+#[derive(Debug, Clone)]
+pub struct Expression {
+    source: String,
+    ast: Expr,
+}
 
 impl Expression {
     pub fn as_str(&self) -> &str {
-        &self.0
+        &self.source
+    }
+
+    /// The syntax tree of the expression, spans are byte offsets into [Expression::as_str].
+    pub fn ast(&self) -> &Expr {
+        &self.ast
+    }
+
+    /// Evaluate the expression against a context, e.g. to dry-run an `if:` condition.
+    pub fn evaluate(&self, context: &Context) -> Result<Value, crate::Error> {
+        evaluator::evaluate(&self.source, &self.ast, context).map_err(crate::Error::Evaluation)
     }
+
     pub fn new(s: &str) -> Result<Self, crate::Error> {
-        if RE.is_match(s) {
-            Err(error_expression!(s))
-        } else {
-            Ok(Expression(s.to_string()))
+        if !RE.is_match(s) {
+            return Err(error_expression!(s));
         }
+        Self::parse(s).map_err(crate::Error::Expression)
+    }
+
+    /// Parse the inside of `${{ ... }}`, `s` must match the expression pattern.
+    fn parse(s: &str) -> Result<Self, ExpressionError> {
+        let ast = parser::parse_range(s, 3, s.len() - 2)?;
+        Ok(Expression {
+            source: s.to_string(),
+            ast,
+        })
     }
 }
 
+impl PartialEq for Expression {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for Expression {}
+
 impl fmt::Display for Expression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.source)
     }
 }
 
@@ -55,7 +88,7 @@ impl<'de> Visitor<'de> for ExpressionVisitor {
         if !RE.is_match(v) {
             Err(Error::invalid_value(serde::de::Unexpected::Str(v), &self))
         } else {
-            Ok(Expression(v.to_string()))
+            Expression::parse(v).map_err(Error::custom)
         }
     }
 }
@@ -74,14 +107,7 @@ impl Serialize for Expression {
     where
         S: serde::Serializer,
     {
-        if !RE.is_match(&self.0) {
-            Err(serde::ser::Error::custom(format!(
-                "invalid value: Expression({:?}), expected a string matching {}",
-                self.0, VALID_EXPRESSION_PATTERN
-            )))
-        } else {
-            serializer.serialize_str(&self.0)
-        }
+        serializer.serialize_str(&self.source)
     }
 }
 
@@ -90,20 +116,52 @@ mod tests {
     use serde_yaml::from_str;
 
     use super::*;
+    use crate::expression::{ExprKind, Span};
 
     #[test]
-    fn serialize() {
+    fn new() {
+        let e = Expression::new("${{ github.ref }}").unwrap();
+        assert_eq!(e.as_str(), "${{ github.ref }}");
+        assert_eq!(e.ast().span, Span::new(4, 14));
+        assert!(matches!(e.ast().kind, ExprKind::Property { .. }));
+    }
+
+    #[test]
+    fn new_err() {
         assert_eq!(
-            serde_yaml::to_string(&Expression("${{ x }}".to_string())).unwrap(),
-            "${{ x }}\n"
+            Expression::new("abc").unwrap_err(),
+            error_expression!("abc")
+        );
+        assert_eq!(
+            Expression::new("${{ a == }}").unwrap_err().to_string(),
+            r#"not a valid string expression: "${{ a == }}", unexpected end of expression, expected an operand at position 9"#
+        );
+    }
+
+    #[test]
+    fn evaluate() {
+        let e = Expression::new("${{ github.event_name == 'push' && !cancelled() }}").unwrap();
+        let context = Context {
+            github: Value::from(serde_json::json!({"event_name": "push"})),
+            ..Context::default()
+        };
+        assert_eq!(e.evaluate(&context).unwrap(), Value::Bool(true));
+    }
+
+    #[test]
+    fn evaluate_err() {
+        let e = Expression::new("${{ foo(1) }}").unwrap();
+        assert_eq!(
+            e.evaluate(&Context::default()).unwrap_err().to_string(),
+            r#"failed to evaluate expression: "${{ foo(1) }}", unknown function `foo` at position 4"#
         );
     }
 
     #[test]
-    fn serialize_err() {
+    fn serialize() {
         assert_eq!(
-            serde_yaml::to_string(&Expression("abc".to_string())).unwrap_err().to_string(),
-            "invalid value: Expression(\"abc\"), expected a string matching ^\\$\\{\\{(.|[\\r\\n])*\\}\\}$"
+            serde_yaml::to_string(&Expression::new("${{ x }}").unwrap()).unwrap(),
+            "${{ x }}\n"
         );
     }
 
@@ -111,7 +169,7 @@ mod tests {
     fn deserialize() {
         assert_eq!(
             from_str::<Expression>(r#"${{ x }}"#).unwrap(),
-            Expression("${{ x }}".to_string())
+            Expression::new("${{ x }}").unwrap()
         );
     }
 
@@ -123,10 +181,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deserialize_syntax_err() {
+        assert_eq!(
+            from_str::<Expression>("${{ github.ref = 'main' }}")
+                .unwrap_err()
+                .to_string(),
+            r#"not a valid string expression: "${{ github.ref = 'main' }}", unexpected `=`, did you mean `==`? at position 15"#
+        );
+    }
+
     #[test]
     fn display() {
         assert_eq!(
-            format!("{}", Expression("${{ x }}".to_string())),
+            format!("{}", Expression::new("${{ x }}").unwrap()),
             r#"${{ x }}"#
         );
     }
@@ -1,80 +1,185 @@
 use std::fmt;
 
-use lazy_static::lazy_static;
-use regex::Regex;
-use serde::{Deserialize, Deserializer, Serialize};
 use serde::de::{Error, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
 
-use crate::error_expression;
+use crate::expression::{evaluator, parser, Context, Expr, Span};
+use crate::ExpressionError;
 
-pub static VALID_EXPRESSION_PATTERN: &str = r#"^.*\$\{\{(.|[\r\n])*\}\}.*$"#;
-
-lazy_static! {
-    static ref RE: Regex = Regex::new(VALID_EXPRESSION_PATTERN).unwrap();
+/// A string with any number of `${{ ... }}` expressions, e.g. `Deploy ${{ github.ref_name }}`.
+///
+/// The string is split into literal and expression segments, every expression is parsed.
+///
+/// <https://docs.github.com/en/actions/learn-github-actions/expressions>
+#[derive(Debug, Clone)]
+pub struct ExpressionInString {
+    source: String,
+    segments: Vec<Segment>,
 }
 
-/// a string that should match `^.*\$\{\{(.|[\r\n])*\}\}.*$`
-#[derive(Debug, Eq, PartialEq)]
-pub struct ExpressionInString(String);
+/// A part of an [ExpressionInString], spans are byte offsets into the whole string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    /// Text outside of any expression.
+    Literal { text: String, span: Span },
+
+    /// A `${{ ... }}` expression, the span includes the delimiters.
+    Expression { expr: Expr, span: Span },
+}
 
 impl ExpressionInString {
     pub fn as_str(&self) -> &str {
-        &self.0
+        &self.source
+    }
+
+    /// The literal and expression segments of the string, in order.
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// The syntax trees of the expressions in the string, in order.
+    pub fn expressions(&self) -> impl Iterator<Item = &Expr> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Expression { expr, .. } => Some(expr),
+            Segment::Literal { .. } => None,
+        })
     }
+
     pub fn new(s: &str) -> Result<Self, crate::Error> {
-        if RE.is_match(s) {
-            Ok(ExpressionInString(s.to_string()))
-        } else {
-            Err(error_expression!(s))
+        Self::parse(s).map_err(crate::Error::Expression)
+    }
+
+    /// Render the string, replacing every expression with its value coerced to a string.
+    pub fn interpolate(&self, context: &Context) -> Result<String, crate::Error> {
+        let mut result = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal { text, .. } => result.push_str(text),
+                Segment::Expression { expr, .. } => {
+                    let value = evaluator::evaluate(&self.source, expr, context)
+                        .map_err(crate::Error::Evaluation)?;
+                    result.push_str(&value.to_string());
+                }
+            }
         }
+        Ok(result)
     }
-}
 
-impl fmt::Display for ExpressionInString {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+    fn parse(s: &str) -> Result<Self, ExpressionError> {
+        let mut segments = vec![];
+        let mut pos = 0;
+        while let Some(offset) = s[pos..].find("${{") {
+            let start = pos + offset;
+            if start > pos {
+                segments.push(Segment::Literal {
+                    text: s[pos..start].to_string(),
+                    span: Span::new(pos, start),
+                });
+            }
+            let end = closing_braces(s, start + 3).ok_or_else(|| {
+                ExpressionError::syntax(
+                    s,
+                    "unclosed `${{`, expected `}}`",
+                    Span::new(start, start + 3),
+                )
+            })?;
+            segments.push(Segment::Expression {
+                expr: parser::parse_range(s, start + 3, end)?,
+                span: Span::new(start, end + 2),
+            });
+            pos = end + 2;
+        }
+        if pos < s.len() {
+            segments.push(Segment::Literal {
+                text: s[pos..].to_string(),
+                span: Span::new(pos, s.len()),
+            });
+        }
+        Ok(ExpressionInString {
+            source: s.to_string(),
+            segments,
+        })
     }
 }
 
+/// The pattern of a string with at least one `${{ ... }}` expression.
+#[deprecated(note = "use `ExpressionInString::new` and check `ExpressionInString::expressions`")]
+pub static VALID_EXPRESSION_PATTERN: &str = r#"^.*\$\{\{(.|[\r\n])*\}\}.*$"#;
+
+/// Create an [ExpressionInString] from a string that must contain at least one expression.
+#[deprecated(
+    note = "use `ExpressionInString::new`, which also accepts strings without expressions"
+)]
 #[macro_export]
 macro_rules! expression_in_string {
     ($($arg:tt)+) => {
-        if RE.is_match($($arg)+) {
-            Ok(ExpressionInString($($arg)+.to_string()))
-        } else {
-            Err(error_expression!($($arg)+))
-        }
+        $crate::expression::expression_in_string::with_expressions($($arg)+)
     };
 }
 
+/// The implementation of [expression_in_string!].
+#[doc(hidden)]
+pub fn with_expressions(s: &str) -> Result<ExpressionInString, crate::Error> {
+    let value = ExpressionInString::new(s)?;
+    if value.expressions().next().is_none() {
+        return Err(crate::error_expression!(s));
+    }
+    Ok(value)
+}
+
+/// The offset of the `}}` closing the expression starting at `start`, braces inside string
+/// literals are skipped.
+fn closing_braces(s: &str, start: usize) -> Option<usize> {
+    let mut in_string = false;
+    let bytes = s.as_bytes();
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            // `''` escapes a quote, toggling twice leaves the state unchanged
+            b'\'' => in_string = !in_string,
+            b'}' if !in_string && bytes.get(i + 1) == Some(&b'}') => return Some(i),
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+impl PartialEq for ExpressionInString {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for ExpressionInString {}
+
+impl fmt::Display for ExpressionInString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
 struct ExpressionInStringVisitor;
 
 impl<'de> Visitor<'de> for ExpressionInStringVisitor {
     type Value = ExpressionInString;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_fmt(format_args!(
-            "a string matching {}",
-            VALID_EXPRESSION_PATTERN
-        ))
+        formatter.write_str("a string with `${{ ... }}` expressions")
     }
 
     fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-        where
-            E: Error,
+    where
+        E: Error,
     {
-        if RE.is_match(v) {
-            Ok(ExpressionInString(v.to_string()))
-        } else {
-            Err(Error::invalid_value(serde::de::Unexpected::Str(v), &self))
-        }
+        ExpressionInString::parse(v).map_err(Error::custom)
     }
 }
 
 impl<'de> Deserialize<'de> for ExpressionInString {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
+    where
+        D: Deserializer<'de>,
     {
         deserializer.deserialize_str(ExpressionInStringVisitor)
     }
@@ -82,17 +187,10 @@ impl<'de> Deserialize<'de> for ExpressionInString {
 
 impl Serialize for ExpressionInString {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: serde::Serializer,
+    where
+        S: serde::Serializer,
     {
-        if RE.is_match(&self.0) {
-            serializer.serialize_str(&self.0)
-        } else {
-            Err(serde::ser::Error::custom(format!(
-                "invalid value: Expression({:?}), expected a string matching {}",
-                self.0, VALID_EXPRESSION_PATTERN
-            )))
-        }
+        serializer.serialize_str(&self.source)
     }
 }
 
@@ -101,20 +199,111 @@ mod tests {
     use serde_yaml::from_str;
 
     use super::*;
+    use crate::expression::{ExprKind, Value};
 
     #[test]
-    fn serialize() {
+    #[allow(deprecated)]
+    fn deprecated_macro() {
+        let s = crate::expression_in_string!("Deploy ${{ github.ref_name }}").unwrap();
+        assert_eq!(s.expressions().count(), 1);
         assert_eq!(
-            serde_yaml::to_string(&ExpressionInString("x=${{ x }}".to_string())).unwrap(),
-            "x=${{ x }}\n"
+            crate::expression_in_string!("Deploy")
+                .unwrap_err()
+                .to_string(),
+            r#"not a valid string expression: "Deploy""#
+        );
+        assert!(crate::expression_in_string!("${{ a ==  }}").is_err());
+        let re = regex::Regex::new(VALID_EXPRESSION_PATTERN).unwrap();
+        assert!(re.is_match("Deploy ${{ github.ref_name }}"));
+    }
+
+    #[test]
+    fn segments() {
+        let s = ExpressionInString::new("x=${{ a }}, y=${{ '}}' }}").unwrap();
+        let segments = s.segments();
+        assert_eq!(segments.len(), 4);
+        assert_eq!(
+            segments[0],
+            Segment::Literal {
+                text: "x=".to_string(),
+                span: Span::new(0, 2)
+            }
+        );
+        assert!(matches!(
+            &segments[1],
+            Segment::Expression { expr, span } if expr.kind == ExprKind::Context("a".to_string())
+                && *span == Span::new(2, 10)
+        ));
+        assert_eq!(
+            segments[2],
+            Segment::Literal {
+                text: ", y=".to_string(),
+                span: Span::new(10, 14)
+            }
         );
+        assert!(matches!(
+            &segments[3],
+            Segment::Expression { expr, span } if expr.kind == ExprKind::String("}}".to_string())
+                && *span == Span::new(14, 25)
+        ));
+        assert_eq!(s.expressions().count(), 2);
+    }
+
+    #[test]
+    fn literal_only() {
+        let s = ExpressionInString::new("Build").unwrap();
+        assert_eq!(s.expressions().count(), 0);
+        assert_eq!(s.interpolate(&Context::default()).unwrap(), "Build");
+    }
+
+    #[test]
+    fn interpolate() {
+        let s =
+            ExpressionInString::new("Deploy ${{ github.ref_name }} (${{ matrix.node }})").unwrap();
+        let context = Context {
+            github: Value::from(serde_json::json!({"ref_name": "main"})),
+            matrix: Value::from(serde_json::json!({"node": 18})),
+            ..Context::default()
+        };
+        assert_eq!(s.interpolate(&context).unwrap(), "Deploy main (18)");
     }
 
     #[test]
-    fn serialize_err() {
+    fn interpolate_err() {
+        let s = ExpressionInString::new("a ${{ foo }}").unwrap();
         assert_eq!(
-            serde_yaml::to_string(&ExpressionInString("abc".to_string())).unwrap_err().to_string(),
-            "invalid value: Expression(\"abc\"), expected a string matching ^.*\\$\\{\\{(.|[\\r\\n])*\\}\\}.*$"
+            s.interpolate(&Context::default()).unwrap_err().to_string(),
+            r#"failed to evaluate expression: "a ${{ foo }}", unknown context `foo` at position 6"#
+        );
+    }
+
+    #[test]
+    fn unclosed_err() {
+        assert_eq!(
+            ExpressionInString::new("a ${{ b }} ${{ c").unwrap_err(),
+            crate::Error::Expression(ExpressionError::syntax(
+                "a ${{ b }} ${{ c",
+                "unclosed `${{`, expected `}}`",
+                Span::new(11, 14)
+            ))
+        );
+    }
+
+    #[test]
+    fn syntax_err() {
+        assert_eq!(
+            ExpressionInString::new("x=${{ a == }}")
+                .unwrap_err()
+                .to_string(),
+            r#"not a valid string expression: "x=${{ a == }}", unexpected end of expression, expected an operand at position 11"#
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!(
+            serde_yaml::to_string(&ExpressionInString::new("x=${{ x }}").unwrap()).unwrap(),
+            "x=${{ x }}\n"
         );
     }
 
@@ -122,22 +311,24 @@ mod tests {
     fn deserialize() {
         assert_eq!(
             from_str::<ExpressionInString>(r#"${{ x }}"#).unwrap(),
-            ExpressionInString("${{ x }}".to_string())
+            ExpressionInString::new("${{ x }}").unwrap()
         );
     }
 
     #[test]
     fn deserialize_err() {
         assert_eq!(
-            from_str::<ExpressionInString>("abc").unwrap_err().to_string(),
-            "invalid value: string \"abc\", expected a string matching ^.*\\$\\{\\{(.|[\\r\\n])*\\}\\}.*$"
+            from_str::<ExpressionInString>("abc ${{ x")
+                .unwrap_err()
+                .to_string(),
+            r#"not a valid string expression: "abc ${{ x", unclosed `${{`, expected `}}` at position 4"#
         );
     }
 
     #[test]
     fn display() {
         assert_eq!(
-            format!("{}", ExpressionInString("${{ x }}".to_string())),
+            format!("{}", ExpressionInString::new("${{ x }}").unwrap()),
             r#"${{ x }}"#
         );
     }
@@ -0,0 +1,444 @@
+use std::fs;
+use std::path::Path;
+
+use glob::{MatchOptions, Pattern};
+use sha2::{Digest, Sha256};
+
+use crate::expression::context::JobStatus;
+use crate::expression::evaluator::Evaluator;
+use crate::expression::{Expr, Span, Value};
+use crate::EvaluationError;
+
+/// Call a built-in function, function names are case-insensitive.
+///
+/// <https://docs.github.com/en/actions/learn-github-actions/expressions#functions>
+pub(crate) fn call(
+    evaluator: &Evaluator,
+    name: &str,
+    args: &[Expr],
+    span: Span,
+) -> Result<Value, EvaluationError> {
+    let (min, max) = match arity(name) {
+        Some(arity) => arity,
+        None => return Err(evaluator.error(format!("unknown function `{}`", name), span)),
+    };
+    if args.len() < min || args.len() > max {
+        let expected = match (min, max) {
+            (min, max) if min == max => min.to_string(),
+            (min, usize::MAX) => format!("at least {}", min),
+            (min, max) => format!("{} to {}", min, max),
+        };
+        return Err(evaluator.error(
+            format!(
+                "`{}` expects {} argument(s), found {}",
+                name,
+                expected,
+                args.len()
+            ),
+            span,
+        ));
+    }
+    let args = args
+        .iter()
+        .map(|arg| evaluator.value(arg))
+        .collect::<Result<Vec<_>, _>>()?;
+    let status = evaluator.context.status;
+    let value = match name.to_ascii_lowercase().as_str() {
+        "contains" => Value::Bool(contains(&args[0], &args[1])),
+        "startswith" => Value::Bool(
+            args[0]
+                .to_string()
+                .to_uppercase()
+                .starts_with(&args[1].to_string().to_uppercase()),
+        ),
+        "endswith" => Value::Bool(
+            args[0]
+                .to_string()
+                .to_uppercase()
+                .ends_with(&args[1].to_string().to_uppercase()),
+        ),
+        "format" => format(&args[0].to_string(), &args[1..])
+            .map(Value::String)
+            .map_err(|message| evaluator.error(message, span))?,
+        "join" => Value::String(join(&args[0], args.get(1))),
+        "tojson" => Value::String(
+            serde_json::to_string_pretty(&serde_json::Value::from(&args[0]))
+                .map_err(|err| evaluator.error(format!("`toJSON` failed: {}", err), span))?,
+        ),
+        "fromjson" => serde_json::from_str::<serde_json::Value>(&args[0].to_string())
+            .map(Value::from)
+            .map_err(|err| {
+                evaluator.error(format!("`fromJSON` got invalid JSON: {}", err), span)
+            })?,
+        "hashfiles" => hash_files(&evaluator.context.workspace, &args)
+            .map(Value::String)
+            .map_err(|message| evaluator.error(message, span))?,
+        "success" => Value::Bool(status == JobStatus::Success),
+        "failure" => Value::Bool(status == JobStatus::Failure),
+        "cancelled" => Value::Bool(status == JobStatus::Cancelled),
+        "always" => Value::Bool(true),
+        _ => unreachable!("every function with an arity is implemented"),
+    };
+    Ok(value)
+}
+
+/// The minimum and maximum number of arguments of a built-in function.
+fn arity(name: &str) -> Option<(usize, usize)> {
+    let arity = match name.to_ascii_lowercase().as_str() {
+        "contains" | "startswith" | "endswith" => (2, 2),
+        "format" | "hashfiles" => (1, usize::MAX),
+        "join" => (1, 2),
+        "tojson" | "fromjson" => (1, 1),
+        "success" | "failure" | "cancelled" | "always" => (0, 0),
+        _ => return None,
+    };
+    Some(arity)
+}
+
+/// `contains(search, item)`, whether an array has an element loosely equal to the item or a
+/// string contains the item, case-insensitively.
+fn contains(search: &Value, item: &Value) -> bool {
+    match search {
+        Value::Array(values) => values.iter().any(|value| value.loose_eq(item)),
+        _ => search
+            .to_string()
+            .to_uppercase()
+            .contains(&item.to_string().to_uppercase()),
+    }
+}
+
+/// `format(string, replaceValue0, ...)`, replaces `{N}` with the Nth value, `{{` and `}}` escape
+/// braces.
+fn format(string: &str, values: &[Value]) -> Result<String, String> {
+    let mut result = String::new();
+    let mut chars = string.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '{' if chars.peek().map(|(_, c)| *c) == Some('{') => {
+                chars.next();
+                result.push('{');
+            }
+            '}' if chars.peek().map(|(_, c)| *c) == Some('}') => {
+                chars.next();
+                result.push('}');
+            }
+            '{' => {
+                let end = string[i..]
+                    .find('}')
+                    .map(|end| i + end)
+                    .ok_or_else(|| format!("invalid format string `{}`, unclosed `{{`", string))?;
+                let index: usize = string[i + 1..end].parse().map_err(|_| {
+                    format!(
+                        "invalid format string `{}`, `{}` is not an argument index",
+                        string,
+                        &string[i..=end]
+                    )
+                })?;
+                let value = values.get(index).ok_or_else(|| {
+                    format!(
+                        "invalid format string `{}`, there is no argument {}",
+                        string, index
+                    )
+                })?;
+                result.push_str(&value.to_string());
+                while chars.next_if(|(j, _)| *j <= end).is_some() {}
+            }
+            '}' => {
+                return Err(format!(
+                    "invalid format string `{}`, unescaped `}}`, use `}}}}`",
+                    string
+                ))
+            }
+            c => result.push(c),
+        }
+    }
+    Ok(result)
+}
+
+/// `join(array, separator)`, the elements of an array joined by the separator, `,` by default.
+fn join(array: &Value, separator: Option<&Value>) -> String {
+    let separator = separator.map_or(",".to_string(), Value::to_string);
+    match array {
+        Value::Array(values) => values
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join(&separator),
+        value => value.to_string(),
+    }
+}
+
+/// `hashFiles(path, ...)`, the SHA-256 of the SHA-256 of every file below the workspace matching
+/// the patterns, an empty string if no file matches. Patterns starting with `!` exclude files.
+fn hash_files(workspace: &Path, patterns: &[Value]) -> Result<String, String> {
+    let patterns = patterns
+        .iter()
+        .map(|pattern| {
+            let pattern = pattern.to_string();
+            let (exclude, glob) = match pattern.strip_prefix('!') {
+                Some(glob) => (true, glob.to_string()),
+                None => (false, pattern.clone()),
+            };
+            Pattern::new(glob.trim_start_matches("./"))
+                .map(|glob| (exclude, glob))
+                .map_err(|err| format!("invalid `hashFiles` pattern `{}`: {}", pattern, err))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let options = MatchOptions {
+        require_literal_separator: true,
+        ..MatchOptions::default()
+    };
+    let mut files = vec![];
+    walk(workspace, "", &mut files).map_err(|err| {
+        format!(
+            "`hashFiles` failed to read {}: {}",
+            workspace.display(),
+            err
+        )
+    })?;
+    files.sort();
+    let mut hasher = Sha256::new();
+    let mut matched = false;
+    for file in files {
+        let mut included = false;
+        for (exclude, pattern) in &patterns {
+            if pattern.matches_with(&file, options) {
+                included = !exclude;
+            }
+        }
+        if included {
+            let content = fs::read(workspace.join(&file))
+                .map_err(|err| format!("`hashFiles` failed to read {}: {}", file, err))?;
+            hasher.update(Sha256::digest(&content));
+            matched = true;
+        }
+    }
+    if !matched {
+        return Ok(String::new());
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Collect the paths of the files below a directory, relative to it and separated by `/`.
+///
+/// Symbolic links to directories are skipped, they may form a cycle.
+fn walk(dir: &Path, prefix: &str, files: &mut Vec<String>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            walk(&entry.path(), &format!("{}/", name), files)?;
+        } else if !(file_type.is_symlink() && entry.path().is_dir()) {
+            files.push(name);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::expression::{evaluator, parser, Context};
+
+    fn eval(source: &str) -> Result<Value, EvaluationError> {
+        eval_with(source, &Context::default())
+    }
+
+    fn eval_with(source: &str, context: &Context) -> Result<Value, EvaluationError> {
+        let expr = parser::parse(source).unwrap();
+        evaluator::evaluate(source, &expr, context)
+    }
+
+    #[test]
+    fn contains() {
+        assert_eq!(
+            eval("contains('Hello world', 'WORLD')").unwrap(),
+            Value::from(true)
+        );
+        assert_eq!(
+            eval("contains(fromJSON('[\"push\", 1]'), '1')").unwrap(),
+            Value::from(true)
+        );
+        assert_eq!(
+            eval("contains(fromJSON('[]'), 'a')").unwrap(),
+            Value::from(false)
+        );
+    }
+
+    #[test]
+    fn starts_with_ends_with() {
+        assert_eq!(
+            eval("startsWith('Hello', 'he')").unwrap(),
+            Value::from(true)
+        );
+        assert_eq!(eval("endsWith('Hello', 'LO')").unwrap(), Value::from(true));
+        assert_eq!(eval("endsWith('Hello', 'he')").unwrap(), Value::from(false));
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(
+            eval("format('{{Hello {0} {1} {2}!}}', 'Mona', 'the', 'Octocat')").unwrap(),
+            Value::from("{Hello Mona the Octocat!}")
+        );
+        assert_eq!(eval("format('{0}{0}', 1)").unwrap(), Value::from("11"));
+    }
+
+    #[test]
+    fn format_err() {
+        assert_eq!(
+            eval("format('{1}', 'a')").unwrap_err().message(),
+            "invalid format string `{1}`, there is no argument 1"
+        );
+        assert_eq!(
+            eval("format('{a}')").unwrap_err().message(),
+            "invalid format string `{a}`, `{a}` is not an argument index"
+        );
+        assert_eq!(
+            eval("format('a}')").unwrap_err().message(),
+            "invalid format string `a}`, unescaped `}`, use `}}`"
+        );
+    }
+
+    #[test]
+    fn join() {
+        assert_eq!(
+            eval("join(fromJSON('[\"a\", 1, true]'))").unwrap(),
+            Value::from("a,1,true")
+        );
+        assert_eq!(
+            eval("join(fromJSON('[\"a\", \"b\"]'), ', ')").unwrap(),
+            Value::from("a, b")
+        );
+        assert_eq!(eval("join('abc', ', ')").unwrap(), Value::from("abc"));
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            eval("toJSON(fromJSON('{\"a\": [1, null]}'))").unwrap(),
+            Value::from("{\n  \"a\": [\n    1,\n    null\n  ]\n}")
+        );
+        assert_eq!(eval("fromJSON('true')").unwrap(), Value::from(true));
+    }
+
+    #[test]
+    fn from_json_err() {
+        assert_eq!(
+            eval("fromJSON('{')").unwrap_err().message(),
+            "`fromJSON` got invalid JSON: EOF while parsing an object at line 1 column 1"
+        );
+    }
+
+    #[test]
+    fn hash_files() {
+        let workspace =
+            std::env::temp_dir().join(format!("act-core-hash-files-{}", std::process::id()));
+        fs::create_dir_all(workspace.join("src")).unwrap();
+        fs::write(workspace.join("Cargo.lock"), "lock").unwrap();
+        fs::write(workspace.join("src/Cargo.lock"), "nested").unwrap();
+        let context = Context {
+            workspace: workspace.clone(),
+            ..Context::default()
+        };
+        let all = eval_with("hashFiles('**/Cargo.lock')", &context).unwrap();
+        let root = eval_with("hashFiles('**/Cargo.lock', '!src/**')", &context).unwrap();
+        let none = eval_with("hashFiles('*.toml')", &context).unwrap();
+        fs::remove_dir_all(&workspace).unwrap();
+
+        let root_digest = Sha256::digest(Sha256::digest(b"lock"))
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        assert_eq!(root, Value::from(root_digest));
+        assert_ne!(all, root);
+        assert_eq!(none, Value::from(""));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn hash_files_symlink_cycle() {
+        let workspace =
+            std::env::temp_dir().join(format!("act-core-hash-files-cycle-{}", std::process::id()));
+        fs::create_dir_all(workspace.join("src")).unwrap();
+        fs::write(workspace.join("src/Cargo.lock"), "lock").unwrap();
+        std::os::unix::fs::symlink(&workspace, workspace.join("src/loop")).unwrap();
+        let context = Context {
+            workspace: workspace.clone(),
+            ..Context::default()
+        };
+        let all = eval_with("hashFiles('**/Cargo.lock')", &context).unwrap();
+        fs::remove_dir_all(&workspace).unwrap();
+
+        let digest = Sha256::digest(Sha256::digest(b"lock"))
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        assert_eq!(all, Value::from(digest));
+    }
+
+    #[test]
+    fn hash_files_err() {
+        let context = Context {
+            workspace: PathBuf::from("does-not-exist"),
+            ..Context::default()
+        };
+        assert!(eval_with("hashFiles('*')", &context)
+            .unwrap_err()
+            .message()
+            .starts_with("`hashFiles` failed to read does-not-exist"));
+    }
+
+    #[test]
+    fn status() {
+        assert_eq!(eval("success()").unwrap(), Value::from(true));
+        assert_eq!(eval("always()").unwrap(), Value::from(true));
+        let context = Context {
+            status: JobStatus::Cancelled,
+            ..Context::default()
+        };
+        assert_eq!(
+            eval_with("cancelled()", &context).unwrap(),
+            Value::from(true)
+        );
+        assert_eq!(
+            eval_with("failure() || success()", &context).unwrap(),
+            Value::from(false)
+        );
+    }
+
+    #[test]
+    fn unknown_function_err() {
+        assert_eq!(
+            eval("startWith('a', 'b')").unwrap_err(),
+            EvaluationError::new(
+                "startWith('a', 'b')",
+                "unknown function `startWith`",
+                Span::new(0, 19)
+            )
+        );
+    }
+
+    #[test]
+    fn arity_err() {
+        assert_eq!(
+            eval("contains('a')").unwrap_err().message(),
+            "`contains` expects 2 argument(s), found 1"
+        );
+        assert_eq!(
+            eval("format()").unwrap_err().message(),
+            "`format` expects at least 1 argument(s), found 0"
+        );
+        assert_eq!(
+            eval("join('a', 'b', 'c')").unwrap_err().message(),
+            "`join` expects 1 to 2 argument(s), found 3"
+        );
+    }
+}
@@ -0,0 +1,338 @@
+use std::fmt;
+
+use crate::expression::Span;
+use crate::ExpressionError;
+
+/// A token of the expression syntax.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Null,
+    True,
+    False,
+    Number(f64),
+    String(String),
+    Ident(String),
+    Dot,
+    Star,
+    Comma,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Not,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+    End,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Null => write!(f, "`null`"),
+            Token::True => write!(f, "`true`"),
+            Token::False => write!(f, "`false`"),
+            Token::Number(n) => write!(f, "number `{}`", n),
+            Token::String(s) => write!(f, "string '{}'", s),
+            Token::Ident(i) => write!(f, "identifier `{}`", i),
+            Token::Dot => write!(f, "`.`"),
+            Token::Star => write!(f, "`*`"),
+            Token::Comma => write!(f, "`,`"),
+            Token::LParen => write!(f, "`(`"),
+            Token::RParen => write!(f, "`)`"),
+            Token::LBracket => write!(f, "`[`"),
+            Token::RBracket => write!(f, "`]`"),
+            Token::Not => write!(f, "`!`"),
+            Token::Eq => write!(f, "`==`"),
+            Token::Ne => write!(f, "`!=`"),
+            Token::Lt => write!(f, "`<`"),
+            Token::Le => write!(f, "`<=`"),
+            Token::Gt => write!(f, "`>`"),
+            Token::Ge => write!(f, "`>=`"),
+            Token::And => write!(f, "`&&`"),
+            Token::Or => write!(f, "`||`"),
+            Token::End => write!(f, "end of expression"),
+        }
+    }
+}
+
+/// Splits the `start..end` range of a string into expression tokens.
+pub struct Lexer<'a> {
+    source: &'a str,
+    pos: usize,
+    end: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(source: &'a str, start: usize, end: usize) -> Self {
+        Self {
+            source,
+            pos: start,
+            end,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.pos..self.end].chars().next()
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        self.source[self.pos..self.end].chars().nth(1)
+    }
+
+    fn error(&self, message: impl Into<String>, start: usize) -> ExpressionError {
+        ExpressionError::syntax(self.source, message, Span::new(start, self.pos))
+    }
+
+    /// Returns the next token and its span, [Token::End] once the range is exhausted.
+    pub fn next_token(&mut self) -> Result<(Token, Span), ExpressionError> {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += self.peek().unwrap().len_utf8();
+        }
+        let start = self.pos;
+        let Some(c) = self.peek() else {
+            return Ok((Token::End, Span::new(start, start)));
+        };
+        let two = |lexer: &mut Self, token: Token| {
+            lexer.pos += 2;
+            token
+        };
+        let one = |lexer: &mut Self, token: Token| {
+            lexer.pos += 1;
+            token
+        };
+        let token = match (c, self.peek_second()) {
+            ('=', Some('=')) => two(self, Token::Eq),
+            ('!', Some('=')) => two(self, Token::Ne),
+            ('<', Some('=')) => two(self, Token::Le),
+            ('>', Some('=')) => two(self, Token::Ge),
+            ('&', Some('&')) => two(self, Token::And),
+            ('|', Some('|')) => two(self, Token::Or),
+            ('!', _) => one(self, Token::Not),
+            ('<', _) => one(self, Token::Lt),
+            ('>', _) => one(self, Token::Gt),
+            ('.', _) => one(self, Token::Dot),
+            ('*', _) => one(self, Token::Star),
+            (',', _) => one(self, Token::Comma),
+            ('(', _) => one(self, Token::LParen),
+            (')', _) => one(self, Token::RParen),
+            ('[', _) => one(self, Token::LBracket),
+            (']', _) => one(self, Token::RBracket),
+            ('\'', _) => self.string()?,
+            ('-', Some(d)) if d.is_ascii_digit() => self.number()?,
+            (d, _) if d.is_ascii_digit() => self.number()?,
+            (a, _) if a.is_ascii_alphabetic() || a == '_' => self.ident(),
+            ('=', _) => {
+                self.pos += 1;
+                return Err(self.error("unexpected `=`, did you mean `==`?", start));
+            }
+            _ => {
+                self.pos += c.len_utf8();
+                return Err(self.error(format!("unexpected character `{}`", c), start));
+            }
+        };
+        Ok((token, Span::new(start, self.pos)))
+    }
+
+    fn string(&mut self) -> Result<Token, ExpressionError> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                Some('\'') if self.peek_second() == Some('\'') => {
+                    value.push('\'');
+                    self.pos += 2;
+                }
+                Some('\'') => {
+                    self.pos += 1;
+                    return Ok(Token::String(value));
+                }
+                Some(c) => {
+                    value.push(c);
+                    self.pos += c.len_utf8();
+                }
+                None => return Err(self.error("unterminated string literal", start)),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Token, ExpressionError> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'))
+        {
+            // a sign is only part of a number at its start or right after an exponent
+            let c = self.peek().unwrap();
+            if matches!(c, '-' | '+')
+                && self.pos != start
+                && !matches!(self.source[..self.pos].chars().last(), Some('e' | 'E'))
+            {
+                break;
+            }
+            self.pos += 1;
+        }
+        let literal = &self.source[start..self.pos];
+        let (negative, digits) = match literal.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, literal),
+        };
+        let value = match digits.strip_prefix("0x") {
+            Some(hex) => i64::from_str_radix(hex, 16).ok().map(|v| v as f64),
+            None if digits.starts_with(|c: char| c.is_ascii_digit()) => digits.parse().ok(),
+            None => None,
+        };
+        match value {
+            Some(v) if negative => Ok(Token::Number(-v)),
+            Some(v) => Ok(Token::Number(v)),
+            None => Err(self.error(format!("invalid number `{}`", literal), start)),
+        }
+    }
+
+    fn ident(&mut self) -> Token {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            self.pos += 1;
+        }
+        match &self.source[start..self.pos] {
+            "null" => Token::Null,
+            "true" => Token::True,
+            "false" => Token::False,
+            "NaN" => Token::Number(f64::NAN),
+            "Infinity" => Token::Number(f64::INFINITY),
+            ident => Token::Ident(ident.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(s: &str) -> Result<Vec<Token>, ExpressionError> {
+        let mut lexer = Lexer::new(s, 0, s.len());
+        let mut tokens = vec![];
+        loop {
+            match lexer.next_token()? {
+                (Token::End, _) => return Ok(tokens),
+                (token, _) => tokens.push(token),
+            }
+        }
+    }
+
+    #[test]
+    fn operators() {
+        assert_eq!(
+            tokens("! != == < <= > >= && || . * , ( ) [ ]").unwrap(),
+            vec![
+                Token::Not,
+                Token::Ne,
+                Token::Eq,
+                Token::Lt,
+                Token::Le,
+                Token::Gt,
+                Token::Ge,
+                Token::And,
+                Token::Or,
+                Token::Dot,
+                Token::Star,
+                Token::Comma,
+                Token::LParen,
+                Token::RParen,
+                Token::LBracket,
+                Token::RBracket,
+            ]
+        );
+    }
+
+    #[test]
+    fn literals() {
+        assert_eq!(
+            tokens("null true false 42 -1.5 2e3 0xff 'it''s'").unwrap(),
+            vec![
+                Token::Null,
+                Token::True,
+                Token::False,
+                Token::Number(42.0),
+                Token::Number(-1.5),
+                Token::Number(2000.0),
+                Token::Number(255.0),
+                Token::String("it's".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn idents() {
+        assert_eq!(
+            tokens("steps.my-step.outputs").unwrap(),
+            vec![
+                Token::Ident("steps".to_string()),
+                Token::Dot,
+                Token::Ident("my-step".to_string()),
+                Token::Dot,
+                Token::Ident("outputs".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn spans() {
+        let mut lexer = Lexer::new("${{ a == 'b' }}", 3, 13);
+        assert_eq!(
+            lexer.next_token().unwrap(),
+            (Token::Ident("a".to_string()), Span::new(4, 5))
+        );
+        assert_eq!(lexer.next_token().unwrap(), (Token::Eq, Span::new(6, 8)));
+        assert_eq!(
+            lexer.next_token().unwrap(),
+            (Token::String("b".to_string()), Span::new(9, 12))
+        );
+        assert_eq!(lexer.next_token().unwrap(), (Token::End, Span::new(13, 13)));
+    }
+
+    #[test]
+    fn unterminated_string_err() {
+        assert_eq!(
+            tokens("a == 'b").unwrap_err(),
+            ExpressionError::syntax("a == 'b", "unterminated string literal", Span::new(5, 7))
+        );
+    }
+
+    #[test]
+    fn single_equal_err() {
+        assert_eq!(
+            tokens("a = b").unwrap_err(),
+            ExpressionError::syntax(
+                "a = b",
+                "unexpected `=`, did you mean `==`?",
+                Span::new(2, 3)
+            )
+        );
+    }
+
+    #[test]
+    fn invalid_number_err() {
+        assert_eq!(
+            tokens("12abc").unwrap_err(),
+            ExpressionError::syntax("12abc", "invalid number `12abc`", Span::new(0, 5))
+        );
+    }
+
+    #[test]
+    fn unexpected_character_err() {
+        assert_eq!(
+            tokens("a + b").unwrap_err(),
+            ExpressionError::syntax("a + b", "unexpected character `+`", Span::new(2, 3))
+        );
+    }
+}
@@ -1,5 +1,19 @@
+pub use ast::{BinaryOp, Expr, ExprKind, Span, UnaryOp};
+pub use condition::Condition;
+pub use context::{Context, JobStatus};
+pub use evaluator::evaluate;
 pub use expression::Expression;
-pub use expression_in_string::ExpressionInString;
+pub use expression_in_string::{ExpressionInString, Segment};
+pub use value::Value;
 
+pub mod ast;
+pub mod condition;
+pub mod context;
+pub mod evaluator;
+#[allow(clippy::module_inception)]
 pub mod expression;
 pub mod expression_in_string;
+mod functions;
+pub mod lexer;
+pub mod parser;
+pub mod value;
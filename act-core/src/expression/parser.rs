@@ -0,0 +1,437 @@
+use crate::expression::lexer::{Lexer, Token};
+use crate::expression::{BinaryOp, Expr, ExprKind, Span, UnaryOp};
+use crate::ExpressionError;
+
+/// Parse a bare expression, e.g. `github.ref == 'refs/heads/main'`.
+///
+/// Spans of the returned tree are byte offsets into `source`.
+pub fn parse(source: &str) -> Result<Expr, ExpressionError> {
+    parse_range(source, 0, source.len())
+}
+
+/// Parse the bare expression in the `start..end` range of `source`, e.g. the inside of `${{ ... }}`.
+///
+/// Spans of the returned tree and of errors are byte offsets into `source`.
+pub fn parse_range(source: &str, start: usize, end: usize) -> Result<Expr, ExpressionError> {
+    let mut parser = Parser::new(source, start, end)?;
+    if parser.token == Token::End {
+        return Err(ExpressionError::syntax(
+            source,
+            "empty expression",
+            Span::new(start, end),
+        ));
+    }
+    let expr = parser.or()?;
+    if parser.token != Token::End {
+        return Err(parser.unexpected("an operator or end of expression"));
+    }
+    Ok(expr)
+}
+
+/// A recursive descent parser for the expression grammar.
+///
+/// From the lowest to the highest precedence: `||`, `&&`, `==` `!=`, `<` `<=` `>` `>=`, `!`,
+/// then property dereference, index, filter and function call.
+struct Parser<'a> {
+    source: &'a str,
+    lexer: Lexer<'a>,
+    token: Token,
+    span: Span,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str, start: usize, end: usize) -> Result<Self, ExpressionError> {
+        let mut lexer = Lexer::new(source, start, end);
+        let (token, span) = lexer.next_token()?;
+        Ok(Self {
+            source,
+            lexer,
+            token,
+            span,
+        })
+    }
+
+    /// Consume the current token, returning it with its span.
+    fn bump(&mut self) -> Result<(Token, Span), ExpressionError> {
+        let (token, span) = self.lexer.next_token()?;
+        let previous = std::mem::replace(&mut self.token, token);
+        let previous_span = std::mem::replace(&mut self.span, span);
+        Ok((previous, previous_span))
+    }
+
+    fn unexpected(&self, expected: &str) -> ExpressionError {
+        ExpressionError::syntax(
+            self.source,
+            format!("unexpected {}, expected {}", self.token, expected),
+            self.span,
+        )
+    }
+
+    fn expect(&mut self, token: Token) -> Result<Span, ExpressionError> {
+        if self.token == token {
+            self.bump().map(|(_, span)| span)
+        } else {
+            Err(self.unexpected(&token.to_string()))
+        }
+    }
+
+    fn binary(
+        &mut self,
+        operand: fn(&mut Self) -> Result<Expr, ExpressionError>,
+        operator: fn(&Token) -> Option<BinaryOp>,
+    ) -> Result<Expr, ExpressionError> {
+        let mut left = operand(self)?;
+        while let Some(op) = operator(&self.token) {
+            self.bump()?;
+            let right = operand(self)?;
+            let span = left.span.to(right.span);
+            left = Expr::new(
+                ExprKind::Binary {
+                    op,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+                span,
+            );
+        }
+        Ok(left)
+    }
+
+    fn or(&mut self) -> Result<Expr, ExpressionError> {
+        self.binary(Self::and, |t| (*t == Token::Or).then_some(BinaryOp::Or))
+    }
+
+    fn and(&mut self) -> Result<Expr, ExpressionError> {
+        self.binary(Self::equality, |t| {
+            (*t == Token::And).then_some(BinaryOp::And)
+        })
+    }
+
+    fn equality(&mut self) -> Result<Expr, ExpressionError> {
+        self.binary(Self::comparison, |t| match t {
+            Token::Eq => Some(BinaryOp::Eq),
+            Token::Ne => Some(BinaryOp::Ne),
+            _ => None,
+        })
+    }
+
+    fn comparison(&mut self) -> Result<Expr, ExpressionError> {
+        self.binary(Self::unary, |t| match t {
+            Token::Lt => Some(BinaryOp::Lt),
+            Token::Le => Some(BinaryOp::Le),
+            Token::Gt => Some(BinaryOp::Gt),
+            Token::Ge => Some(BinaryOp::Ge),
+            _ => None,
+        })
+    }
+
+    fn unary(&mut self) -> Result<Expr, ExpressionError> {
+        if self.token == Token::Not {
+            let (_, start) = self.bump()?;
+            let operand = self.unary()?;
+            let span = start.to(operand.span);
+            return Ok(Expr::new(
+                ExprKind::Unary {
+                    op: UnaryOp::Not,
+                    operand: Box::new(operand),
+                },
+                span,
+            ));
+        }
+        self.postfix()
+    }
+
+    fn postfix(&mut self) -> Result<Expr, ExpressionError> {
+        let mut expr = self.primary()?;
+        loop {
+            let start = expr.span;
+            match self.token {
+                Token::Dot => {
+                    self.bump()?;
+                    let (token, span) = self.bump()?;
+                    let kind = match token {
+                        Token::Star => ExprKind::Filter {
+                            object: Box::new(expr),
+                        },
+                        Token::Ident(name) => ExprKind::Property {
+                            object: Box::new(expr),
+                            name,
+                        },
+                        Token::Null | Token::True | Token::False => ExprKind::Property {
+                            object: Box::new(expr),
+                            name: self.source[span.start..span.end].to_string(),
+                        },
+                        token => {
+                            return Err(ExpressionError::syntax(
+                                self.source,
+                                format!("unexpected {}, expected a property name or `*`", token),
+                                span,
+                            ))
+                        }
+                    };
+                    expr = Expr::new(kind, start.to(span));
+                }
+                Token::LBracket => {
+                    self.bump()?;
+                    let kind = if self.token == Token::Star {
+                        self.bump()?;
+                        ExprKind::Filter {
+                            object: Box::new(expr),
+                        }
+                    } else {
+                        let index = self.or()?;
+                        ExprKind::Index {
+                            object: Box::new(expr),
+                            index: Box::new(index),
+                        }
+                    };
+                    let end = self.expect(Token::RBracket)?;
+                    expr = Expr::new(kind, start.to(end));
+                }
+                _ => return Ok(expr),
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, ExpressionError> {
+        let kind = match &self.token {
+            Token::Null => ExprKind::Null,
+            Token::True => ExprKind::Bool(true),
+            Token::False => ExprKind::Bool(false),
+            Token::Number(n) => ExprKind::Number(*n),
+            Token::String(s) => ExprKind::String(s.clone()),
+            Token::Ident(_) => return self.context_or_call(),
+            Token::LParen => {
+                let (_, start) = self.bump()?;
+                let mut expr = self.or()?;
+                let end = self.expect(Token::RParen)?;
+                expr.span = start.to(end);
+                return Ok(expr);
+            }
+            _ => return Err(self.unexpected("an operand")),
+        };
+        let (_, span) = self.bump()?;
+        Ok(Expr::new(kind, span))
+    }
+
+    fn context_or_call(&mut self) -> Result<Expr, ExpressionError> {
+        let (Token::Ident(name), span) = self.bump()? else {
+            unreachable!("called on an identifier")
+        };
+        if self.token != Token::LParen {
+            return Ok(Expr::new(ExprKind::Context(name), span));
+        }
+        self.bump()?;
+        let mut args = vec![];
+        if self.token != Token::RParen {
+            args.push(self.or()?);
+            while self.token == Token::Comma {
+                self.bump()?;
+                args.push(self.or()?);
+            }
+        }
+        if self.token != Token::RParen {
+            return Err(self.unexpected("`,` or `)`"));
+        }
+        let (_, end) = self.bump()?;
+        Ok(Expr::new(ExprKind::Call { name, args }, span.to(end)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(name: &str, start: usize) -> Expr {
+        Expr::new(
+            ExprKind::Context(name.to_string()),
+            Span::new(start, start + name.len()),
+        )
+    }
+
+    #[test]
+    fn literal() {
+        assert_eq!(
+            parse("'main'").unwrap(),
+            Expr::new(ExprKind::String("main".to_string()), Span::new(0, 6))
+        );
+    }
+
+    #[test]
+    fn property() {
+        assert_eq!(
+            parse("github.ref").unwrap(),
+            Expr::new(
+                ExprKind::Property {
+                    object: Box::new(context("github", 0)),
+                    name: "ref".to_string()
+                },
+                Span::new(0, 10)
+            )
+        );
+    }
+
+    #[test]
+    fn index_and_filter() {
+        let expr = parse("github.event['labels'].*.name").unwrap();
+        assert_eq!(expr.span, Span::new(0, 29));
+        let ExprKind::Property { object, name } = expr.kind else {
+            panic!("expected a property")
+        };
+        assert_eq!(name, "name");
+        let ExprKind::Filter { object } = object.kind else {
+            panic!("expected a filter")
+        };
+        assert!(matches!(object.kind, ExprKind::Index { .. }));
+        assert_eq!(object.span, Span::new(0, 22));
+    }
+
+    #[test]
+    fn precedence() {
+        let expr = parse("!a || b == 'c' && d < 1").unwrap();
+        let ExprKind::Binary { op, left, right } = expr.kind else {
+            panic!("expected a binary operation")
+        };
+        assert_eq!(op, BinaryOp::Or);
+        assert!(matches!(
+            left.kind,
+            ExprKind::Unary {
+                op: UnaryOp::Not,
+                ..
+            }
+        ));
+        let ExprKind::Binary { op, left, right } = right.kind else {
+            panic!("expected a binary operation")
+        };
+        assert_eq!(op, BinaryOp::And);
+        assert!(matches!(
+            left.kind,
+            ExprKind::Binary {
+                op: BinaryOp::Eq,
+                ..
+            }
+        ));
+        assert!(matches!(
+            right.kind,
+            ExprKind::Binary {
+                op: BinaryOp::Lt,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn parentheses() {
+        let expr = parse("(a || b) && c").unwrap();
+        let ExprKind::Binary { op, left, .. } = expr.kind else {
+            panic!("expected a binary operation")
+        };
+        assert_eq!(op, BinaryOp::And);
+        assert_eq!(left.span, Span::new(0, 8));
+    }
+
+    #[test]
+    fn call() {
+        assert_eq!(
+            parse("contains(github.ref, 'main')").unwrap(),
+            Expr::new(
+                ExprKind::Call {
+                    name: "contains".to_string(),
+                    args: vec![
+                        Expr::new(
+                            ExprKind::Property {
+                                object: Box::new(context("github", 9)),
+                                name: "ref".to_string()
+                            },
+                            Span::new(9, 19)
+                        ),
+                        Expr::new(ExprKind::String("main".to_string()), Span::new(21, 27)),
+                    ]
+                },
+                Span::new(0, 28)
+            )
+        );
+        assert_eq!(
+            parse("success()").unwrap().kind,
+            ExprKind::Call {
+                name: "success".to_string(),
+                args: vec![]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_range_spans() {
+        let source = "${{ github.ref }}";
+        assert_eq!(parse_range(source, 3, 15).unwrap().span, Span::new(4, 14));
+    }
+
+    #[test]
+    fn missing_operand_err() {
+        let source = "${{ github.ref == }}";
+        assert_eq!(
+            parse_range(source, 3, 18).unwrap_err(),
+            ExpressionError::syntax(
+                source,
+                "unexpected end of expression, expected an operand",
+                Span::new(18, 18)
+            )
+        );
+    }
+
+    #[test]
+    fn empty_err() {
+        assert_eq!(
+            parse_range("${{ }}", 3, 4).unwrap_err(),
+            ExpressionError::syntax("${{ }}", "empty expression", Span::new(3, 4))
+        );
+    }
+
+    #[test]
+    fn trailing_token_err() {
+        assert_eq!(
+            parse("a b").unwrap_err(),
+            ExpressionError::syntax(
+                "a b",
+                "unexpected identifier `b`, expected an operator or end of expression",
+                Span::new(2, 3)
+            )
+        );
+    }
+
+    #[test]
+    fn unclosed_call_err() {
+        assert_eq!(
+            parse("format('{0}', a").unwrap_err(),
+            ExpressionError::syntax(
+                "format('{0}', a",
+                "unexpected end of expression, expected `,` or `)`",
+                Span::new(15, 15)
+            )
+        );
+    }
+
+    #[test]
+    fn unclosed_index_err() {
+        assert_eq!(
+            parse("a['b'").unwrap_err(),
+            ExpressionError::syntax(
+                "a['b'",
+                "unexpected end of expression, expected `]`",
+                Span::new(5, 5)
+            )
+        );
+    }
+
+    #[test]
+    fn property_name_err() {
+        assert_eq!(
+            parse("github.'ref'").unwrap_err(),
+            ExpressionError::syntax(
+                "github.'ref'",
+                "unexpected string 'ref', expected a property name or `*`",
+                Span::new(7, 12)
+            )
+        );
+    }
+}
@@ -0,0 +1,316 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The value of an evaluated expression or of a context.
+///
+/// <https://docs.github.com/en/actions/learn-github-actions/expressions#literals>
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Value {
+    #[default]
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(IndexMap<String, Value>),
+}
+
+impl Value {
+    /// Whether the value is coerced to `true` in a condition.
+    ///
+    /// `null`, `false`, `0`, `NaN` and `''` are falsy, everything else is truthy.
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Null => false,
+            Value::Bool(b) => *b,
+            Value::Number(n) => *n != 0.0 && !n.is_nan(),
+            Value::String(s) => !s.is_empty(),
+            Value::Array(_) | Value::Object(_) => true,
+        }
+    }
+
+    /// The value coerced to a number, `NaN` if it has no numeric value.
+    pub fn to_number(&self) -> f64 {
+        match self {
+            Value::Null => 0.0,
+            Value::Bool(b) => f64::from(u8::from(*b)),
+            Value::Number(n) => *n,
+            Value::String(s) => parse_number(s),
+            Value::Array(_) | Value::Object(_) => f64::NAN,
+        }
+    }
+
+    /// The property of an object with the given name, property names are case-insensitive.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        match self {
+            Value::Object(map) => map.get(name).or_else(|| {
+                map.iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                    .map(|(_, value)| value)
+            }),
+            _ => None,
+        }
+    }
+
+    /// Loose equality, operands of different types are compared as numbers and strings are
+    /// compared case-insensitively.
+    pub fn loose_eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Null, Value::Null) => true,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Number(a), Value::Number(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a.to_uppercase() == b.to_uppercase(),
+            (Value::Array(_), Value::Array(_)) | (Value::Object(_), Value::Object(_)) => {
+                self == other
+            }
+            (Value::Array(_) | Value::Object(_), _) | (_, Value::Array(_) | Value::Object(_)) => {
+                false
+            }
+            _ => self.to_number() == other.to_number(),
+        }
+    }
+
+    /// Loose ordering, operands of different types are compared as numbers and strings are
+    /// compared case-insensitively. Arrays, objects and `NaN` are not ordered.
+    pub fn loose_cmp(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::String(a), Value::String(b)) => Some(a.to_uppercase().cmp(&b.to_uppercase())),
+            (Value::Array(_) | Value::Object(_), _) | (_, Value::Array(_) | Value::Object(_)) => {
+                None
+            }
+            _ => self.to_number().partial_cmp(&other.to_number()),
+        }
+    }
+}
+
+/// Parse a string as a number the way expressions coerce strings, `NaN` if it is not a number.
+fn parse_number(s: &str) -> f64 {
+    let s = s.trim();
+    if s.is_empty() {
+        return 0.0;
+    }
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let value = if let Some(hex) = digits.strip_prefix("0x") {
+        i64::from_str_radix(hex, 16).map_or(f64::NAN, |v| v as f64)
+    } else if digits == "Infinity" {
+        f64::INFINITY
+    } else if digits.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        && digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'))
+    {
+        digits.parse().unwrap_or(f64::NAN)
+    } else {
+        f64::NAN
+    };
+    if negative {
+        -value
+    } else {
+        value
+    }
+}
+
+/// The value coerced to a string, as used by `format`, `join` and string interpolation.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => Ok(()),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Number(n) if n.is_nan() => write!(f, "NaN"),
+            Value::Number(n) if n.is_infinite() && *n > 0.0 => write!(f, "Infinity"),
+            Value::Number(n) if n.is_infinite() => write!(f, "-Infinity"),
+            Value::Number(n) => write!(f, "{}", n),
+            Value::String(s) => write!(f, "{}", s),
+            Value::Array(_) => write!(f, "Array"),
+            Value::Object(_) => write!(f, "Object"),
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(n: f64) -> Self {
+        Value::Number(n)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(values: Vec<Value>) -> Self {
+        Value::Array(values)
+    }
+}
+
+impl From<IndexMap<String, Value>> for Value {
+    fn from(map: IndexMap<String, Value>) -> Self {
+        Value::Object(map)
+    }
+}
+
+impl From<serde_json::Value> for Value {
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(b) => Value::Bool(b),
+            serde_json::Value::Number(n) => Value::Number(n.as_f64().unwrap_or(f64::NAN)),
+            serde_json::Value::String(s) => Value::String(s),
+            serde_json::Value::Array(values) => {
+                Value::Array(values.into_iter().map(Value::from).collect())
+            }
+            serde_json::Value::Object(map) => Value::Object(
+                map.into_iter()
+                    .map(|(key, value)| (key, Value::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<&Value> for serde_json::Value {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Null => serde_json::Value::Null,
+            Value::Bool(b) => serde_json::Value::Bool(*b),
+            // whole numbers are written without a fraction, like `1` rather than `1.0`
+            Value::Number(n) if n.fract() == 0.0 && n.abs() < 2f64.powi(53) => {
+                serde_json::Value::from(*n as i64)
+            }
+            Value::Number(n) => serde_json::Number::from_f64(*n)
+                .map_or(serde_json::Value::Null, serde_json::Value::Number),
+            Value::String(s) => serde_json::Value::String(s.clone()),
+            Value::Array(values) => {
+                serde_json::Value::Array(values.iter().map(serde_json::Value::from).collect())
+            }
+            Value::Object(map) => serde_json::Value::Object(
+                map.iter()
+                    .map(|(key, value)| (key.clone(), serde_json::Value::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        serde_json::Value::deserialize(deserializer).map(Value::from)
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serde_json::Value::from(self).serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_truthy() {
+        assert!(!Value::Null.is_truthy());
+        assert!(!Value::from(0.0).is_truthy());
+        assert!(!Value::from(f64::NAN).is_truthy());
+        assert!(!Value::from("").is_truthy());
+        assert!(Value::from("false").is_truthy());
+        assert!(Value::Array(vec![]).is_truthy());
+    }
+
+    #[test]
+    fn to_number() {
+        assert_eq!(Value::Null.to_number(), 0.0);
+        assert_eq!(Value::from(true).to_number(), 1.0);
+        assert_eq!(Value::from("").to_number(), 0.0);
+        assert_eq!(Value::from(" 42 ").to_number(), 42.0);
+        assert_eq!(Value::from("-1.5").to_number(), -1.5);
+        assert_eq!(Value::from("0xff").to_number(), 255.0);
+        assert!(Value::from("inf").to_number().is_nan());
+        assert!(Value::from("abc").to_number().is_nan());
+        assert!(Value::Array(vec![]).to_number().is_nan());
+    }
+
+    #[test]
+    fn loose_eq() {
+        assert!(Value::from("ABC").loose_eq(&Value::from("abc")));
+        assert!(Value::from("1").loose_eq(&Value::from(1.0)));
+        assert!(Value::from(true).loose_eq(&Value::from(1.0)));
+        assert!(Value::Null.loose_eq(&Value::from(0.0)));
+        assert!(Value::Null.loose_eq(&Value::from(false)));
+        assert!(!Value::from("").loose_eq(&Value::Array(vec![])));
+        assert!(!Value::from(f64::NAN).loose_eq(&Value::from(f64::NAN)));
+    }
+
+    #[test]
+    fn loose_cmp() {
+        assert_eq!(
+            Value::from("a").loose_cmp(&Value::from("B")),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            Value::from("10").loose_cmp(&Value::from(9.0)),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(Value::from("a").loose_cmp(&Value::from(1.0)), None);
+    }
+
+    #[test]
+    fn get() {
+        let value = Value::from(serde_json::json!({"Ref": "main"}));
+        assert_eq!(value.get("ref"), Some(&Value::from("main")));
+        assert_eq!(value.get("sha"), None);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Value::Null.to_string(), "");
+        assert_eq!(Value::from(1.0).to_string(), "1");
+        assert_eq!(Value::from(1.5).to_string(), "1.5");
+        assert_eq!(Value::from(f64::INFINITY).to_string(), "Infinity");
+        assert_eq!(Value::Object(IndexMap::new()).to_string(), "Object");
+    }
+
+    #[test]
+    fn serialize() {
+        let value = Value::from(serde_json::json!({"a": [1, 1.5, null, "b"]}));
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"a":[1,1.5,null,"b"]}"#
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        assert_eq!(
+            serde_yaml::from_str::<Value>("os: [ubuntu-latest]").unwrap(),
+            Value::from(serde_json::json!({"os": ["ubuntu-latest"]}))
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use std::path::Path;
+
+use crate::diagnostic::Edit;
+use crate::{Diagnostics, Registry};
+
+/// How many times a source is linted and fixed at most, a fix can reveal other problems
+/// or make room for a fix that overlapped it.
+pub const MAX_PASSES: usize = 10;
+
+/// A source with the fixes of its problems applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fixed {
+    pub source: String,
+
+    /// How many fixes were applied.
+    pub fixes: usize,
+
+    /// The problems of the fixed source.
+    pub diagnostics: Diagnostics,
+}
+
+/// Replace spans of a source, the edits must not overlap.
+pub fn apply(source: &str, edits: &[Edit]) -> String {
+    let mut edits = edits.iter().collect::<Vec<_>>();
+    edits.sort_by_key(|edit| (edit.span.start, edit.span.end));
+    let mut fixed = String::with_capacity(source.len());
+    let mut offset = 0;
+    for edit in edits {
+        fixed.push_str(&source[offset..edit.span.start]);
+        fixed.push_str(&edit.replacement);
+        offset = edit.span.end;
+    }
+    fixed.push_str(&source[offset..]);
+    fixed
+}
+
+/// Lint a source and apply the fixes of its problems until no fix is left.
+///
+/// Fixes whose edits overlap the edits of a fix earlier in the source are left for the
+/// next pass, each pass lints the source fixed by the previous one.
+pub fn fix(registry: &Registry, source: &str, file: Option<&Path>) -> Fixed {
+    let mut source = source.to_string();
+    let mut fixes = 0;
+    let mut diagnostics = registry.lint(&source, file);
+    for _ in 0..MAX_PASSES {
+        let mut edits: Vec<&Edit> = vec![];
+        for fix in diagnostics
+            .iter()
+            .filter_map(|diagnostic| diagnostic.fix.as_ref())
+        {
+            if fix
+                .edits
+                .iter()
+                .all(|edit| edits.iter().all(|other| !overlap(edit, other)))
+            {
+                edits.extend(&fix.edits);
+                fixes += 1;
+            }
+        }
+        if edits.is_empty() {
+            break;
+        }
+        source = apply(&source, &edits.into_iter().cloned().collect::<Vec<_>>());
+        diagnostics = registry.lint(&source, file);
+    }
+    Fixed {
+        source,
+        fixes,
+        diagnostics,
+    }
+}
+
+/// Whether two edits touch the same bytes, or insert at the same offset in an order that
+/// would be arbitrary.
+fn overlap(a: &Edit, b: &Edit) -> bool {
+    (a.span.start < b.span.end && b.span.start < a.span.end) || a.span.start == b.span.start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expression::Span;
+
+    #[test]
+    fn apply_edits() {
+        let edits = [
+            Edit::new(Span::new(4, 8), "pull_request"),
+            Edit::new(Span::new(0, 0), "# CI\n"),
+        ];
+        assert_eq!(apply("on: push\n", &edits), "# CI\non: pull_request\n");
+    }
+
+    #[test]
+    fn fix_unused_suppressions() {
+        let source =
+            "# act-lint-disable-next-line AL005\non: push # act-lint-disable-next-line\nname: CI\n";
+        let fixed = fix(&Registry::builtin(), source, None);
+        assert_eq!(fixed.source, "on: push\nname: CI\n");
+        assert_eq!(fixed.fixes, 2);
+        assert!(fixed.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn fix_nothing() {
+        let source = "on: push\nname: [CI]\n";
+        let fixed = fix(&Registry::builtin(), source, None);
+        assert_eq!(fixed.source, source);
+        assert_eq!(fixed.fixes, 0);
+        assert_eq!(fixed.diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn overlapping() {
+        let a = Edit::new(Span::new(0, 4), "");
+        assert!(overlap(&a, &Edit::new(Span::new(3, 5), "")));
+        assert!(!overlap(&a, &Edit::new(Span::new(4, 5), "")));
+        assert!(overlap(
+            &Edit::new(Span::new(2, 2), "a"),
+            &Edit::new(Span::new(2, 2), "b")
+        ));
+    }
+}
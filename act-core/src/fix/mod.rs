@@ -0,0 +1,3 @@
+pub use fixer::{apply, fix, Fixed, MAX_PASSES};
+
+pub mod fixer;
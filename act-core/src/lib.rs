@@ -1,8 +1,27 @@
+pub use config::Config;
+pub use diagnostic::Diagnostic;
+pub use diagnostic::Diagnostics;
+pub use diagnostic::Severity;
 pub use error::Error;
+pub use error::EvaluationError;
 pub use error::ExpressionError;
+pub use error::JobIdError;
+pub use expression::Context;
 pub use expression::Expression;
+pub use expression::Value;
+pub use rule::Registry;
+pub use rule::Rule;
 pub use workflow::Workflow;
+pub use yaml::Document;
+pub use yaml::Location;
 
+pub mod baseline;
+pub mod config;
+pub mod diagnostic;
 pub mod error;
 pub mod expression;
+pub mod fix;
+pub mod report;
+pub mod rule;
 pub mod workflow;
+pub mod yaml;
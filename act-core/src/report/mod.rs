@@ -0,0 +1,7 @@
+pub use report::{
+    DiagnosticReport, EditReport, FileReport, FixReport, LabelReport, Position, Range, Record,
+    Report, VERSION,
+};
+
+#[allow(clippy::module_inception)]
+pub mod report;
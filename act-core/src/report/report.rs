@@ -0,0 +1,272 @@
+use serde::{Deserialize, Serialize};
+
+use crate::diagnostic::{Diagnostic, Edit, Fix, Label, Severity};
+use crate::expression::Span;
+use crate::Location;
+
+/// The version of the report schema, it changes when a field is removed or changes meaning.
+pub const VERSION: u32 = 1;
+
+/// The diagnostics of linted files, the schema of `act-lint lint --format json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    /// The [VERSION] of the schema.
+    pub version: u32,
+
+    pub files: Vec<FileReport>,
+}
+
+/// A diagnostic with its file and the schema version, the schema of each line of
+/// `act-lint lint --format jsonl`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub version: u32,
+
+    pub file: String,
+
+    #[serde(flatten)]
+    pub diagnostic: DiagnosticReport,
+}
+
+/// The diagnostics of a file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileReport {
+    /// The path of the file, as given on the command line.
+    pub file: String,
+
+    pub diagnostics: Vec<DiagnosticReport>,
+}
+
+/// A [Diagnostic] with line and column ranges instead of byte offsets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiagnosticReport {
+    /// The id of the rule that found the problem, e.g. `AL002`.
+    pub rule: String,
+
+    pub severity: Severity,
+
+    pub message: String,
+
+    pub range: Range,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<LabelReport>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<FixReport>,
+}
+
+/// A part of the source that explains a diagnostic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabelReport {
+    pub range: Range,
+    pub message: String,
+}
+
+/// A change of the source that solves the problem of a diagnostic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FixReport {
+    pub message: String,
+    pub edits: Vec<EditReport>,
+}
+
+/// The replacement of a range of the source.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditReport {
+    pub range: Range,
+    pub replacement: String,
+}
+
+/// A range of the source, the end is exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// A position in the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Position {
+    /// The line, starting at 1.
+    pub line: usize,
+
+    /// The column in characters, starting at 1.
+    pub column: usize,
+
+    /// The byte offset, starting at 0.
+    pub offset: usize,
+}
+
+impl Report {
+    pub fn new(files: Vec<FileReport>) -> Self {
+        Self {
+            version: VERSION,
+            files,
+        }
+    }
+
+    /// One record per diagnostic, in file order.
+    pub fn records(&self) -> impl Iterator<Item = Record> + '_ {
+        self.files.iter().flat_map(|file| {
+            file.diagnostics.iter().map(|diagnostic| Record {
+                version: self.version,
+                file: file.file.clone(),
+                diagnostic: diagnostic.clone(),
+            })
+        })
+    }
+}
+
+impl FileReport {
+    /// Report the diagnostics of a source, their spans are located in the source.
+    pub fn new(file: impl Into<String>, source: &str, diagnostics: &[Diagnostic]) -> Self {
+        Self {
+            file: file.into(),
+            diagnostics: diagnostics
+                .iter()
+                .map(|diagnostic| DiagnosticReport::new(source, diagnostic))
+                .collect(),
+        }
+    }
+}
+
+impl DiagnosticReport {
+    pub fn new(source: &str, diagnostic: &Diagnostic) -> Self {
+        Self {
+            rule: diagnostic.rule.to_string(),
+            severity: diagnostic.severity,
+            message: diagnostic.message.clone(),
+            range: Range::new(source, diagnostic.span),
+            labels: diagnostic
+                .labels
+                .iter()
+                .map(|label| LabelReport::new(source, label))
+                .collect(),
+            help: diagnostic.help.clone(),
+            fix: diagnostic
+                .fix
+                .as_ref()
+                .map(|fix| FixReport::new(source, fix)),
+        }
+    }
+}
+
+impl LabelReport {
+    pub fn new(source: &str, label: &Label) -> Self {
+        Self {
+            range: Range::new(source, label.span),
+            message: label.message.clone(),
+        }
+    }
+}
+
+impl FixReport {
+    pub fn new(source: &str, fix: &Fix) -> Self {
+        Self {
+            message: fix.message.clone(),
+            edits: fix
+                .edits
+                .iter()
+                .map(|edit| EditReport::new(source, edit))
+                .collect(),
+        }
+    }
+}
+
+impl EditReport {
+    pub fn new(source: &str, edit: &Edit) -> Self {
+        Self {
+            range: Range::new(source, edit.span),
+            replacement: edit.replacement.clone(),
+        }
+    }
+}
+
+impl Range {
+    /// The range of a span of a source.
+    pub fn new(source: &str, span: Span) -> Self {
+        Self {
+            start: Position::from(Location::from_offset(source, span.start)),
+            end: Position::from(Location::from_offset(source, span.end)),
+        }
+    }
+}
+
+impl From<Location> for Position {
+    fn from(location: Location) -> Self {
+        Self {
+            line: location.line,
+            column: location.column,
+            offset: location.offset,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> Report {
+        let source = "on: push\nenv:\n  GITHUB_SHA: x\n";
+        let diagnostic = Diagnostic::warning("AL005", "reserved name", Span::new(16, 26))
+            .with_help("rename the variable")
+            .with_fix(Fix::new(
+                "rename",
+                vec![Edit::new(Span::new(16, 26), "MY_SHA")],
+            ));
+        Report::new(vec![FileReport::new("ci.yml", source, &[diagnostic])])
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!(
+            serde_json::to_value(report()).unwrap(),
+            serde_json::json!({
+                "version": 1,
+                "files": [{
+                    "file": "ci.yml",
+                    "diagnostics": [{
+                        "rule": "AL005",
+                        "severity": "warning",
+                        "message": "reserved name",
+                        "range": {
+                            "start": {"line": 3, "column": 3, "offset": 16},
+                            "end": {"line": 3, "column": 13, "offset": 26}
+                        },
+                        "help": "rename the variable",
+                        "fix": {
+                            "message": "rename",
+                            "edits": [{
+                                "range": {
+                                    "start": {"line": 3, "column": 3, "offset": 16},
+                                    "end": {"line": 3, "column": 13, "offset": 26}
+                                },
+                                "replacement": "MY_SHA"
+                            }]
+                        }
+                    }]
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        let json = serde_json::to_string(&report()).unwrap();
+        assert_eq!(serde_json::from_str::<Report>(&json).unwrap(), report());
+    }
+
+    #[test]
+    fn records() {
+        let records = report().records().collect::<Vec<_>>();
+        assert_eq!(records.len(), 1);
+        let json = serde_json::to_value(&records[0]).unwrap();
+        assert_eq!(json["file"], "ci.yml");
+        assert_eq!(json["rule"], "AL005");
+        assert_eq!(json["version"], 1);
+        assert_eq!(serde_json::from_value::<Record>(json).unwrap(), records[0]);
+    }
+}
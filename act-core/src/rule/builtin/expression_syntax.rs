@@ -0,0 +1,59 @@
+use crate::rule::builtin::report_errors;
+use crate::rule::{Category, LintContext, Rule};
+use crate::{Diagnostics, Error, Severity};
+
+/// `AL004`: every `${{ ... }}` expression must be valid.
+pub struct ExpressionSyntax;
+
+impl Rule for ExpressionSyntax {
+    fn id(&self) -> &'static str {
+        "AL004"
+    }
+
+    fn name(&self) -> &'static str {
+        "expression-syntax"
+    }
+
+    fn category(&self) -> Category {
+        Category::Expression
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn description(&self) -> &'static str {
+        "Every `${{ ... }}` expression must be valid."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "GitHub fails a run when an expression cannot be parsed, often only once the step \
+         using it is reached. Common mistakes are `=` instead of `==`, unbalanced \
+         parentheses or quotes, and strings in double quotes, expressions only accept \
+         single quotes."
+    }
+
+    fn help_uri(&self) -> Option<&'static str> {
+        Some("https://docs.github.com/en/actions/learn-github-actions/expressions")
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Diagnostics) {
+        report_errors(self, context, diagnostics, |err| {
+            matches!(err, Error::Expression(_) | Error::Evaluation(_))
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::Registry;
+
+    #[test]
+    fn check() {
+        let source = "on: push\nrun-name: ${{ a = b }}\n";
+        let diagnostics = Registry::builtin().lint(source, None);
+        let diagnostic = diagnostics.iter().next().unwrap();
+        assert_eq!(diagnostic.rule, "AL004");
+        assert_eq!(&source[diagnostic.span.start..diagnostic.span.end], "=");
+    }
+}
@@ -0,0 +1,110 @@
+use crate::rule::{Category, LintContext, Rule};
+use crate::{Diagnostics, Severity};
+
+/// `AL008`: container registry passwords should come from secrets.
+pub struct HardcodedPassword;
+
+impl Rule for HardcodedPassword {
+    fn id(&self) -> &'static str {
+        "AL008"
+    }
+
+    fn name(&self) -> &'static str {
+        "hardcoded-password"
+    }
+
+    fn category(&self) -> Category {
+        Category::Security
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn description(&self) -> &'static str {
+        "Container credentials should not contain a literal password."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "The `credentials` of a job container or a service container log in to the \
+         registry of the image. A password written in the workflow can be read by anyone \
+         who can read the repository, store it as a secret and reference it with an \
+         expression, e.g. `password: ${{ secrets.REGISTRY_PASSWORD }}`."
+    }
+
+    fn help_uri(&self) -> Option<&'static str> {
+        Some("https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idcontainercredentials")
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Diagnostics) {
+        let (Some(document), Some(workflow)) = (context.document, context.workflow) else {
+            return;
+        };
+        for (id, job) in &workflow.jobs {
+            let mut containers = vec![];
+            if let Some(container) = &job.container {
+                containers.push((format!("jobs.{}.container", id), container));
+            }
+            for (name, service) in job.services.iter().flatten() {
+                containers.push((format!("jobs.{}.services.{}", id, name), service));
+            }
+            for (path, container) in containers {
+                let Some(credentials) = container.credentials() else {
+                    continue;
+                };
+                if credentials.password.expressions().next().is_some() {
+                    continue;
+                }
+                let path = format!("{}.credentials.password", path);
+                diagnostics.push(
+                    self.diagnostic(
+                        format!("{}: the password is written in the workflow", path),
+                        document.path_span(&path),
+                    )
+                    .with_help(
+                        "store the password as a secret, e.g. `${{ secrets.REGISTRY_PASSWORD }}`",
+                    ),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::Registry;
+
+    #[test]
+    fn check() {
+        let source = r#"on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    container:
+      image: ghcr.io/owner/image
+      credentials:
+        username: ${{ github.actor }}
+        password: ${{ secrets.GITHUB_TOKEN }}
+    services:
+      db:
+        image: registry.example.com/postgres
+        credentials:
+          username: admin
+          password: hunter2
+    steps:
+      - run: make
+"#;
+        let diagnostics = Registry::builtin().lint(source, None);
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| format!("{} {}", d.rule, d.message))
+                .collect::<Vec<_>>(),
+            vec!["AL008 jobs.test.services.db.credentials.password: the password is written in the workflow"]
+        );
+        assert_eq!(
+            diagnostics.iter().next().unwrap().span.start,
+            source.find("hunter2").unwrap()
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::rule::{Category, LintContext, Rule};
+use crate::workflow::strategy::MatrixProblem;
+use crate::{Diagnostics, Severity};
+
+/// `AL007`: `include` and `exclude` entries of a matrix should do what they look like.
+pub struct MatrixIncludeExclude;
+
+impl Rule for MatrixIncludeExclude {
+    fn id(&self) -> &'static str {
+        "AL007"
+    }
+
+    fn name(&self) -> &'static str {
+        "matrix-include-exclude"
+    }
+
+    fn category(&self) -> Category {
+        Category::Style
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn description(&self) -> &'static str {
+        "Matrix `exclude` entries should match a combination and `include` entries should not overwrite each other."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "GitHub removes the combinations of the axes matching an `exclude` entry, an entry \
+         matching none, e.g. after a value was renamed, has no effect. Then each `include` \
+         entry is added to every combination whose axes it doesn't change, and it replaces \
+         the values an earlier `include` entry added: the earlier value is silently lost \
+         for those combinations. Both are reported for matrices without expressions."
+    }
+
+    fn help_uri(&self) -> Option<&'static str> {
+        Some("https://docs.github.com/en/actions/using-jobs/using-a-matrix-for-your-jobs#expanding-or-adding-matrix-configurations")
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Diagnostics) {
+        let (Some(document), Some(workflow)) = (context.document, context.workflow) else {
+            return;
+        };
+        for (id, job) in &workflow.jobs {
+            let Some(matrix) = job.strategy.as_ref().and_then(|s| s.matrix.as_ref()) else {
+                continue;
+            };
+            let Some(expansion) = matrix.expand() else {
+                continue;
+            };
+            let matrix_path = format!("jobs.{}.strategy.matrix", id);
+            for problem in expansion.problems {
+                let (path, message, help) = match problem {
+                    MatrixProblem::UnmatchedExclude(index) => (
+                        format!("{}.exclude[{}]", matrix_path, index),
+                        "matches no combination of the matrix".to_string(),
+                        "fix the values of the entry or remove it".to_string(),
+                    ),
+                    MatrixProblem::IncludeOverwrite {
+                        index,
+                        key,
+                        overwritten,
+                    } => (
+                        format!("{}.include[{}].{}", matrix_path, index, key),
+                        format!(
+                            "overwrites the `{}` value added by `include[{}]` in some combinations",
+                            key, overwritten
+                        ),
+                        "add the value to the axes instead, or make the entries match distinct combinations".to_string(),
+                    ),
+                    // GitHub rejects the matrix, the validation reports it
+                    MatrixProblem::TooManyCombinations(_) => continue,
+                };
+                diagnostics.push(
+                    self.diagnostic(format!("{}: {}", path, message), document.path_span(&path))
+                        .with_help(help),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::Registry;
+
+    #[test]
+    fn check() {
+        let source = r#"on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    strategy:
+      matrix:
+        os: [linux, windows]
+        node: [18, 20]
+        include:
+          - experimental: false
+          - node: 20
+            experimental: true
+        exclude:
+          - os: mac
+    steps:
+      - run: npm test
+"#;
+        let diagnostics = Registry::builtin().lint(source, None);
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| format!("{} {}", d.rule, d.message))
+                .collect::<Vec<_>>(),
+            vec![
+                "AL007 jobs.test.strategy.matrix.include[1].experimental: overwrites the `experimental` value added by `include[0]` in some combinations",
+                "AL007 jobs.test.strategy.matrix.exclude[0]: matches no combination of the matrix",
+            ]
+        );
+    }
+
+    #[test]
+    fn check_expression() {
+        let source = "on: push\njobs:\n  test:\n    runs-on: ubuntu-latest\n    strategy:\n      matrix:\n        os: ${{ fromJSON(vars.OS) }}\n        exclude:\n          - os: mac\n    steps:\n      - run: make\n";
+        assert!(Registry::builtin().lint(source, None).is_empty());
+    }
+}
@@ -0,0 +1,71 @@
+pub use expression_syntax::ExpressionSyntax;
+pub use hardcoded_password::HardcodedPassword;
+pub use matrix_include_exclude::MatrixIncludeExclude;
+pub use reserved_env_name::ReservedEnvName;
+pub use runner_label::RunnerLabel;
+pub use schema::Schema;
+pub use undeclared_secret::UndeclaredSecret;
+pub use unused_suppression::UnusedSuppression;
+pub use validation::Validation;
+pub use yaml_syntax::YamlSyntax;
+
+use crate::rule::{LintContext, Rule};
+use crate::{Diagnostics, Error};
+
+pub mod expression_syntax;
+pub mod hardcoded_password;
+pub mod matrix_include_exclude;
+pub mod reserved_env_name;
+pub mod runner_label;
+pub mod schema;
+pub mod undeclared_secret;
+pub mod unused_suppression;
+pub mod validation;
+pub mod yaml_syntax;
+
+/// Every rule of act-lint, ordered by id.
+pub fn all() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(YamlSyntax),
+        Box::new(Schema),
+        Box::new(Validation),
+        Box::new(ExpressionSyntax),
+        Box::new(ReservedEnvName),
+        Box::new(UnusedSuppression),
+        Box::new(MatrixIncludeExclude),
+        Box::new(HardcodedPassword),
+        Box::new(RunnerLabel),
+        Box::new(UndeclaredSecret),
+    ]
+}
+
+/// Report the errors found loading the workflow that the rule is about.
+fn report_errors(
+    rule: &dyn Rule,
+    context: &LintContext,
+    diagnostics: &mut Diagnostics,
+    is_reported: fn(&Error) -> bool,
+) {
+    for err in context.errors.iter().filter(|err| is_reported(err)) {
+        diagnostics.push(rule.diagnostic(err.to_string(), err.span().unwrap_or_default()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordered_unique_ids() {
+        let rules = all();
+        assert!(rules.windows(2).all(|pair| pair[0].id() < pair[1].id()));
+    }
+
+    #[test]
+    fn documented() {
+        for rule in all() {
+            assert!(rule.description().ends_with('.'), "{}", rule.id());
+            assert!(!rule.explanation().is_empty(), "{}", rule.id());
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::rule::{Category, LintContext, Rule};
+use crate::{Diagnostics, Severity};
+
+/// `AL005`: environment variables should not be named like the default ones.
+pub struct ReservedEnvName;
+
+impl Rule for ReservedEnvName {
+    fn id(&self) -> &'static str {
+        "AL005"
+    }
+
+    fn name(&self) -> &'static str {
+        "reserved-env-name"
+    }
+
+    fn category(&self) -> Category {
+        Category::Style
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn description(&self) -> &'static str {
+        "Environment variable names should not start with `GITHUB_`, which is reserved for the default variables."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "GitHub sets default environment variables starting with `GITHUB_`, e.g. \
+         `GITHUB_SHA`, and may override a variable of the workflow with the same name, \
+         or ignore it. Rename the variable, e.g. with a prefix of the project."
+    }
+
+    fn help_uri(&self) -> Option<&'static str> {
+        Some("https://docs.github.com/en/actions/learn-github-actions/variables#naming-conventions-for-environment-variables")
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Diagnostics) {
+        let (Some(document), Some(workflow)) = (context.document, context.workflow) else {
+            return;
+        };
+        let mut envs = vec![("env".to_string(), workflow.env.as_ref())];
+        for (id, job) in &workflow.jobs {
+            envs.push((format!("jobs.{}.env", id), job.env.as_ref()));
+            if let Some(container) = &job.container {
+                envs.push((format!("jobs.{}.container.env", id), container.env()));
+            }
+            for (name, service) in job.services.iter().flatten() {
+                envs.push((format!("jobs.{}.services.{}.env", id, name), service.env()));
+            }
+            for (i, step) in job.steps.iter().flatten().enumerate() {
+                envs.push((format!("jobs.{}.steps[{}].env", id, i), step.env.as_ref()));
+            }
+        }
+        for (path, env) in envs {
+            let Some(env) = env else { continue };
+            for name in env.reserved_names() {
+                let path = format!("{}.{}", path, name);
+                diagnostics.push(
+                    self.diagnostic(
+                        format!(
+                            "{}: names starting with `GITHUB_` are reserved for the default environment variables",
+                            path
+                        ),
+                        document.key_span(&path),
+                    )
+                    .with_help("rename the variable, GitHub may override it"),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::Registry;
+
+    #[test]
+    fn check() {
+        let source = "on: push\nenv:\n  GITHUB_SHA: x\n  SHA: y\njobs:\n  build:\n    runs-on: ubuntu-latest\n    env:\n      github_token: z\n";
+        let diagnostics = Registry::builtin().lint(source, None);
+        assert_eq!(
+            diagnostics.iter().map(|d| &d.message[..]).collect::<Vec<_>>(),
+            vec![
+                "env.GITHUB_SHA: names starting with `GITHUB_` are reserved for the default environment variables",
+                "jobs.build.env.github_token: names starting with `GITHUB_` are reserved for the default environment variables",
+            ]
+        );
+    }
+
+    #[test]
+    fn check_nested() {
+        let source = r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    container:
+      image: node:20
+      env:
+        GITHUB_X: 1
+    services:
+      db:
+        image: postgres
+        env:
+          GITHUB_DB: 2
+          POSTGRES_DB: test
+    steps:
+      - run: make
+        env:
+          GITHUB_Y: 3
+      - uses: actions/checkout@v4
+        env:
+          CHECKOUT: 4
+"#;
+        let diagnostics = Registry::builtin().lint(source, None);
+        assert_eq!(
+            diagnostics.iter().map(|d| &d.message[..]).collect::<Vec<_>>(),
+            vec![
+                "jobs.build.container.env.GITHUB_X: names starting with `GITHUB_` are reserved for the default environment variables",
+                "jobs.build.services.db.env.GITHUB_DB: names starting with `GITHUB_` are reserved for the default environment variables",
+                "jobs.build.steps[0].env.GITHUB_Y: names starting with `GITHUB_` are reserved for the default environment variables",
+            ]
+        );
+        assert_eq!(
+            diagnostics.iter().nth(2).unwrap().span.start,
+            source.find("GITHUB_Y: 3").unwrap()
+        );
+    }
+}
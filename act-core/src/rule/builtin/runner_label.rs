@@ -0,0 +1,310 @@
+use crate::diagnostic::{Edit, Fix};
+use crate::expression::Span;
+use crate::rule::{Category, LintContext, Rule};
+use crate::workflow::RunsOn;
+use crate::{Diagnostics, Severity};
+
+/// The labels of the runners hosted by GitHub.
+///
+/// <https://docs.github.com/en/actions/using-github-hosted-runners/about-github-hosted-runners#standard-github-hosted-runners-for-public-repositories>
+const GITHUB_HOSTED: &[&str] = &[
+    "ubuntu-latest",
+    "ubuntu-24.04",
+    "ubuntu-22.04",
+    "ubuntu-24.04-arm",
+    "ubuntu-22.04-arm",
+    "ubuntu-slim",
+    "windows-latest",
+    "windows-2025",
+    "windows-2022",
+    "windows-11-arm",
+    "macos-latest",
+    "macos-26",
+    "macos-15",
+    "macos-14",
+    "macos-latest-large",
+    "macos-26-large",
+    "macos-15-large",
+    "macos-14-large",
+    "macos-latest-xlarge",
+    "macos-26-xlarge",
+    "macos-15-xlarge",
+    "macos-14-xlarge",
+];
+
+/// The labels of runner images GitHub removed, with the label to use instead.
+const DEPRECATED: &[(&str, &str)] = &[
+    ("ubuntu-16.04", "ubuntu-24.04"),
+    ("ubuntu-18.04", "ubuntu-24.04"),
+    ("ubuntu-20.04", "ubuntu-24.04"),
+    ("windows-2016", "windows-2025"),
+    ("windows-2019", "windows-2025"),
+    ("macos-10.15", "macos-15"),
+    ("macos-11", "macos-15"),
+    ("macos-12", "macos-15"),
+    ("macos-12-xl", "macos-15-large"),
+    ("macos-13", "macos-15"),
+    ("macos-13-large", "macos-15-large"),
+    ("macos-13-xlarge", "macos-15-xlarge"),
+];
+
+/// The labels every self-hosted runner gets.
+///
+/// <https://docs.github.com/en/actions/hosting-your-own-runners/managing-self-hosted-runners/using-self-hosted-runners-in-a-workflow#using-default-labels-to-route-jobs>
+const SELF_HOSTED: &[&str] = &[
+    "self-hosted",
+    "linux",
+    "windows",
+    "macos",
+    "x64",
+    "arm",
+    "arm64",
+];
+
+/// The most edits between an unknown label and a known one for the known one to be suggested.
+const MAX_TYPO_DISTANCE: usize = 2;
+
+/// `AL009`: the labels of `runs-on` should match a runner.
+pub struct RunnerLabel;
+
+impl Rule for RunnerLabel {
+    fn id(&self) -> &'static str {
+        "AL009"
+    }
+
+    fn name(&self) -> &'static str {
+        "runner-label"
+    }
+
+    fn category(&self) -> Category {
+        Category::Style
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn description(&self) -> &'static str {
+        "Runner labels should be known GitHub-hosted or self-hosted labels, and not name a removed runner image."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A job whose `runs-on` labels match no runner waits until it is cancelled. Labels \
+         are checked against those of the GitHub-hosted runners, the default labels of \
+         self-hosted runners, and the custom labels listed in `self-hosted-runner.labels` \
+         of the configuration file; a label close to a known one is likely a typo. Labels \
+         with expressions and the labels of runner groups are not checked.\n\n\
+         Jobs on a runner image GitHub removed, e.g. `ubuntu-18.04`, fail to start, the fix \
+         replaces the label with a supported image of the same system. On self-hosted \
+         runners these labels are custom labels, declare them in `self-hosted-runner.labels`."
+    }
+
+    fn help_uri(&self) -> Option<&'static str> {
+        Some("https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idruns-on")
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Diagnostics) {
+        let (Some(document), Some(workflow)) = (context.document, context.workflow) else {
+            return;
+        };
+        let custom = &context.config.self_hosted_runner.labels;
+        for (id, job) in &workflow.jobs {
+            let Some(runs_on) = &job.runs_on else {
+                continue;
+            };
+            let (path, is_group) = match runs_on {
+                RunsOn::Label(_) | RunsOn::Labels(_) => (format!("jobs.{}.runs-on", id), false),
+                RunsOn::Group(_) => (format!("jobs.{}.runs-on.labels", id), true),
+            };
+            // a self-hosted runner may keep the label of a removed image as a custom label
+            let is_self_hosted = runs_on
+                .labels()
+                .iter()
+                .any(|label| label.as_str().eq_ignore_ascii_case("self-hosted"));
+            for (i, label) in runs_on.labels().iter().enumerate() {
+                if label.expressions().next().is_some() {
+                    continue;
+                }
+                let label = label.as_str();
+                if custom
+                    .iter()
+                    .any(|custom| custom.eq_ignore_ascii_case(label))
+                {
+                    continue;
+                }
+                let path = match runs_on {
+                    RunsOn::Label(_) => path.clone(),
+                    _ => format!("{}[{}]", path, i),
+                };
+                let span = label_span(document.source(), document.path_span(&path), label);
+                let deprecated = DEPRECATED
+                    .iter()
+                    .find(|(deprecated, _)| deprecated.eq_ignore_ascii_case(label));
+                if let (Some((_, replacement)), false) = (deprecated, is_self_hosted) {
+                    // the help repeats the fix for the formats that do not show fixes
+                    let replace = format!("replace with `{}`", replacement);
+                    diagnostics.push(
+                        self.diagnostic(
+                            format!("{}: the `{}` runner image was removed", path, label),
+                            span,
+                        )
+                        .with_help(replace.clone())
+                        .with_fix(Fix::new(replace, vec![Edit::new(span, *replacement)])),
+                    );
+                    continue;
+                }
+                let known = GITHUB_HOSTED
+                    .iter()
+                    .chain(SELF_HOSTED)
+                    .copied()
+                    .chain(custom.iter().map(String::as_str));
+                if is_group || known.clone().any(|known| known.eq_ignore_ascii_case(label)) {
+                    continue;
+                }
+                let mut diagnostic =
+                    self.diagnostic(format!("{}: unknown runner label `{}`", path, label), span);
+                // the label of a removed image is not a typo of a current one
+                let suggestion = if deprecated.is_some() {
+                    None
+                } else {
+                    suggestion(label, known)
+                };
+                diagnostic = match suggestion {
+                    Some(known) => diagnostic.with_help(format!("did you mean `{}`?", known)),
+                    None => diagnostic.with_help(
+                        "add the labels of self-hosted runners to `self-hosted-runner.labels` in the configuration file",
+                    ),
+                };
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+}
+
+/// The span of a label in its node, without the quotes.
+fn label_span(source: &str, node: Span, label: &str) -> Span {
+    match source[node.start..node.end].find(label) {
+        Some(offset) => Span::new(node.start + offset, node.start + offset + label.len()),
+        None => node,
+    }
+}
+
+/// The known label closest to an unknown one, if it is close enough to be a typo.
+fn suggestion<'a>(label: &str, known: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let label = label.to_ascii_lowercase();
+    known
+        .map(|known| (distance(&label, &known.to_ascii_lowercase()), known))
+        .filter(|(distance, _)| *distance <= MAX_TYPO_DISTANCE)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+/// The Levenshtein distance between two strings, in characters.
+fn distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::Registry;
+    use crate::Config;
+
+    fn lint(registry: &Registry, runs_on: &str) -> Vec<String> {
+        let source = format!(
+            "on: push\njobs:\n  build:\n    runs-on: {}\n    steps:\n      - run: make\n",
+            runs_on
+        );
+        registry
+            .lint(&source, None)
+            .iter()
+            .map(|d| format!("{} ({})", d.message, d.help.as_deref().unwrap_or_default()))
+            .collect()
+    }
+
+    #[test]
+    fn check() {
+        let registry = Registry::builtin();
+        assert!(lint(&registry, "ubuntu-latest").is_empty());
+        assert!(lint(&registry, "[self-hosted, Linux, ARM64]").is_empty());
+        assert!(lint(&registry, "${{ matrix.os }}").is_empty());
+        assert!(lint(&registry, "{ group: large, labels: [gpu] }").is_empty());
+        assert_eq!(
+            lint(&registry, "[self-hosted, gpu]"),
+            vec!["jobs.build.runs-on[1]: unknown runner label `gpu` (add the labels of self-hosted runners to `self-hosted-runner.labels` in the configuration file)"]
+        );
+        assert_eq!(
+            lint(&registry, "ubuntu-lastest"),
+            vec!["jobs.build.runs-on: unknown runner label `ubuntu-lastest` (did you mean `ubuntu-latest`?)"]
+        );
+    }
+
+    #[test]
+    fn check_self_hosted_labels() {
+        let mut registry = Registry::builtin();
+        let mut config = Config::default();
+        config.self_hosted_runner.labels = vec!["gpu".to_string()];
+        registry.configure(config);
+        assert!(lint(&registry, "[self-hosted, gpu]").is_empty());
+        assert_eq!(
+            lint(&registry, "[self-hosted, gpus]"),
+            vec!["jobs.build.runs-on[1]: unknown runner label `gpus` (did you mean `gpu`?)"]
+        );
+    }
+
+    #[test]
+    fn check_deprecated() {
+        let source = "on: push\njobs:\n  build:\n    runs-on: \"ubuntu-18.04\"\n    steps:\n      - run: make\n";
+        let diagnostics = Registry::builtin().lint(source, None);
+        let diagnostic = diagnostics.iter().next().unwrap();
+        assert_eq!(
+            diagnostic.message,
+            "jobs.build.runs-on: the `ubuntu-18.04` runner image was removed"
+        );
+        let fix = diagnostic.fix.as_ref().unwrap();
+        assert_eq!(diagnostic.help.as_deref(), Some(&fix.message[..]));
+        assert_eq!(
+            crate::fix::apply(source, &fix.edits),
+            source.replace("ubuntu-18.04", "ubuntu-24.04")
+        );
+    }
+
+    #[test]
+    fn check_deprecated_self_hosted() {
+        let mut registry = Registry::builtin();
+        assert_eq!(
+            lint(&registry, "[self-hosted, ubuntu-18.04]"),
+            vec!["jobs.build.runs-on[1]: unknown runner label `ubuntu-18.04` (add the labels of self-hosted runners to `self-hosted-runner.labels` in the configuration file)"]
+        );
+        let source = "on: push\njobs:\n  build:\n    runs-on: [self-hosted, ubuntu-18.04]\n    steps:\n      - run: make\n";
+        assert_eq!(crate::fix::fix(&registry, source, None).source, source);
+
+        let mut config = Config::default();
+        config.self_hosted_runner.labels = vec!["macos-12".to_string()];
+        registry.configure(config);
+        assert!(lint(&registry, "macos-12").is_empty());
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!(distance("ubuntu-latest", "ubuntu-latest"), 0);
+        assert_eq!(distance("ubuntu-lastest", "ubuntu-latest"), 1);
+        assert_eq!(distance("macos-14", "macos-15"), 1);
+        assert_eq!(distance("", "gpu"), 3);
+        assert_eq!(
+            suggestion("windows-lates", GITHUB_HOSTED.iter().copied()),
+            Some("windows-latest")
+        );
+        assert_eq!(suggestion("fedora", GITHUB_HOSTED.iter().copied()), None);
+    }
+}
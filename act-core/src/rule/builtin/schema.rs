@@ -0,0 +1,50 @@
+use crate::rule::builtin::report_errors;
+use crate::rule::{Category, LintContext, Rule};
+use crate::{Diagnostics, Error, Severity};
+
+/// `AL002`: every node of the workflow must have the expected shape.
+pub struct Schema;
+
+impl Rule for Schema {
+    fn id(&self) -> &'static str {
+        "AL002"
+    }
+
+    fn name(&self) -> &'static str {
+        "schema"
+    }
+
+    fn category(&self) -> Category {
+        Category::Syntax
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn description(&self) -> &'static str {
+        "Every key of the workflow must be known and every value must have the expected type."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "GitHub rejects a workflow with an unknown key, e.g. a misspelled `runs-on`, or a \
+         value of the wrong type, e.g. a list where a string is expected. Job ids must \
+         start with a letter or `_` and contain only alphanumeric characters, `-` or `_`.\n\n\
+         The message lists the expected keys or type, compare it with the workflow syntax."
+    }
+
+    fn help_uri(&self) -> Option<&'static str> {
+        Some(
+            "https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions",
+        )
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Diagnostics) {
+        report_errors(self, context, diagnostics, |err| {
+            !matches!(
+                err,
+                Error::Yaml(_) | Error::Validation(_) | Error::Expression(_) | Error::Evaluation(_)
+            )
+        });
+    }
+}
@@ -0,0 +1,249 @@
+use crate::expression::{Condition, Expr, ExprKind, ExpressionInString, Span};
+use crate::rule::{Category, LintContext, Rule};
+use crate::workflow::on::EventConfig;
+use crate::workflow::{On, Workflow};
+use crate::yaml::{Node, NodeKind};
+use crate::{Diagnostics, Severity};
+
+/// The secret every workflow run gets, whether or not it is declared.
+const GITHUB_TOKEN: &str = "GITHUB_TOKEN";
+
+/// `AL010`: the secrets and variables referenced by expressions should be declared.
+pub struct UndeclaredSecret;
+
+impl Rule for UndeclaredSecret {
+    fn id(&self) -> &'static str {
+        "AL010"
+    }
+
+    fn name(&self) -> &'static str {
+        "undeclared-secret"
+    }
+
+    fn category(&self) -> Category {
+        Category::Expression
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn description(&self) -> &'static str {
+        "Referenced `secrets` and `vars` should be declared in the configuration file."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A secret or configuration variable that is not defined for the repository \
+         evaluates to an empty string, the run carries on without it, e.g. it deploys \
+         without credentials. When `secrets` of the configuration file lists the secrets of \
+         the repository, references to other secrets are reported, besides `GITHUB_TOKEN` \
+         and the secrets of `on.workflow_call`; `vars` does the same for configuration \
+         variables. Names are compared ignoring case, as GitHub does."
+    }
+
+    fn help_uri(&self) -> Option<&'static str> {
+        Some("https://docs.github.com/en/actions/learn-github-actions/contexts#secrets-context")
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Diagnostics) {
+        let (Some(document), Some(workflow)) = (context.document, context.workflow) else {
+            return;
+        };
+        let config = context.config;
+        if config.secrets.is_empty() && config.vars.is_empty() {
+            return;
+        }
+        let mut secrets = config
+            .secrets
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        secrets.push(GITHUB_TOKEN);
+        secrets.extend(workflow_call_secrets(workflow));
+        let vars = config.vars.iter().map(String::as_str).collect::<Vec<_>>();
+        let mut references = vec![];
+        collect(document.root(), "", context.source, &mut references);
+        for (path, context_name, name, span) in references {
+            let (known, key) = match context_name {
+                "secrets" if !config.secrets.is_empty() => (&secrets, "secrets"),
+                "vars" if !vars.is_empty() => (&vars, "vars"),
+                _ => continue,
+            };
+            if known.iter().any(|known| known.eq_ignore_ascii_case(&name)) {
+                continue;
+            }
+            diagnostics.push(
+                self.diagnostic(
+                    format!("{}: `{}.{}` is not declared", path, context_name, name),
+                    span,
+                )
+                .with_help(format!(
+                    "add `{}` to `{}` in the configuration file, or fix the name",
+                    name, key
+                )),
+            );
+        }
+    }
+}
+
+/// The names of the secrets of a reusable workflow, passed by its callers.
+fn workflow_call_secrets(workflow: &Workflow) -> Vec<&str> {
+    let Some(On::Map(events)) = &workflow.on else {
+        return vec![];
+    };
+    events
+        .values()
+        .filter_map(|config| match config {
+            EventConfig::WorkflowCall(call) => call.secrets.as_ref(),
+            _ => None,
+        })
+        .flat_map(|secrets| secrets.keys().map(String::as_str))
+        .collect()
+}
+
+/// Collects the `secrets` and `vars` references of the expressions of a node and its
+/// children, with the path of the node, the context, the name and its span in the source.
+///
+/// `if` values are conditions, the other strings only have expressions in `${{ }}`.
+fn collect(
+    node: &Node,
+    path: &str,
+    source: &str,
+    references: &mut Vec<(String, &'static str, String, Span)>,
+) {
+    match &node.kind {
+        NodeKind::Scalar(value, _) => {
+            let is_condition = path == "if" || path.ends_with(".if");
+            let exprs = if is_condition {
+                Condition::new(value).map(|c| vec![c.ast().clone()])
+            } else if value.contains("${{") {
+                ExpressionInString::new(value).map(|s| s.expressions().cloned().collect())
+            } else {
+                return;
+            };
+            // invalid expressions are reported by `AL004`
+            for expr in exprs.iter().flatten() {
+                visit(expr, &mut |context, name, span| {
+                    references.push((
+                        path.to_string(),
+                        context,
+                        name,
+                        node.value_span(source, span),
+                    ))
+                });
+            }
+        }
+        NodeKind::Sequence(items) => {
+            for (i, item) in items.iter().enumerate() {
+                collect(item, &format!("{}[{}]", path, i), source, references);
+            }
+        }
+        NodeKind::Mapping(entries) => {
+            for (key, value) in entries {
+                let Some(key) = key.as_str() else {
+                    continue;
+                };
+                let path = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                collect(value, &path, source, references);
+            }
+        }
+    }
+}
+
+/// Calls `f` with the context, name and span of the `secrets` and `vars` references of an
+/// expression, e.g. `secrets.TOKEN` or `vars['REGION']`.
+fn visit(expr: &Expr, f: &mut impl FnMut(&'static str, String, Span)) {
+    let context = |object: &Expr| match &object.kind {
+        ExprKind::Context(name) if name == "secrets" => Some("secrets"),
+        ExprKind::Context(name) if name == "vars" => Some("vars"),
+        _ => None,
+    };
+    match &expr.kind {
+        ExprKind::Property { object, name } => match context(object) {
+            Some(context) => f(context, name.clone(), expr.span),
+            None => visit(object, f),
+        },
+        ExprKind::Index { object, index } => match (context(object), &index.kind) {
+            (Some(context), ExprKind::String(name)) => f(context, name.clone(), expr.span),
+            _ => {
+                visit(object, f);
+                visit(index, f);
+            }
+        },
+        ExprKind::Filter { object } => visit(object, f),
+        ExprKind::Call { args, .. } => args.iter().for_each(|arg| visit(arg, f)),
+        ExprKind::Unary { operand, .. } => visit(operand, f),
+        ExprKind::Binary { left, right, .. } => {
+            visit(left, f);
+            visit(right, f);
+        }
+        ExprKind::Null
+        | ExprKind::Bool(_)
+        | ExprKind::Number(_)
+        | ExprKind::String(_)
+        | ExprKind::Context(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::Registry;
+    use crate::Config;
+
+    const SOURCE: &str = r#"on:
+  workflow_call:
+    secrets:
+      npm-token:
+        required: true
+jobs:
+  deploy:
+    if: vars.REGION != ''
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo ${{ secrets.deploy_key }} ${{ secrets.DEPLOY_KEYS }}
+        env:
+          TOKEN: ${{ secrets.GITHUB_TOKEN || secrets['NPM-TOKEN'] }}
+          ZONE: ${{ vars['ZONE'] }}
+"#;
+
+    fn lint(registry: &Registry) -> Vec<String> {
+        registry
+            .lint(SOURCE, None)
+            .iter()
+            .map(|d| format!("{} {}", &SOURCE[d.span.start..d.span.end], d.message))
+            .collect()
+    }
+
+    #[test]
+    fn check() {
+        let mut registry = Registry::builtin();
+        assert_eq!(lint(&registry), Vec::<String>::new());
+
+        registry.configure(Config {
+            secrets: vec!["DEPLOY_KEY".to_string()],
+            vars: vec!["REGION".to_string()],
+            ..Config::default()
+        });
+        assert_eq!(
+            lint(&registry),
+            vec![
+                "secrets.DEPLOY_KEYS jobs.deploy.steps[0].run: `secrets.DEPLOY_KEYS` is not declared",
+                "vars['ZONE'] jobs.deploy.steps[0].env.ZONE: `vars.ZONE` is not declared",
+            ]
+        );
+    }
+
+    #[test]
+    fn check_secrets_only() {
+        let mut registry = Registry::builtin();
+        registry.configure(Config {
+            secrets: vec!["DEPLOY_KEYS".to_string(), "deploy_key".to_string()],
+            ..Config::default()
+        });
+        assert!(lint(&registry).is_empty());
+    }
+}
@@ -0,0 +1,41 @@
+use crate::rule::{Category, LintContext, Rule};
+use crate::{Diagnostics, Severity};
+
+/// `AL006`: suppression comments must suppress a problem.
+///
+/// The [Registry](crate::rule::Registry) reports these once the other rules ran, the
+/// rule exists so that they can be documented and configured like the other problems.
+pub struct UnusedSuppression;
+
+impl Rule for UnusedSuppression {
+    fn id(&self) -> &'static str {
+        "AL006"
+    }
+
+    fn name(&self) -> &'static str {
+        "unused-suppression"
+    }
+
+    fn category(&self) -> Category {
+        Category::Style
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn description(&self) -> &'static str {
+        "Suppression comments must name known rules and suppress a problem."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "`# act-lint-disable-next-line AL005` turns rules off for the next line, \
+         `# act-lint-disable AL005` turns them off until `# act-lint-enable` or the end of \
+         the file. Rules are given by id or name, separated by commas or spaces, and a \
+         reason can follow ` -- `. Without rules, every rule is turned off.\n\n\
+         A suppression that no longer suppresses a problem hides the next one, remove it \
+         once the problem is solved."
+    }
+
+    fn check(&self, _context: &LintContext, _diagnostics: &mut Diagnostics) {}
+}
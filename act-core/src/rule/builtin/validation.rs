@@ -0,0 +1,50 @@
+use crate::rule::builtin::report_errors;
+use crate::rule::{Category, LintContext, Rule};
+use crate::{Diagnostics, Error, Severity};
+
+/// `AL003`: values must follow the rules of the workflow syntax.
+pub struct Validation;
+
+impl Rule for Validation {
+    fn id(&self) -> &'static str {
+        "AL003"
+    }
+
+    fn name(&self) -> &'static str {
+        "validation"
+    }
+
+    fn category(&self) -> Category {
+        Category::Syntax
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn description(&self) -> &'static str {
+        "Values must follow the rules of the workflow syntax, e.g. a concurrency group must not be empty."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Some values have the expected type but are still rejected by GitHub, e.g. an \
+         empty concurrency group, `defaults.run` without `shell` nor `working-directory`, \
+         a step with both `uses` and `run`, two steps of a job with the same id, \
+         a matrix of more than 256 combinations, a container image or port mapping with a \
+         syntax error, \
+         or two filters of an event that cannot be used together like `branches` and \
+         `branches-ignore`."
+    }
+
+    fn help_uri(&self) -> Option<&'static str> {
+        Some(
+            "https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions",
+        )
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Diagnostics) {
+        report_errors(self, context, diagnostics, |err| {
+            matches!(err, Error::Validation(_))
+        });
+    }
+}
@@ -0,0 +1,45 @@
+use crate::rule::builtin::report_errors;
+use crate::rule::{Category, LintContext, Rule};
+use crate::{Diagnostics, Error, Severity};
+
+/// `AL001`: the workflow file must be valid YAML.
+pub struct YamlSyntax;
+
+impl Rule for YamlSyntax {
+    fn id(&self) -> &'static str {
+        "AL001"
+    }
+
+    fn name(&self) -> &'static str {
+        "yaml-syntax"
+    }
+
+    fn category(&self) -> Category {
+        Category::Syntax
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn description(&self) -> &'static str {
+        "The workflow file must be valid YAML."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "GitHub cannot read a workflow file that is not valid YAML and fails every run of \
+         it. Common causes are unclosed brackets or quotes, tabs used for indentation and \
+         values starting with `*`, `&` or `{` that are not quoted.\n\n\
+         No other rule checks a file that is not valid YAML, solve this problem first."
+    }
+
+    fn help_uri(&self) -> Option<&'static str> {
+        Some("https://docs.github.com/en/actions/using-workflows/about-workflows#create-an-example-workflow")
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Diagnostics) {
+        report_errors(self, context, diagnostics, |err| {
+            matches!(err, Error::Yaml(_))
+        });
+    }
+}
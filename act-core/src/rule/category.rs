@@ -0,0 +1,49 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The kind of problems a [Rule](crate::rule::Rule) finds.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    /// The workflow file is not valid YAML or not a valid workflow.
+    Syntax,
+
+    /// A `${{ ... }}` expression is not valid.
+    Expression,
+
+    /// The workflow may leak secrets or run untrusted code.
+    Security,
+
+    /// The workflow works but is likely not what was meant.
+    Style,
+
+    /// The workflow wastes runner time.
+    Performance,
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Category::Syntax => write!(f, "syntax"),
+            Category::Expression => write!(f, "expression"),
+            Category::Security => write!(f, "security"),
+            Category::Style => write!(f, "style"),
+            Category::Performance => write!(f, "performance"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Category::Syntax.to_string(), "syntax");
+        assert_eq!(
+            serde_json::to_string(&Category::Performance).unwrap(),
+            "\"performance\""
+        );
+    }
+}
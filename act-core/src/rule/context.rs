@@ -0,0 +1,23 @@
+use crate::config::Config;
+use crate::{Document, Error, Workflow};
+
+/// What a [Rule](crate::rule::Rule) checks: the source of a workflow file and what
+/// loading it found.
+#[derive(Debug, Clone, Copy)]
+pub struct LintContext<'a> {
+    /// The source of the workflow file.
+    pub source: &'a str,
+
+    /// The configuration of the repository, e.g. its self-hosted runner labels.
+    pub config: &'a Config,
+
+    /// The parsed source, there is none when the source is not valid YAML.
+    pub document: Option<&'a Document>,
+
+    /// The workflow without the nodes that failed to deserialize, there is none when the
+    /// document root is not a workflow.
+    pub workflow: Option<&'a Workflow>,
+
+    /// The errors found loading the workflow, each rule reports the ones of its kind.
+    pub errors: &'a [Error],
+}
@@ -0,0 +1,13 @@
+pub use category::Category;
+pub use context::LintContext;
+pub use registry::Registry;
+pub use rule::Rule;
+pub use suppression::{Suppression, Suppressions};
+
+pub mod builtin;
+pub mod category;
+pub mod context;
+pub mod registry;
+#[allow(clippy::module_inception)]
+pub mod rule;
+pub mod suppression;
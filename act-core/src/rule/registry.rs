@@ -0,0 +1,288 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::config::Config;
+use crate::rule::builtin::{self, UnusedSuppression};
+use crate::rule::{LintContext, Rule, Suppressions};
+use crate::{Diagnostics, Document, Severity, Workflow};
+
+/// The rules to run on workflow files, with the severity and whether each one is enabled.
+#[derive(Default)]
+pub struct Registry {
+    rules: Vec<Box<dyn Rule>>,
+    severities: HashMap<&'static str, Severity>,
+    disabled: HashSet<&'static str>,
+    config: Config,
+}
+
+impl Registry {
+    /// A registry without rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry with every rule of act-lint, ordered by id.
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        for rule in builtin::all() {
+            registry.register(rule);
+        }
+        registry
+    }
+
+    /// Add a rule, it is run after the rules registered before.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a rule with the same id is already registered.
+    pub fn register(&mut self, rule: Box<dyn Rule>) {
+        assert!(
+            self.get(rule.id()).is_none(),
+            "rule `{}` is already registered",
+            rule.id()
+        );
+        self.rules.push(rule);
+    }
+
+    /// The registered rules, in the order they were registered.
+    pub fn rules(&self) -> impl Iterator<Item = &dyn Rule> {
+        self.rules.iter().map(Box::as_ref)
+    }
+
+    /// The rule with the given id.
+    pub fn get(&self, id: &str) -> Option<&dyn Rule> {
+        self.rules().find(|rule| rule.id() == id)
+    }
+
+    /// The rule with the given id, or name as in configuration files.
+    pub fn find(&self, id_or_name: &str) -> Option<&dyn Rule> {
+        self.rules()
+            .find(|rule| rule.id() == id_or_name || rule.name() == id_or_name)
+    }
+
+    /// Configure the rules, the configuration is also given to the rules as they check.
+    ///
+    /// Rules registered afterwards keep their default severity.
+    pub fn configure(&mut self, config: Config) {
+        config.apply(self);
+        self.config = config;
+    }
+
+    /// The configuration given to the rules.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Override the severity of the diagnostics of a rule, returns false for an unknown rule.
+    pub fn set_severity(&mut self, id: &str, severity: Severity) -> bool {
+        match self.get(id).map(|rule| rule.id()) {
+            Some(id) => {
+                self.severities.insert(id, severity);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The severity of the diagnostics of a rule, as configured or by default.
+    pub fn severity(&self, id: &str) -> Option<Severity> {
+        let rule = self.get(id)?;
+        Some(
+            self.severities
+                .get(rule.id())
+                .copied()
+                .unwrap_or_else(|| rule.default_severity()),
+        )
+    }
+
+    /// Enable or disable a rule, returns false for an unknown rule.
+    pub fn set_enabled(&mut self, id: &str, enabled: bool) -> bool {
+        match self.get(id).map(|rule| rule.id()) {
+            Some(id) => {
+                if enabled {
+                    self.disabled.remove(id);
+                } else {
+                    self.disabled.insert(id);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether a rule is registered and enabled.
+    pub fn is_enabled(&self, id: &str) -> bool {
+        self.get(id).is_some() && !self.disabled.contains(id)
+    }
+
+    /// Run the enabled rules on a workflow file, the diagnostics are ordered by where they
+    /// are in the source.
+    ///
+    /// Diagnostics on lines turned off by suppression comments are left out, and unused
+    /// suppressions are reported.
+    pub fn check(&self, context: &LintContext) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+        for rule in self.rules().filter(|rule| self.is_enabled(rule.id())) {
+            rule.check(context, &mut diagnostics);
+        }
+        if let Some(document) = context.document {
+            let suppressions = Suppressions::parse(document, self);
+            diagnostics = suppressions.apply(document, diagnostics, self, &UnusedSuppression);
+            if !self.is_enabled(UnusedSuppression.id()) {
+                diagnostics = diagnostics
+                    .into_iter()
+                    .filter(|diagnostic| diagnostic.rule != UnusedSuppression.id())
+                    .collect();
+            }
+        }
+        let mut diagnostics: Diagnostics = diagnostics
+            .into_iter()
+            .map(|mut diagnostic| {
+                if let Some(severity) = self.severities.get(diagnostic.rule) {
+                    diagnostic.severity = *severity;
+                }
+                diagnostic
+            })
+            .collect();
+        diagnostics.sort();
+        diagnostics
+    }
+
+    /// Parse and load a workflow file, then run the enabled rules on it.
+    pub fn lint(&self, source: &str, file: Option<&Path>) -> Diagnostics {
+        let document = match Document::parse(source) {
+            Ok(document) => document,
+            Err(err) => {
+                return self.check(&LintContext {
+                    source,
+                    config: &self.config,
+                    document: None,
+                    workflow: None,
+                    errors: &[err],
+                })
+            }
+        };
+        let document = match file {
+            Some(file) => document.with_file(file),
+            None => document,
+        };
+        let (workflow, errors) = Workflow::load_all(&document);
+        self.check(&LintContext {
+            source,
+            config: &self.config,
+            document: Some(&document),
+            workflow: workflow.as_ref(),
+            errors: &errors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::Category;
+
+    struct NoName;
+
+    impl Rule for NoName {
+        fn id(&self) -> &'static str {
+            "X001"
+        }
+
+        fn name(&self) -> &'static str {
+            "no-name"
+        }
+
+        fn category(&self) -> Category {
+            Category::Style
+        }
+
+        fn default_severity(&self) -> Severity {
+            Severity::Note
+        }
+
+        fn description(&self) -> &'static str {
+            "Workflows should have a name."
+        }
+
+        fn explanation(&self) -> &'static str {
+            "GitHub shows the path of workflows without a name."
+        }
+
+        fn check(&self, context: &LintContext, diagnostics: &mut Diagnostics) {
+            if context.workflow.is_some_and(|w| w.name.is_none()) {
+                diagnostics.push(self.diagnostic("missing name".to_string(), Default::default()));
+            }
+        }
+    }
+
+    fn lint(registry: &Registry, source: &str) -> Vec<String> {
+        registry
+            .lint(source, None)
+            .iter()
+            .map(|d| format!("{} {} {}", d.span, d.rule, d.severity))
+            .collect()
+    }
+
+    #[test]
+    fn builtin() {
+        let registry = Registry::builtin();
+        let ids = registry.rules().map(|rule| rule.id()).collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                "AL001", "AL002", "AL003", "AL004", "AL005", "AL006", "AL007", "AL008", "AL009",
+                "AL010"
+            ]
+        );
+        assert_eq!(registry.get("AL002").unwrap().name(), "schema");
+        assert_eq!(registry.find("schema").unwrap().id(), "AL002");
+        assert!(registry.get("AL999").is_none());
+    }
+
+    #[test]
+    fn lint_all() {
+        let source =
+            "on: push\nconcurrency:\n  group: ''\njobs:\n  build:\n    runs-on: ubuntu-latest\n    env:\n      GITHUB_SHA: x\n";
+        assert_eq!(
+            lint(&Registry::builtin(), source),
+            vec!["31..33 AL003 error", "91..101 AL005 warning"]
+        );
+    }
+
+    #[test]
+    fn lint_yaml_err() {
+        assert_eq!(
+            lint(&Registry::builtin(), "on: [push\n"),
+            vec!["10..10 AL001 error"]
+        );
+    }
+
+    #[test]
+    fn configure() {
+        let mut registry = Registry::builtin();
+        assert!(registry.set_severity("AL005", Severity::Error));
+        assert!(registry.set_enabled("AL003", false));
+        assert!(!registry.set_enabled("AL999", false));
+        assert_eq!(registry.severity("AL005"), Some(Severity::Error));
+        assert!(!registry.is_enabled("AL003"));
+        let source =
+            "on: push\nconcurrency:\n  group: ''\njobs:\n  build:\n    env:\n      GITHUB_SHA: x\n";
+        assert_eq!(lint(&registry, source), vec!["64..74 AL005 error"]);
+    }
+
+    #[test]
+    fn register() {
+        let mut registry = Registry::builtin();
+        registry.register(Box::new(NoName));
+        assert_eq!(lint(&registry, "on: push\n"), vec!["0..0 X001 note"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "rule `X001` is already registered")]
+    fn register_twice() {
+        let mut registry = Registry::new();
+        registry.register(Box::new(NoName));
+        registry.register(Box::new(NoName));
+    }
+}
@@ -0,0 +1,41 @@
+use crate::expression::Span;
+use crate::rule::{Category, LintContext};
+use crate::{Diagnostic, Diagnostics, Severity};
+
+/// A check of workflow files, identified by a stable id so that it can be documented,
+/// configured and suppressed on its own.
+///
+/// Rules are run by a [Registry](crate::rule::Registry), which also holds rules
+/// registered by other crates.
+pub trait Rule: Send + Sync {
+    /// A stable id, e.g. `AL002`.
+    fn id(&self) -> &'static str;
+
+    /// A short kebab-case name, e.g. `schema`.
+    fn name(&self) -> &'static str;
+
+    /// The kind of problems the rule finds.
+    fn category(&self) -> Category;
+
+    /// The severity of the diagnostics of the rule unless configured otherwise.
+    fn default_severity(&self) -> Severity;
+
+    /// One sentence describing what the rule checks.
+    fn description(&self) -> &'static str;
+
+    /// Why the rule exists and how to solve the problems it finds, in a few paragraphs.
+    fn explanation(&self) -> &'static str;
+
+    /// Where to read about what the rule checks.
+    fn help_uri(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Report the problems found in a workflow file.
+    fn check(&self, context: &LintContext, diagnostics: &mut Diagnostics);
+
+    /// A diagnostic of the rule with its default severity.
+    fn diagnostic(&self, message: String, span: Span) -> Diagnostic {
+        Diagnostic::new(self.id(), self.default_severity(), message, span)
+    }
+}
@@ -0,0 +1,336 @@
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+use crate::diagnostic::{Edit, Fix};
+use crate::expression::Span;
+use crate::rule::{Registry, Rule};
+use crate::yaml::Comment;
+use crate::{Diagnostics, Document};
+
+/// Turns rules off for the line after the comment.
+const DISABLE_NEXT_LINE: &str = "act-lint-disable-next-line";
+
+/// Turns rules off until an `act-lint-enable` comment or the end of the file.
+const DISABLE: &str = "act-lint-disable";
+
+/// Turns rules turned off by `act-lint-disable` back on.
+const ENABLE: &str = "act-lint-enable";
+
+/// A comment turning rules off for some lines, e.g. `# act-lint-disable-next-line AL005`.
+///
+/// The rules are separated by commas or spaces, a reason can follow ` -- `. Without rules,
+/// every rule is turned off.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suppression {
+    /// The comment.
+    pub span: Span,
+
+    /// The ids of the rules turned off with the span of each in the comment, empty for
+    /// every rule.
+    pub rules: Vec<(&'static str, Span)>,
+
+    /// The lines the rules are off for, starting at 1.
+    pub lines: RangeInclusive<usize>,
+}
+
+/// The suppression comments of a document.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Suppressions {
+    items: Vec<Suppression>,
+    /// Comments that cannot be honored, e.g. with an unknown rule.
+    problems: Vec<(Span, String)>,
+}
+
+impl Suppressions {
+    /// Find the suppression comments of a document, rules are given by id or name.
+    pub fn parse(document: &Document, registry: &Registry) -> Self {
+        let mut suppressions = Suppressions::default();
+        // `act-lint-disable` comments waiting for their `act-lint-enable`
+        let mut open: Vec<Suppression> = vec![];
+        for comment in document.comments() {
+            let Some((directive, rules)) = directive(document.source(), comment) else {
+                continue;
+            };
+            let line = document.location(comment.span.start).line;
+            let mut ids = vec![];
+            for (name, span) in rules {
+                match registry.find(name) {
+                    Some(rule) => ids.push((rule.id(), span)),
+                    None => suppressions
+                        .problems
+                        .push((span, format!("unknown rule `{}`", name))),
+                }
+            }
+            match directive {
+                DISABLE_NEXT_LINE => suppressions.items.push(Suppression {
+                    span: comment.span,
+                    rules: ids,
+                    lines: line + 1..=line + 1,
+                }),
+                DISABLE => open.push(Suppression {
+                    span: comment.span,
+                    rules: ids,
+                    lines: line..=usize::MAX,
+                }),
+                _ => {
+                    let (closed, still_open): (Vec<_>, Vec<_>) =
+                        open.into_iter().partition(|suppression| {
+                            ids.is_empty()
+                                || suppression.rules.is_empty()
+                                || suppression
+                                    .rules
+                                    .iter()
+                                    .any(|(id, _)| ids.iter().any(|(enabled, _)| id == enabled))
+                        });
+                    open = still_open;
+                    if closed.is_empty() {
+                        suppressions.problems.push((
+                            comment.span,
+                            format!("`{}` without a matching `{}`", ENABLE, DISABLE),
+                        ));
+                    }
+                    suppressions
+                        .items
+                        .extend(closed.into_iter().map(|suppression| Suppression {
+                            lines: *suppression.lines.start()..=line,
+                            ..suppression
+                        }));
+                }
+            }
+        }
+        suppressions.items.extend(open);
+        suppressions
+            .items
+            .sort_by_key(|suppression| suppression.span.start);
+        suppressions
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Suppression> {
+        self.items.iter()
+    }
+
+    /// Remove the suppressed diagnostics, then report the problems of the comments and the
+    /// suppressions that suppressed nothing as diagnostics of `unused`.
+    ///
+    /// Rules that are not enabled report nothing, their suppressions are not unused.
+    pub fn apply(
+        &self,
+        document: &Document,
+        diagnostics: Diagnostics,
+        registry: &Registry,
+        unused: &dyn Rule,
+    ) -> Diagnostics {
+        let mut used = HashSet::new();
+        let mut kept: Diagnostics = diagnostics
+            .into_iter()
+            .filter(|diagnostic| {
+                let line = document.location(diagnostic.span.start).line;
+                let mut suppressed = false;
+                for (i, suppression) in self.items.iter().enumerate() {
+                    if !suppression.lines.contains(&line) {
+                        continue;
+                    }
+                    if suppression.rules.is_empty() {
+                        used.insert((i, None));
+                        suppressed = true;
+                    } else if suppression
+                        .rules
+                        .iter()
+                        .any(|(id, _)| *id == diagnostic.rule)
+                    {
+                        used.insert((i, Some(diagnostic.rule)));
+                        suppressed = true;
+                    }
+                }
+                !suppressed
+            })
+            .collect();
+
+        for (span, message) in &self.problems {
+            kept.push(unused.diagnostic(format!("{} in a suppression comment", message), *span));
+        }
+        for (i, suppression) in self.items.iter().enumerate() {
+            if suppression.rules.is_empty() {
+                if !used.contains(&(i, None)) {
+                    kept.push(
+                        unused
+                            .diagnostic(
+                                "unused suppression, no problem is reported on the suppressed lines"
+                                    .to_string(),
+                                suppression.span,
+                            )
+                            .with_help("remove the comment")
+                            .with_fix(remove_comment(document.source(), suppression.span)),
+                    );
+                }
+                continue;
+            }
+            let unused_rules = suppression
+                .rules
+                .iter()
+                .filter(|(id, _)| registry.is_enabled(id) && !used.contains(&(i, Some(*id))))
+                .collect::<Vec<_>>();
+            if unused_rules.len() == suppression.rules.len() {
+                let ids = unused_rules
+                    .iter()
+                    .map(|(id, _)| format!("`{}`", id))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                kept.push(
+                    unused
+                        .diagnostic(
+                            format!(
+                                "unused suppression of {}, no problem of the rule is reported on the suppressed lines",
+                                ids
+                            ),
+                            suppression.span,
+                        )
+                        .with_help("remove the comment")
+                        .with_fix(remove_comment(document.source(), suppression.span)),
+                );
+            } else {
+                for (id, span) in unused_rules {
+                    kept.push(
+                        unused
+                            .diagnostic(
+                                format!(
+                                    "unused suppression of `{}`, no problem of the rule is reported on the suppressed lines",
+                                    id
+                                ),
+                                *span,
+                            )
+                            .with_help(format!("remove `{}` from the comment", id)),
+                    );
+                }
+            }
+        }
+        kept
+    }
+}
+
+/// The directive of a suppression comment and its rules with their spans.
+fn directive<'a>(
+    source: &'a str,
+    comment: &Comment,
+) -> Option<(&'static str, Vec<(&'a str, Span)>)> {
+    let text = &source[comment.span.start..comment.span.end];
+    let start = text.find(|c: char| c != '#' && !c.is_whitespace())?;
+    let rest = &text[start..];
+    let directive = [DISABLE_NEXT_LINE, DISABLE, ENABLE]
+        .into_iter()
+        .find(|directive| {
+            rest.strip_prefix(directive)
+                .is_some_and(|after| after.is_empty() || after.starts_with(char::is_whitespace))
+        })?;
+    let offset = start + directive.len();
+    let args = &text[offset..];
+    let args = &args[..args.find(" --").unwrap_or(args.len())];
+    let mut rules = vec![];
+    let mut token_start = None;
+    for (i, c) in args
+        .char_indices()
+        .chain(std::iter::once((args.len(), ' ')))
+    {
+        let separator = c == ',' || c.is_whitespace();
+        match (token_start, separator) {
+            (None, false) => token_start = Some(i),
+            (Some(s), true) => {
+                let span_start = comment.span.start + offset + s;
+                rules.push((&args[s..i], Span::new(span_start, span_start + i - s)));
+                token_start = None;
+            }
+            _ => {}
+        }
+    }
+    Some((directive, rules))
+}
+
+/// Remove a comment, with its line when it is alone on it.
+fn remove_comment(source: &str, span: Span) -> Fix {
+    let line_start = source[..span.start].rfind('\n').map_or(0, |i| i + 1);
+    let span = if source[line_start..span.start].trim().is_empty() {
+        let end = source[span.end..]
+            .find('\n')
+            .map_or(source.len(), |i| span.end + i + 1);
+        Span::new(line_start, end)
+    } else {
+        let start = source[..span.start].trim_end().len();
+        Span::new(start, span.end)
+    };
+    Fix::new("remove the comment", vec![Edit::new(span, "")])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint(source: &str) -> Vec<String> {
+        Registry::builtin()
+            .lint(source, None)
+            .iter()
+            .map(|d| {
+                format!(
+                    "{}: {}: {}",
+                    Document::parse(source).unwrap().location(d.span.start),
+                    d.rule,
+                    d.message
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn disable_next_line() {
+        assert_eq!(
+            lint("on: push\nenv:\n  # act-lint-disable-next-line AL005 -- set by a script\n  GITHUB_A: 1\n  GITHUB_B: 2\n"),
+            vec!["5:3: AL005: env.GITHUB_B: names starting with `GITHUB_` are reserved for the default environment variables"]
+        );
+    }
+
+    #[test]
+    fn disable_enable() {
+        assert_eq!(
+            lint("on: push\nenv:\n  # act-lint-disable reserved-env-name\n  GITHUB_A: 1\n  GITHUB_B: 2\n  # act-lint-enable\n  GITHUB_C: 3\n"),
+            vec!["7:3: AL005: env.GITHUB_C: names starting with `GITHUB_` are reserved for the default environment variables"]
+        );
+        assert!(lint("on: push\n# act-lint-disable\nname: [CI]\nenv:\n  GITHUB_A: 1\n").is_empty());
+    }
+
+    #[test]
+    fn unused() {
+        assert_eq!(
+            lint("on: push # act-lint-disable-next-line\nname: CI\n"),
+            vec!["1:10: AL006: unused suppression, no problem is reported on the suppressed lines"]
+        );
+        assert_eq!(
+            lint("on: push\nenv:\n  # act-lint-disable-next-line AL005, AL002\n  GITHUB_A: 1\n"),
+            vec!["3:39: AL006: unused suppression of `AL002`, no problem of the rule is reported on the suppressed lines"]
+        );
+    }
+
+    #[test]
+    fn problems() {
+        assert_eq!(
+            lint("on: push\n# act-lint-enable\n# act-lint-disable-next-line AL999\nname: CI\n"),
+            vec![
+                "2:1: AL006: `act-lint-enable` without a matching `act-lint-disable` in a suppression comment",
+                "3:1: AL006: unused suppression, no problem is reported on the suppressed lines",
+                "3:30: AL006: unknown rule `AL999` in a suppression comment",
+            ]
+        );
+    }
+
+    #[test]
+    fn not_a_suppression() {
+        assert!(lint("on: push # act-lint-disabled\nname: CI # see act-lint-disable\n").is_empty());
+    }
+
+    #[test]
+    fn remove() {
+        let source = "a: 1\n  # act-lint-disable\nb: 2 # act-lint-disable\n";
+        let fix = remove_comment(source, Span::new(7, 25));
+        assert_eq!(fix.edits[0].span, Span::new(5, 26));
+        let fix = remove_comment(source, Span::new(31, 49));
+        assert_eq!(fix.edits[0].span, Span::new(30, 49));
+    }
+}
@@ -8,11 +8,10 @@ use std::fmt;
 #[derive(Debug, Eq, PartialEq, Validate)]
 pub enum Concurrency {
     /// A string or expression concurrency group.
-    #[validate(min_length = 1)]
-    String(String),
+    String(#[validate(min_length = 1)] String),
 
     /// A concurrency group with cancel-in-progress.
-    ConcurrencyWithCancel(ConcurrencyWithCancel),
+    ConcurrencyWithCancel(#[validate] ConcurrencyWithCancel),
 }
 
 /// A concurrency group with cancel-in-progress.
@@ -83,6 +82,7 @@ impl Serialize for Concurrency {
 #[cfg(test)]
 mod tests {
     use super::Concurrency;
+    use serde_valid::Validate;
     use serde_yaml::from_str;
 
     #[test]
@@ -147,4 +147,22 @@ mod tests {
         let err = serde_yaml::to_string(&s).unwrap_err();
         assert_eq!(err.to_string(), "group cannot be empty");
     }
+
+    #[test]
+    fn validate_empty_string_err() {
+        let s = Concurrency::String("".to_string());
+        assert_eq!(
+            s.validate().unwrap_err().to_string(),
+            r#"{"errors":["The length of the value must be `>= 1`."]}"#
+        );
+    }
+
+    #[test]
+    fn validate_empty_group_err() {
+        let s = Concurrency::ConcurrencyWithCancel(super::ConcurrencyWithCancel {
+            group: "".to_string(),
+            cancel_in_progress: true,
+        });
+        assert!(s.validate().is_err());
+    }
 }
@@ -0,0 +1,555 @@
+use std::fmt;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::de::{MapAccess, Visitor};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_valid::validation::{Error, Errors};
+use serde_valid::Validate;
+use serde_with::skip_serializing_none;
+
+use crate::expression::ExpressionInString;
+use crate::workflow::Env;
+
+lazy_static! {
+    /// `[registry[:port]/]name[:tag][@digest]`, the name is made of lowercase components.
+    static ref IMAGE: Regex = Regex::new(concat!(
+        r"^(?:[a-zA-Z0-9-]+(?:\.[a-zA-Z0-9-]+)*(?::[0-9]+)?/)?",
+        r"[a-z0-9]+(?:(?:[._]|__|-+)[a-z0-9]+)*",
+        r"(?:/[a-z0-9]+(?:(?:[._]|__|-+)[a-z0-9]+)*)*",
+        r"(?::[a-zA-Z0-9_][a-zA-Z0-9_.-]{0,127})?",
+        r"(?:@[a-zA-Z][a-zA-Z0-9]*(?:[-_+.][a-zA-Z][a-zA-Z0-9]*)*:[0-9a-fA-F]{32,})?$"
+    ))
+    .unwrap();
+
+    /// `[host-ip:][host-port:]container-port[/protocol]`, ports can be ranges.
+    static ref PORT: Regex = Regex::new(concat!(
+        r"^(?:(?:[0-9]{1,3}(?:\.[0-9]{1,3}){3}|\[[0-9a-fA-F:]+\]):)?",
+        r"(?:([0-9]+(?:-[0-9]+)?)?:)?",
+        r"([0-9]+(?:-[0-9]+)?)",
+        r"(?:/(?:tcp|udp|sctp))?$"
+    ))
+    .unwrap();
+}
+
+/// The options of a volume, after the container path.
+const VOLUME_MODES: [&str; 14] = [
+    "ro",
+    "rw",
+    "z",
+    "Z",
+    "consistent",
+    "cached",
+    "delegated",
+    "nocopy",
+    "shared",
+    "slave",
+    "private",
+    "rshared",
+    "rslave",
+    "rprivate",
+];
+
+/// A container to run the steps of a job in, or a service container of a job.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idcontainer>
+#[derive(Debug, PartialEq)]
+pub enum Container {
+    /// The image of the container, e.g. `container: node:18`.
+    Image(Image),
+
+    /// The image of the container with its settings.
+    Config(Box<ContainerConfig>),
+}
+
+/// The image of a container with its settings.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct ContainerConfig {
+    /// The Docker image to run, e.g. `node:18` or `ghcr.io/owner/image:1.0`.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idcontainerimage>
+    #[serde(rename = "image", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub image: Option<Image>,
+
+    /// The credentials of the registry of the image.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idcontainercredentials>
+    pub credentials: Option<Credentials>,
+
+    /// Environment variables of the container.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idcontainerenv>
+    #[serde(rename = "env", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub env: Option<Env>,
+
+    /// Ports of the container to expose.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idcontainerports>
+    #[serde(rename = "ports", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub ports: Option<Vec<Port>>,
+
+    /// Volumes to mount in the container.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idcontainervolumes>
+    #[serde(rename = "volumes", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub volumes: Option<Vec<Volume>>,
+
+    /// Options passed to `docker create`, e.g. `--cpus 1`.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idcontaineroptions>
+    pub options: Option<String>,
+}
+
+/// The credentials of a container registry.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idcontainercredentials>
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Credentials {
+    pub username: ExpressionInString,
+
+    /// The password, it should come from a secret, e.g. `${{ secrets.REGISTRY_TOKEN }}`.
+    pub password: ExpressionInString,
+}
+
+/// A Docker image reference, `[registry/]name[:tag][@digest]`, possibly with expressions.
+#[derive(Debug, PartialEq)]
+pub struct Image(ExpressionInString);
+
+/// A port mapping, `[host-ip:][host-port:]container-port[/protocol]`, e.g. `8080:80`.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Port(String);
+
+/// A volume mount, `[source:]container-path[:mode]`, e.g. `my_docker_volume:/volume_mount`.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Volume(String);
+
+impl Container {
+    /// The image of the container, if any.
+    pub fn image(&self) -> Option<&Image> {
+        match self {
+            Container::Image(image) => Some(image),
+            Container::Config(config) => config.image.as_ref(),
+        }
+    }
+
+    /// The registry credentials of the container, if any.
+    pub fn credentials(&self) -> Option<&Credentials> {
+        match self {
+            Container::Image(_) => None,
+            Container::Config(config) => config.credentials.as_ref(),
+        }
+    }
+
+    /// The environment variables of the container, if any.
+    pub fn env(&self) -> Option<&Env> {
+        match self {
+            Container::Image(_) => None,
+            Container::Config(config) => config.env.as_ref(),
+        }
+    }
+}
+
+impl Image {
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// The image reference as written, with its expressions.
+    pub fn reference(&self) -> &ExpressionInString {
+        &self.0
+    }
+}
+
+impl Port {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Volume {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Returns true for a value that depends on an expression, it is only known when the job runs.
+fn has_expression(s: &str) -> bool {
+    s.contains("${{")
+}
+
+/// Reports a single problem of a value.
+fn invalid(message: String) -> Result<(), Errors> {
+    Err(Errors::NewType(vec![Error::Custom(message)]))
+}
+
+// `container: node:20` is reported at `container` itself, the errors of a configuration
+// at its fields, e.g. `container.image`.
+impl Validate for Container {
+    fn validate(&self) -> Result<(), Errors> {
+        match self {
+            Container::Image(image) => image.validate(),
+            Container::Config(config) => config.validate(),
+        }
+    }
+}
+
+impl Validate for Image {
+    fn validate(&self) -> Result<(), Errors> {
+        let image = self.as_str();
+        if image.is_empty() {
+            invalid("the image must not be empty".to_string())
+        } else if has_expression(image) || IMAGE.is_match(image) {
+            Ok(())
+        } else {
+            invalid(format!(
+                "invalid image `{}`, expected `[registry/]name[:tag][@digest]` with a lowercase name, e.g. `node:18`",
+                image
+            ))
+        }
+    }
+}
+
+impl Validate for Port {
+    fn validate(&self) -> Result<(), Errors> {
+        if has_expression(&self.0) {
+            return Ok(());
+        }
+        let Some(captures) = PORT.captures(&self.0) else {
+            return invalid(format!(
+                "invalid port mapping `{}`, expected `[host-ip:][host-port:]container-port[/protocol]`, e.g. `8080:80`",
+                self.0
+            ));
+        };
+        let out_of_range = captures
+            .iter()
+            .skip(1)
+            .flatten()
+            .flat_map(|ports| ports.as_str().split('-'))
+            .find(|port| !matches!(port.parse::<u32>(), Ok(1..=65535)));
+        match out_of_range {
+            Some(port) => invalid(format!(
+                "invalid port mapping `{}`, port `{}` is not between 1 and 65535",
+                self.0, port
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Validate for Volume {
+    fn validate(&self) -> Result<(), Errors> {
+        if has_expression(&self.0) {
+            return Ok(());
+        }
+        let parts = self.0.split(':').collect::<Vec<_>>();
+        let reason = match parts[..] {
+            [_] | [_, _] | [_, _, _] if parts.iter().any(|part| part.is_empty()) => {
+                Some("a part is empty".to_string())
+            }
+            [target] | [_, target] | [_, target, _] if !target.starts_with('/') => Some(format!(
+                "the path in the container `{}` must be absolute",
+                target
+            )),
+            [_, _, modes] => modes
+                .split(',')
+                .find(|mode| !VOLUME_MODES.contains(mode))
+                .map(|mode| format!("unknown mode `{}`", mode)),
+            [_] | [_, _] => None,
+            _ => Some("too many `:`".to_string()),
+        };
+        match reason {
+            Some(reason) => invalid(format!(
+                "invalid volume `{}`, {}, expected `[source:]/path/in/container[:mode]`",
+                self.0, reason
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+struct ContainerVisitor;
+
+impl<'de> Visitor<'de> for ContainerVisitor {
+    type Value = Container;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an image or a map with an image")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Image::deserialize(de::value::StrDeserializer::new(v)).map(Container::Image)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        Deserialize::deserialize(de::value::MapAccessDeserializer::new(map)).map(Container::Config)
+    }
+}
+
+impl<'de> Deserialize<'de> for Container {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ContainerVisitor)
+    }
+}
+
+impl Serialize for Container {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Container::Image(image) => image.serialize(serializer),
+            Container::Config(config) => config.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Image {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ExpressionInString::deserialize(deserializer).map(Image)
+    }
+}
+
+impl Serialize for Image {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+struct PortVisitor;
+
+impl<'de> Visitor<'de> for PortVisitor {
+    type Value = Port;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a port number or a port mapping")
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Port(v.to_string()))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Port(v.to_string()))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Port(v.to_string()))
+    }
+}
+
+impl<'de> Deserialize<'de> for Port {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(PortVisitor)
+    }
+}
+
+impl Serialize for Port {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0.parse::<u64>() {
+            Ok(port) => serializer.serialize_u64(port),
+            Err(_) => serializer.serialize_str(&self.0),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Volume {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(Volume)
+    }
+}
+
+impl Serialize for Volume {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::{from_str, to_string};
+
+    use super::*;
+    use crate::error::ValidationError;
+
+    fn messages(container: &str) -> Vec<String> {
+        match from_str::<Container>(container).unwrap().validate() {
+            Ok(()) => vec![],
+            Err(errors) => ValidationError::flatten(&errors)
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn deserialize() {
+        let c: Container = from_str(
+            r#"
+        image: ghcr.io/owner/image:1.0
+        credentials:
+          username: ${{ github.actor }}
+          password: ${{ secrets.GITHUB_TOKEN }}
+        env:
+          NODE_ENV: development
+        ports:
+          - 80
+          - 8080:80/tcp
+        volumes:
+          - my_docker_volume:/volume_mount
+        options: --cpus 1
+        "#,
+        )
+        .unwrap();
+        assert_eq!(c.image().unwrap().as_str(), "ghcr.io/owner/image:1.0");
+        assert_eq!(
+            c.credentials().unwrap().password.as_str(),
+            "${{ secrets.GITHUB_TOKEN }}"
+        );
+        let Container::Config(config) = c else {
+            panic!("not a map");
+        };
+        assert_eq!(
+            config
+                .ports
+                .unwrap()
+                .iter()
+                .map(Port::as_str)
+                .collect::<Vec<_>>(),
+            vec!["80", "8080:80/tcp"]
+        );
+        assert_eq!(config.options, Some("--cpus 1".to_string()));
+    }
+
+    #[test]
+    fn deserialize_image() {
+        let c: Container = from_str("node:18").unwrap();
+        assert!(matches!(&c, Container::Image(image) if image.as_str() == "node:18"));
+        assert!(c.credentials().is_none());
+    }
+
+    #[test]
+    fn deserialize_err() {
+        let err = from_str::<Container>("image: node\nport: 80").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("unknown field `port`, expected one of `image`"));
+        let err = from_str::<Container>("image: node\ncredentials:\n  username: me\n").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("credentials: missing field `password`"));
+    }
+
+    #[test]
+    fn serialize() {
+        let source = "image: redis\nports:\n- 6379\n- 127.0.0.1:6380:6379\n";
+        let c: Container = from_str(source).unwrap();
+        assert_eq!(to_string(&c).unwrap(), source);
+    }
+
+    #[test]
+    fn validate() {
+        for image in [
+            "node",
+            "node:18-alpine",
+            "library/node@sha256:0123456789abcdef0123456789abcdef",
+            "localhost:5000/my_image:latest",
+            "${{ matrix.image }}",
+        ] {
+            assert!(messages(image).is_empty(), "{}", image);
+        }
+        assert!(messages(
+            r#"
+        image: node
+        ports:
+          - 80
+          - 8000-8010:8000-8010
+          - "[::1]:80:80/udp"
+        volumes:
+          - /data
+          - ./src:/src:ro,z
+          - ${{ github.workspace }}:/w
+        "#
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn validate_image_err() {
+        assert_eq!(
+            messages("Node:18"),
+            vec!["invalid image `Node:18`, expected `[registry/]name[:tag][@digest]` with a lowercase name, e.g. `node:18`"]
+        );
+        assert_eq!(
+            messages("image: ''"),
+            vec!["image: the image must not be empty"]
+        );
+    }
+
+    #[test]
+    fn validate_ports_err() {
+        assert_eq!(
+            messages("image: node\nports: [80, 'http', 70000:80]"),
+            vec![
+                "ports[1]: invalid port mapping `http`, expected `[host-ip:][host-port:]container-port[/protocol]`, e.g. `8080:80`",
+                "ports[2]: invalid port mapping `70000:80`, port `70000` is not between 1 and 65535",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_volumes_err() {
+        assert_eq!(
+            messages("image: node\nvolumes: [data, 'a:b', '/a:/b:rx', 'a::/b', 'a:/b:ro:x']"),
+            vec![
+                "volumes[0]: invalid volume `data`, the path in the container `data` must be absolute, expected `[source:]/path/in/container[:mode]`",
+                "volumes[1]: invalid volume `a:b`, the path in the container `b` must be absolute, expected `[source:]/path/in/container[:mode]`",
+                "volumes[2]: invalid volume `/a:/b:rx`, unknown mode `rx`, expected `[source:]/path/in/container[:mode]`",
+                "volumes[3]: invalid volume `a::/b`, a part is empty, expected `[source:]/path/in/container[:mode]`",
+                "volumes[4]: invalid volume `a:/b:ro:x`, too many `:`, expected `[source:]/path/in/container[:mode]`",
+            ]
+        );
+    }
+}
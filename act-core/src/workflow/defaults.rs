@@ -32,16 +32,14 @@ impl<'de> Visitor<'de> for DefaultsVisitor {
     where
         A: MapAccess<'de>,
     {
-        while let Some(key) = map.next_key::<String>()? {
-            return match key.as_str() {
-                "run" => map
-                    .next_value::<Run>()
-                    .map(|run| Defaults { run })
-                    .and_then(|d| d.validate().map(|_| d).map_err(de::Error::custom)),
+        match map.next_key::<String>()? {
+            Some(key) => match key.as_str() {
+                // the rules are checked by `validate`, which reports the path of `run`
+                "run" => map.next_value::<Run>().map(|run| Defaults { run }),
                 _ => Err(de::Error::unknown_field(&key, &["run"])),
-            };
+            },
+            None => Err(de::Error::missing_field("run")),
         }
-        Err(de::Error::missing_field("run"))
     }
 }
 
@@ -61,6 +59,7 @@ mod tests {
     use serde_yaml::from_str;
 
     use super::*;
+    use crate::workflow::Shell;
 
     #[test]
     fn deserialize_ok() {
@@ -77,7 +76,7 @@ mod tests {
             Defaults {
                 run: Run {
                     working_directory: Some("/home/runner/work/my-repo/my-repo".to_string()),
-                    shell: Some("bash".to_string()),
+                    shell: Some(Shell::Bash),
                 },
             }
         );
@@ -1,18 +1,39 @@
+use crate::expression::ExpressionInString;
 use crate::{error_expression, Error};
+use indexmap::IndexMap;
 use lazy_static::lazy_static;
 use regex::Regex;
-use serde::de::{Deserialize, Deserializer, Visitor};
-use serde::ser::{Serialize, Serializer};
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_valid::validation::{Errors, ObjectErrors};
+use serde_valid::Validate;
 use std::fmt;
 
 lazy_static! {
     static ref RE: Regex = Regex::new(r"^.*\$\{\{(.|[\r\n])*}}.*$").unwrap();
 }
 
+/// Prefix of the default environment variables set by GitHub.
+const RESERVED_PREFIX: &str = "GITHUB_";
+
 /// A EnvStringExpression or map of environment variables
-#[derive(Debug, Eq, PartialEq)]
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#env>
+#[derive(Debug, PartialEq)]
 pub enum Env {
     String(String),
+
+    /// Environment variable names to values, in the order they are defined, including
+    /// invalid and duplicate names which are reported by validation.
+    Map(Vec<(String, EnvValue)>),
+}
+
+/// The value of an environment variable or of an action input.
+#[derive(Debug, PartialEq)]
+pub enum EnvValue {
+    String(ExpressionInString),
+    Number(f64),
+    Bool(bool),
 }
 
 impl Env {
@@ -24,6 +45,79 @@ impl Env {
             Err(error_expression!(s))
         }
     }
+
+    /// Returns the names of the variables that start with `GITHUB_`, GitHub reserves these
+    /// for its default environment variables and may override them.
+    ///
+    /// <https://docs.github.com/en/actions/learn-github-actions/variables#naming-conventions-for-environment-variables>
+    pub fn reserved_names(&self) -> Vec<&str> {
+        match self {
+            Env::String(_) => vec![],
+            Env::Map(vars) => vars
+                .iter()
+                .map(|(name, _)| name)
+                .filter(|name| name.to_uppercase().starts_with(RESERVED_PREFIX))
+                .map(String::as_str)
+                .collect(),
+        }
+    }
+}
+
+/// Checks an environment variable name, names cannot contain `=` or start with a digit.
+fn check_name(name: &str) -> Result<(), String> {
+    if name.is_empty()
+        || name.contains(['=', '\0'])
+        || name.starts_with(|c: char| c.is_ascii_digit())
+    {
+        Err(format!(
+            "invalid environment variable name `{}`, a name cannot be empty, start with a digit or contain `=`",
+            name
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+impl fmt::Display for EnvValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvValue::String(s) => write!(f, "{}", s),
+            EnvValue::Number(n) => write!(f, "{}", n),
+            EnvValue::Bool(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+// The names are checked once every variable is read, so that each invalid or duplicate
+// name is reported at its own entry.
+impl Validate for Env {
+    fn validate(&self) -> Result<(), Errors> {
+        let Env::Map(vars) = self else {
+            return Ok(());
+        };
+        let mut properties = IndexMap::<String, Errors>::new();
+        for (i, (name, _)) in vars.iter().enumerate() {
+            let err = if vars[..i].iter().any(|(other, _)| other == name) {
+                format!("duplicate environment variable `{}`", name)
+            } else if let Err(err) = check_name(name) {
+                err
+            } else {
+                continue;
+            };
+            let err = serde_valid::validation::Error::Custom(err);
+            match properties.get_mut(name) {
+                Some(Errors::NewType(errors)) => errors.push(err),
+                _ => {
+                    properties.insert(name.clone(), Errors::NewType(vec![err]));
+                }
+            }
+        }
+        if properties.is_empty() {
+            Ok(())
+        } else {
+            Err(Errors::Object(ObjectErrors::new(vec![], properties)))
+        }
+    }
 }
 
 struct EnvVisitor;
@@ -37,14 +131,20 @@ impl<'de> Visitor<'de> for EnvVisitor {
 
     fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
     where
-        E: serde::de::Error,
+        E: de::Error,
     {
-        let val = Env::string_expression(v);
-        if val.is_ok() {
-            Ok(val.unwrap())
-        } else {
-            Err(val.map_err(serde::de::Error::custom).unwrap_err())
+        Env::string_expression(v).map_err(de::Error::custom)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut vars = vec![];
+        while let Some(name) = map.next_key::<String>()? {
+            vars.push((name, map.next_value()?));
         }
+        Ok(Env::Map(vars))
     }
 }
 
@@ -53,7 +153,69 @@ impl<'de> Deserialize<'de> for Env {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_string(EnvVisitor)
+        deserializer.deserialize_any(EnvVisitor)
+    }
+}
+
+struct EnvValueVisitor;
+
+impl<'de> Visitor<'de> for EnvValueVisitor {
+    type Value = EnvValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string, number or boolean")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(EnvValue::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(EnvValue::Number(v as f64))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(EnvValue::Number(v as f64))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(EnvValue::Number(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        ExpressionInString::deserialize(de::value::StrDeserializer::new(v)).map(EnvValue::String)
+    }
+
+    /// A value left empty, e.g. `FOO:`, is an empty string.
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_str("")
+    }
+}
+
+impl<'de> Deserialize<'de> for EnvValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(EnvValueVisitor)
     }
 }
 
@@ -70,6 +232,30 @@ impl Serialize for Env {
                     Err(serde::ser::Error::custom(error_expression!(s)))
                 }
             }
+            Env::Map(vars) => {
+                let mut map = serializer.serialize_map(Some(vars.len()))?;
+                for (name, value) in vars {
+                    map.serialize_entry(name, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl Serialize for EnvValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            EnvValue::String(s) => s.serialize(serializer),
+            EnvValue::Number(n) if n.fract() == 0.0 && *n >= 0.0 => {
+                serializer.serialize_u64(*n as u64)
+            }
+            EnvValue::Number(n) if n.fract() == 0.0 => serializer.serialize_i64(*n as i64),
+            EnvValue::Number(n) => serializer.serialize_f64(*n),
+            EnvValue::Bool(b) => serializer.serialize_bool(*b),
         }
     }
 }
@@ -77,7 +263,7 @@ impl Serialize for Env {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::error_expression;
+    use crate::error::ValidationError;
     use serde_yaml::{from_str, to_string};
 
     #[test]
@@ -93,6 +279,66 @@ mod tests {
         assert_eq!(result.to_string(), x.to_string());
     }
 
+    #[test]
+    fn deserialize_map_ok() {
+        let e: Env = from_str(
+            r#"
+        SERVER: production
+        PORT: 8080
+        DEBUG: false
+        TOKEN: ${{ secrets.TOKEN }}
+        "#,
+        )
+        .unwrap();
+        let Env::Map(vars) = e else {
+            panic!("expected a map")
+        };
+        assert_eq!(
+            vars.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            vec!["SERVER", "PORT", "DEBUG", "TOKEN"]
+        );
+        assert_eq!(vars[1].1, EnvValue::Number(8080.0));
+        assert_eq!(vars[2].1, EnvValue::Bool(false));
+        assert_eq!(vars[3].1.to_string(), "${{ secrets.TOKEN }}");
+        let e: Env = from_str("EMPTY:").unwrap();
+        assert_eq!(
+            e,
+            Env::Map(vec![(
+                "EMPTY".to_string(),
+                EnvValue::String(ExpressionInString::new("").unwrap())
+            )])
+        );
+    }
+
+    #[test]
+    fn validate_name_err() {
+        let e: Env = from_str("1FOO: a\n'FOO=BAR': b\nFOO: c\nBAR: d\nFOO: e\n1FOO: f").unwrap();
+        let errors = ValidationError::flatten(&e.validate().unwrap_err());
+        assert_eq!(
+            errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                "1FOO: invalid environment variable name `1FOO`, a name cannot be empty, start with a digit or contain `=`",
+                "1FOO: duplicate environment variable `1FOO`",
+                "FOO=BAR: invalid environment variable name `FOO=BAR`, a name cannot be empty, start with a digit or contain `=`",
+                "FOO: duplicate environment variable `FOO`",
+            ]
+        );
+    }
+
+    #[test]
+    fn deserialize_value_err() {
+        assert_eq!(
+            from_str::<Env>("FOO: [a]").unwrap_err().to_string(),
+            "FOO: invalid type: sequence, expected a string, number or boolean at line 1 column 6"
+        );
+    }
+
+    #[test]
+    fn reserved_names() {
+        let e: Env = from_str("GITHUB_TOKEN: x\nMY_GITHUB_TOKEN: y\ngithub_sha: z").unwrap();
+        assert_eq!(e.reserved_names(), vec!["GITHUB_TOKEN", "github_sha"]);
+    }
+
     #[test]
     fn serialize_string_ok() {
         let e: String = to_string(&Env::String("abc=${{ inputs.ABC }}".to_string()))
@@ -110,4 +356,11 @@ mod tests {
             error_expression!("abc=inputs.ABC").to_string()
         );
     }
+
+    #[test]
+    fn serialize_map_round_trip() {
+        let yaml = "SERVER: production\nPORT: 8080\nRATIO: 0.5\nDEBUG: false\nTOKEN: ${{ secrets.TOKEN }}\n";
+        let e: Env = from_str(yaml).unwrap();
+        assert_eq!(to_string(&e).unwrap(), yaml);
+    }
 }
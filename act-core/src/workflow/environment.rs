@@ -0,0 +1,137 @@
+use std::fmt;
+
+use serde::de::{MapAccess, Visitor};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_valid::Validate;
+use serde_with::skip_serializing_none;
+
+/// The environment that a job references.
+#[derive(Debug, Eq, PartialEq, Validate)]
+pub enum Environment {
+    /// The name of the environment.
+    String(#[validate(min_length = 1)] String),
+
+    /// An environment with a name and an url.
+    EnvironmentWithUrl(#[validate] EnvironmentWithUrl),
+}
+
+/// An environment with a name and an url.
+#[skip_serializing_none]
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(expecting = "a map with name and url", deny_unknown_fields)]
+#[derive(Validate)]
+pub struct EnvironmentWithUrl {
+    /// The name of the environment configured in the repo.
+    #[validate(min_length = 1)]
+    pub name: String,
+
+    /// A deployment URL, it is shown in the deployments page and in the workflow graph.
+    pub url: Option<String>,
+}
+
+struct EnvironmentVisitor;
+
+impl<'de> Visitor<'de> for EnvironmentVisitor {
+    type Value = Environment;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("string or a map with name and url")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Environment::String(v.to_string()))
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        Deserialize::deserialize(de::value::MapAccessDeserializer::new(map))
+            .map(Environment::EnvironmentWithUrl)
+    }
+}
+
+impl<'de> Deserialize<'de> for Environment {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(EnvironmentVisitor)
+    }
+}
+
+impl Serialize for Environment {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Environment::String(s) => serializer.serialize_str(s),
+            Environment::EnvironmentWithUrl(e) => e.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_valid::Validate;
+    use serde_yaml::{from_str, to_string};
+
+    use super::*;
+
+    #[test]
+    fn deserialize_string() {
+        let e: Environment = from_str("production").unwrap();
+        assert_eq!(e, Environment::String("production".to_string()));
+    }
+
+    #[test]
+    fn deserialize_environment_with_url() {
+        let e: Environment = from_str(
+            r#"
+        name: production
+        url: https://github.com
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            e,
+            Environment::EnvironmentWithUrl(EnvironmentWithUrl {
+                name: "production".to_string(),
+                url: Some("https://github.com".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_environment_with_url_err() {
+        let err = from_str::<Environment>("url: https://github.com").unwrap_err();
+        assert_eq!(err.to_string(), "missing field `name`");
+    }
+
+    #[test]
+    fn deserialize_type_err() {
+        let err = from_str::<Environment>("[production]").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid type: sequence, expected string or a map with name and url"
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        let e = Environment::EnvironmentWithUrl(EnvironmentWithUrl {
+            name: "production".to_string(),
+            url: None,
+        });
+        assert_eq!(to_string(&e).unwrap(), "name: production\n");
+    }
+
+    #[test]
+    fn validate_err() {
+        assert!(Environment::String("".to_string()).validate().is_err());
+    }
+}
@@ -0,0 +1,624 @@
+use std::fmt;
+
+use indexmap::IndexMap;
+use serde::de::{SeqAccess, Visitor};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_valid::validation::{ArrayErrors, Error, Errors, ObjectErrors};
+use serde_valid::Validate;
+use serde_with::skip_serializing_none;
+
+use crate::expression::{Condition, ExpressionInString};
+use crate::workflow::{
+    ActionRef, Concurrency, Container, Defaults, Env, Environment, JobId, Permissions, RunsOn,
+    Step, Strategy,
+};
+use crate::Expression;
+
+/// A job is a set of steps that execute on the same runner.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_id>
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+#[rule(unique_step_ids(steps))]
+#[rule(workflow_ref(uses, runs_on, steps, container, services))]
+#[rule(runs_on_or_uses(runs_on, uses))]
+pub struct Job {
+    /// Concurrency ensures that only a single job using the same concurrency group will run at a time.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idconcurrency>
+    #[serde(rename = "concurrency", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub concurrency: Option<Concurrency>,
+
+    /// A container to run any steps in a job that don't already specify a container.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idcontainer>
+    #[serde(rename = "container", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub container: Option<Container>,
+
+    /// Prevents a workflow run from failing when a job fails.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idcontinue-on-error>
+    #[serde(rename = "continue-on-error")]
+    pub continue_on_error: Option<ContinueOnError>,
+
+    /// A map of default settings that will apply to all steps in the job.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_iddefaults>
+    #[serde(rename = "defaults", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub defaults: Option<Defaults>,
+
+    /// A map of environment variables that are available to all steps in the job.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idenv>
+    #[serde(rename = "env", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub env: Option<Env>,
+
+    /// The environment that the job references.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idenvironment>
+    #[serde(rename = "environment", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub environment: Option<Environment>,
+
+    /// A conditional to prevent a job from running unless a condition is met.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idif>
+    #[serde(rename = "if")]
+    pub r#if: Option<Condition>,
+
+    /// The name of the job displayed on GitHub.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idname>
+    pub name: Option<ExpressionInString>,
+
+    /// Jobs that must complete successfully before this job will run.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idneeds>
+    pub needs: Option<Needs>,
+
+    /// A map of outputs for the job, available to all downstream jobs that depend on this job.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idoutputs>
+    pub outputs: Option<IndexMap<String, ExpressionInString>>,
+
+    /// Modify the default permissions granted to the `GITHUB_TOKEN` for this job.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idpermissions>
+    #[serde(rename = "permissions", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub permissions: Option<Permissions>,
+
+    /// The type of machine to run the job on.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idruns-on>
+    #[serde(rename = "runs-on", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub runs_on: Option<RunsOn>,
+
+    /// Secrets passed to a called reusable workflow, a map or `inherit`.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idsecrets>
+    pub secrets: Option<serde_yaml::Value>,
+
+    /// Service containers to host services for a job in a workflow.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idservices>
+    #[serde(rename = "services", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub services: Option<IndexMap<String, Container>>,
+
+    /// A sequence of tasks called steps.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idsteps>
+    #[serde(rename = "steps", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub steps: Option<Vec<Step>>,
+
+    /// A matrix strategy to automatically create multiple job runs.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstrategy>
+    #[serde(rename = "strategy", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub strategy: Option<Strategy>,
+
+    /// The maximum number of minutes to let a job run before GitHub automatically cancels it.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idtimeout-minutes>
+    #[serde(rename = "timeout-minutes")]
+    pub timeout_minutes: Option<TimeoutMinutes>,
+
+    /// The location and version of a reusable workflow file to run as a job.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_iduses>
+    pub uses: Option<ActionRef>,
+
+    /// Inputs passed to a called reusable workflow.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idwith>
+    pub with: Option<IndexMap<String, serde_yaml::Value>>,
+}
+
+/// Fails when steps of the job share an id, the error is reported on each duplicate.
+fn unique_step_ids(steps: &Option<Vec<Step>>) -> Result<(), Error> {
+    let mut first = IndexMap::new();
+    let mut items = IndexMap::new();
+    for (index, step) in steps.iter().flatten().enumerate() {
+        let Some(id) = &step.id else {
+            continue;
+        };
+        if let Some(used) = first.get(id) {
+            let mut properties = IndexMap::new();
+            properties.insert(
+                "id".to_string(),
+                Errors::NewType(vec![Error::Custom(format!(
+                    "the step id `{}` is already used by `steps[{}]`",
+                    id, used
+                ))]),
+            );
+            items.insert(index, Errors::Object(ObjectErrors::new(vec![], properties)));
+        } else {
+            first.insert(id, index);
+        }
+    }
+    if items.is_empty() {
+        return Ok(());
+    }
+    let mut properties = IndexMap::new();
+    properties.insert(
+        "steps".to_string(),
+        Errors::Array(ArrayErrors::new(vec![], items)),
+    );
+    Err(Error::Properties(ObjectErrors::new(vec![], properties)))
+}
+
+/// Fails when `uses` does not reference a workflow file, or when the job also has the
+/// settings of a job that runs its own steps.
+fn workflow_ref(
+    uses: &Option<ActionRef>,
+    runs_on: &Option<RunsOn>,
+    steps: &Option<Vec<Step>>,
+    container: &Option<Container>,
+    services: &Option<IndexMap<String, Container>>,
+) -> Result<(), Error> {
+    let Some(uses) = uses else {
+        return Ok(());
+    };
+    let conflicts = [
+        ("runs-on", runs_on.is_some()),
+        ("steps", steps.is_some()),
+        ("container", container.is_some()),
+        ("services", services.is_some()),
+    ]
+    .iter()
+    .filter(|(_, is_set)| *is_set)
+    .map(|(key, _)| format!("`{}`", key))
+    .collect::<Vec<_>>();
+    if !conflicts.is_empty() {
+        return Err(Error::Custom(format!(
+            "a job calling a reusable workflow with `uses` cannot have {}",
+            conflicts.join(", ")
+        )));
+    }
+    let path = match uses {
+        ActionRef::Docker { .. } => {
+            return Err(Error::Custom(
+                "a job can only call a reusable workflow, not a Docker image".to_string(),
+            ))
+        }
+        ActionRef::Local(path) => Some(path),
+        ActionRef::Repository { path, .. } => path.as_ref(),
+    };
+    match path {
+        Some(path) if path.ends_with(".yml") || path.ends_with(".yaml") => Ok(()),
+        _ => Err(Error::Custom(format!(
+            "`{}` is not a workflow file, expected e.g. `owner/repo/.github/workflows/build.yml@v1` or `./.github/workflows/build.yml`",
+            uses
+        ))),
+    }
+}
+
+/// Fails when the job has neither a runner nor a reusable workflow to call.
+fn runs_on_or_uses(runs_on: &Option<RunsOn>, uses: &Option<ActionRef>) -> Result<(), Error> {
+    if runs_on.is_none() && uses.is_none() {
+        Err(Error::Custom(
+            "a job must have `runs-on`, or `uses` to call a reusable workflow".to_string(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// A single job id or a list of job ids that must complete before a job will run.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Needs {
+    /// A single job id.
+    JobId(JobId),
+
+    /// A list of job ids.
+    JobIds(Vec<JobId>),
+}
+
+impl Needs {
+    /// Returns the needed job ids.
+    pub fn job_ids(&self) -> &[JobId] {
+        match self {
+            Needs::JobId(id) => std::slice::from_ref(id),
+            Needs::JobIds(ids) => ids,
+        }
+    }
+}
+
+struct NeedsVisitor;
+
+impl<'de> Visitor<'de> for NeedsVisitor {
+    type Value = Needs;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a job id or a sequence of job ids")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        JobId::new(v).map(Needs::JobId).map_err(de::Error::custom)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Deserialize::deserialize(de::value::SeqAccessDeserializer::new(seq)).map(Needs::JobIds)
+    }
+}
+
+impl<'de> Deserialize<'de> for Needs {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(NeedsVisitor)
+    }
+}
+
+impl Serialize for Needs {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Needs::JobId(id) => id.serialize(serializer),
+            Needs::JobIds(ids) => ids.serialize(serializer),
+        }
+    }
+}
+
+/// A boolean or an expression that evaluates to a boolean.
+#[derive(Debug, Eq, PartialEq)]
+pub enum ContinueOnError {
+    Bool(bool),
+    Expression(Expression),
+}
+
+struct ContinueOnErrorVisitor;
+
+impl<'de> Visitor<'de> for ContinueOnErrorVisitor {
+    type Value = ContinueOnError;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a boolean or an expression")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ContinueOnError::Bool(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Expression::deserialize(de::value::StrDeserializer::new(v)).map(ContinueOnError::Expression)
+    }
+}
+
+impl<'de> Deserialize<'de> for ContinueOnError {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ContinueOnErrorVisitor)
+    }
+}
+
+impl Serialize for ContinueOnError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ContinueOnError::Bool(b) => serializer.serialize_bool(*b),
+            ContinueOnError::Expression(e) => e.serialize(serializer),
+        }
+    }
+}
+
+/// A number of minutes or an expression that evaluates to a number.
+#[derive(Debug, Eq, PartialEq)]
+pub enum TimeoutMinutes {
+    Number(u64),
+    Expression(Expression),
+}
+
+struct TimeoutMinutesVisitor;
+
+impl<'de> Visitor<'de> for TimeoutMinutesVisitor {
+    type Value = TimeoutMinutes;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a whole number greater than 0 or an expression")
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        if v == 0 {
+            Err(de::Error::invalid_value(de::Unexpected::Unsigned(v), &self))
+        } else {
+            Ok(TimeoutMinutes::Number(v))
+        }
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match u64::try_from(v) {
+            Ok(v) => self.visit_u64(v),
+            Err(_) => Err(de::Error::invalid_value(de::Unexpected::Signed(v), &self)),
+        }
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Err(de::Error::invalid_value(de::Unexpected::Float(v), &self))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Expression::deserialize(de::value::StrDeserializer::new(v)).map(TimeoutMinutes::Expression)
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeoutMinutes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(TimeoutMinutesVisitor)
+    }
+}
+
+impl Serialize for TimeoutMinutes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            TimeoutMinutes::Number(n) => serializer.serialize_u64(*n),
+            TimeoutMinutes::Expression(e) => e.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::{from_str, to_string};
+
+    use super::*;
+    use crate::error::ValidationError;
+    use crate::error_job_id;
+
+    #[test]
+    fn deserialize() {
+        let j: Job = from_str(
+            r#"
+        name: Build
+        runs-on: ubuntu-latest
+        needs: [lint, test]
+        if: github.event_name == 'push'
+        timeout-minutes: 10
+        continue-on-error: ${{ matrix.experimental }}
+        environment: production
+        outputs:
+          version: ${{ steps.version.outputs.version }}
+        steps:
+          - run: echo hello
+        "#,
+        )
+        .unwrap();
+        assert_eq!(j.name.unwrap().as_str(), "Build");
+        assert_eq!(
+            j.needs.unwrap().job_ids(),
+            &[JobId::new("lint").unwrap(), JobId::new("test").unwrap()]
+        );
+        assert_eq!(j.r#if.unwrap().as_str(), "github.event_name == 'push'");
+        assert_eq!(j.timeout_minutes, Some(TimeoutMinutes::Number(10)));
+        assert!(matches!(
+            j.continue_on_error,
+            Some(ContinueOnError::Expression(_))
+        ));
+        assert_eq!(
+            j.environment,
+            Some(Environment::String("production".to_string()))
+        );
+        assert_eq!(j.outputs.unwrap().len(), 1);
+        assert_eq!(j.steps.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn deserialize_unknown_field_err() {
+        let err = from_str::<Job>("runs_on: ubuntu-latest").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("unknown field `runs_on`, expected one of `concurrency`"));
+    }
+
+    #[test]
+    fn validate_unique_step_ids() {
+        let j: Job = from_str(
+            r#"
+        runs-on: ubuntu-latest
+        steps:
+          - id: build
+            run: make
+          - run: make test
+          - id: build
+            run: make install
+        "#,
+        )
+        .unwrap();
+        let errors = ValidationError::flatten(&j.validate().unwrap_err());
+        assert_eq!(
+            errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["steps[2].id: the step id `build` is already used by `steps[0]`"]
+        );
+    }
+
+    #[test]
+    fn validate_workflow_ref() {
+        let j: Job = from_str("uses: octo-org/ci/.github/workflows/build.yml@v1").unwrap();
+        assert!(j.validate().is_ok());
+        let j: Job = from_str("uses: ./.github/workflows/build.yaml").unwrap();
+        assert!(j.uses.as_ref().unwrap().is_local());
+        assert!(j.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_workflow_ref_err() {
+        let messages = |job: &str| {
+            let j: Job = from_str(job).unwrap();
+            ValidationError::flatten(&j.validate().unwrap_err())
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            messages("uses: actions/checkout@v4"),
+            vec!["`actions/checkout@v4` is not a workflow file, expected e.g. `owner/repo/.github/workflows/build.yml@v1` or `./.github/workflows/build.yml`"]
+        );
+        assert_eq!(
+            messages("uses: docker://alpine:3"),
+            vec!["a job can only call a reusable workflow, not a Docker image"]
+        );
+        assert_eq!(
+            messages(
+                "uses: ./.github/workflows/x.yml\nruns-on: ubuntu-latest\nsteps:\n  - run: make\n"
+            ),
+            vec!["a job calling a reusable workflow with `uses` cannot have `runs-on`, `steps`"]
+        );
+        assert_eq!(
+            messages(
+                "uses: ./.github/workflows/x.yml\ncontainer: node:20\nservices:\n  db: postgres\n"
+            ),
+            vec![
+                "a job calling a reusable workflow with `uses` cannot have `container`, `services`"
+            ]
+        );
+    }
+
+    #[test]
+    fn deserialize_uses_err() {
+        let err = from_str::<Job>("uses: octo-org/ci/.github/workflows/build.yml").unwrap_err();
+        assert!(err.to_string().starts_with(
+            "uses: invalid reference `octo-org/ci/.github/workflows/build.yml`: missing `@ref`"
+        ));
+    }
+
+    #[test]
+    fn deserialize_needs() {
+        let n: Needs = from_str("build").unwrap();
+        assert_eq!(n, Needs::JobId(JobId::new("build").unwrap()));
+    }
+
+    #[test]
+    fn deserialize_needs_err() {
+        let err = from_str::<Needs>("build job").unwrap_err();
+        assert_eq!(err.to_string(), error_job_id!("build job").to_string());
+    }
+
+    #[test]
+    fn deserialize_continue_on_error() {
+        assert_eq!(
+            from_str::<ContinueOnError>("true").unwrap(),
+            ContinueOnError::Bool(true)
+        );
+    }
+
+    #[test]
+    fn deserialize_continue_on_error_err() {
+        let err = from_str::<ContinueOnError>("yes please").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value: string \"yes please\", expected a string matching ^\\$\\{\\{(.|[\\r\\n])*\\}\\}$"
+        );
+    }
+
+    #[test]
+    fn deserialize_timeout_minutes_err() {
+        let err = from_str::<TimeoutMinutes>("true").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid type: boolean `true`, expected a whole number greater than 0 or an expression"
+        );
+        for (minutes, unexpected) in [
+            ("0", "integer `0`"),
+            ("-1", "integer `-1`"),
+            ("1.5", "floating point `1.5`"),
+        ] {
+            assert_eq!(
+                from_str::<TimeoutMinutes>(minutes).unwrap_err().to_string(),
+                format!(
+                    "invalid value: {}, expected a whole number greater than 0 or an expression",
+                    unexpected
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize_outputs_err() {
+        let err = from_str::<Job>("outputs:\n  x: ${{ steps.a.outputs == }}").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("outputs.x: not a valid string expression"));
+    }
+
+    #[test]
+    fn serialize() {
+        let j: Job = from_str(
+            r#"
+        runs-on: ubuntu-latest
+        needs: build
+        timeout-minutes: 10
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            to_string(&j).unwrap(),
+            "needs: build\nruns-on: ubuntu-latest\ntimeout-minutes: 10\n"
+        );
+    }
+}
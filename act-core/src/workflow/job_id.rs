@@ -0,0 +1,141 @@
+use std::fmt;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::de::{Error, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error_job_id;
+
+pub static VALID_JOB_ID_PATTERN: &str = r#"^[_a-zA-Z][a-zA-Z0-9_-]*$"#;
+
+lazy_static! {
+    static ref RE: Regex = Regex::new(VALID_JOB_ID_PATTERN).unwrap();
+}
+
+/// A unique identifier for a job, a string that should match `^[_a-zA-Z][a-zA-Z0-9_-]*$`
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_id>
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct JobId(String);
+
+impl JobId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+    pub fn new(s: &str) -> Result<Self, crate::Error> {
+        if RE.is_match(s) {
+            Ok(JobId(s.to_string()))
+        } else {
+            Err(error_job_id!(s))
+        }
+    }
+}
+
+impl fmt::Display for JobId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&JobId> for String {
+    fn from(id: &JobId) -> Self {
+        id.0.clone()
+    }
+}
+
+struct JobIdVisitor;
+
+impl<'de> Visitor<'de> for JobIdVisitor {
+    type Value = JobId;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_fmt(format_args!("a job id matching {}", VALID_JOB_ID_PATTERN))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        JobId::new(v).map_err(Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for JobId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(JobIdVisitor)
+    }
+}
+
+impl Serialize for JobId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if RE.is_match(&self.0) {
+            serializer.serialize_str(&self.0)
+        } else {
+            Err(serde::ser::Error::custom(error_job_id!(&self.0)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::{from_str, to_string};
+
+    use super::*;
+
+    #[test]
+    fn new_ok() {
+        assert_eq!(
+            JobId::new("build_and-test1").unwrap().as_str(),
+            "build_and-test1"
+        );
+        assert_eq!(JobId::new("_private").unwrap().as_str(), "_private");
+    }
+
+    #[test]
+    fn new_err() {
+        assert_eq!(JobId::new("1build").unwrap_err(), error_job_id!("1build"));
+        assert_eq!(
+            JobId::new("build test").unwrap_err(),
+            error_job_id!("build test")
+        );
+        assert_eq!(JobId::new("").unwrap_err(), error_job_id!(""));
+    }
+
+    #[test]
+    fn deserialize() {
+        assert_eq!(
+            from_str::<JobId>("build").unwrap(),
+            JobId("build".to_string())
+        );
+    }
+
+    #[test]
+    fn deserialize_err() {
+        assert_eq!(
+            from_str::<JobId>("build.test").unwrap_err().to_string(),
+            error_job_id!("build.test").to_string()
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!(to_string(&JobId("build".to_string())).unwrap(), "build\n");
+    }
+
+    #[test]
+    fn serialize_err() {
+        assert_eq!(
+            to_string(&JobId("build.test".to_string()))
+                .unwrap_err()
+                .to_string(),
+            error_job_id!("build.test").to_string()
+        );
+    }
+}
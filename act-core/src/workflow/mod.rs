@@ -1,11 +1,34 @@
+pub mod action_ref;
 pub mod concurrency;
+pub mod container;
 pub mod defaults;
 pub mod env;
+pub mod environment;
+pub mod job;
+pub mod job_id;
+pub mod on;
+pub mod permissions;
 pub mod run;
+pub mod runs_on;
+pub mod shell;
+pub mod step;
+pub mod strategy;
+#[allow(clippy::module_inception)]
 pub mod workflow;
 
+pub use action_ref::{ActionRef, GitRef};
 pub use concurrency::Concurrency;
+pub use container::Container;
 pub use defaults::Defaults;
-pub use env::Env;
+pub use env::{Env, EnvValue};
+pub use environment::Environment;
+pub use job::Job;
+pub use job_id::JobId;
+pub use on::On;
+pub use permissions::Permissions;
 pub use run::Run;
+pub use runs_on::RunsOn;
+pub use shell::Shell;
+pub use step::Step;
+pub use strategy::{Matrix, Strategy};
 pub use workflow::Workflow;
@@ -0,0 +1,245 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::Visitor;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+macro_rules! events {
+    ($($variant:ident => $name:literal [$($activity_type:literal),*]),+ $(,)?) => {
+        /// An event that triggers a workflow.
+        ///
+        /// <https://docs.github.com/en/actions/using-workflows/events-that-trigger-workflows>
+        #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+        pub enum Event {
+            $($variant,)+
+        }
+
+        impl Event {
+            /// All known events.
+            pub const ALL: &'static [Event] = &[$(Event::$variant,)+];
+
+            /// The name of the event as used in a workflow file.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Event::$variant => $name,)+
+                }
+            }
+
+            /// The activity types that can be used to filter this event with `types`.
+            pub fn activity_types(&self) -> &'static [&'static str] {
+                match self {
+                    $(Event::$variant => &[$($activity_type),*],)+
+                }
+            }
+        }
+
+        impl FromStr for Event {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($name => Ok(Event::$variant),)+
+                    _ => Err(format!("unknown event `{}`", s)),
+                }
+            }
+        }
+    };
+}
+
+events! {
+    BranchProtectionRule => "branch_protection_rule" ["created", "edited", "deleted"],
+    CheckRun => "check_run" ["created", "rerequested", "completed", "requested_action"],
+    CheckSuite => "check_suite" ["completed", "requested", "rerequested"],
+    Create => "create" [],
+    Delete => "delete" [],
+    Deployment => "deployment" [],
+    DeploymentStatus => "deployment_status" [],
+    Discussion => "discussion" [
+        "created", "edited", "deleted", "transferred", "pinned", "unpinned", "labeled", "unlabeled",
+        "locked", "unlocked", "category_changed", "answered", "unanswered"
+    ],
+    DiscussionComment => "discussion_comment" ["created", "edited", "deleted"],
+    Fork => "fork" [],
+    Gollum => "gollum" [],
+    IssueComment => "issue_comment" ["created", "edited", "deleted"],
+    Issues => "issues" [
+        "opened", "edited", "deleted", "transferred", "pinned", "unpinned", "closed", "reopened",
+        "assigned", "unassigned", "labeled", "unlabeled", "locked", "unlocked", "milestoned",
+        "demilestoned"
+    ],
+    Label => "label" ["created", "edited", "deleted"],
+    Member => "member" ["added", "edited", "deleted"],
+    MergeGroup => "merge_group" ["checks_requested"],
+    Milestone => "milestone" ["created", "closed", "opened", "edited", "deleted"],
+    PageBuild => "page_build" [],
+    Project => "project" ["created", "updated", "closed", "reopened", "edited", "deleted"],
+    ProjectCard => "project_card" ["created", "moved", "converted", "edited", "deleted"],
+    ProjectColumn => "project_column" ["created", "updated", "moved", "deleted"],
+    Public => "public" [],
+    PullRequest => "pull_request" [
+        "assigned", "unassigned", "labeled", "unlabeled", "opened", "edited", "closed", "reopened",
+        "synchronize", "converted_to_draft", "ready_for_review", "locked", "unlocked",
+        "review_requested", "review_request_removed", "auto_merge_enabled", "auto_merge_disabled",
+        "enqueued", "dequeued", "milestoned", "demilestoned"
+    ],
+    PullRequestReview => "pull_request_review" ["submitted", "edited", "dismissed"],
+    PullRequestReviewComment => "pull_request_review_comment" ["created", "edited", "deleted"],
+    PullRequestTarget => "pull_request_target" [
+        "assigned", "unassigned", "labeled", "unlabeled", "opened", "edited", "closed", "reopened",
+        "synchronize", "converted_to_draft", "ready_for_review", "locked", "unlocked",
+        "review_requested", "review_request_removed", "auto_merge_enabled", "auto_merge_disabled",
+        "enqueued", "dequeued", "milestoned", "demilestoned"
+    ],
+    Push => "push" [],
+    RegistryPackage => "registry_package" ["published", "updated"],
+    Release => "release" [
+        "published", "unpublished", "created", "edited", "deleted", "prereleased", "released"
+    ],
+    RepositoryDispatch => "repository_dispatch" [],
+    Schedule => "schedule" [],
+    Status => "status" [],
+    Watch => "watch" ["started"],
+    WorkflowCall => "workflow_call" [],
+    WorkflowDispatch => "workflow_dispatch" [],
+    WorkflowRun => "workflow_run" ["requested", "completed", "in_progress"],
+}
+
+impl Event {
+    /// Check that an activity type can be used to filter this event.
+    ///
+    /// `repository_dispatch` accepts any custom type sent with the dispatch request.
+    pub fn check_activity_type(&self, activity_type: &str) -> Result<(), String> {
+        if *self == Event::RepositoryDispatch {
+            return Ok(());
+        }
+        let known = self.activity_types();
+        if known.is_empty() {
+            Err(format!("event `{}` does not support activity types", self))
+        } else if known.contains(&activity_type) {
+            Ok(())
+        } else {
+            Err(format!(
+                "unknown activity type `{}` for event `{}`, expected one of {}",
+                activity_type,
+                self,
+                known
+                    .iter()
+                    .map(|k| format!("`{}`", k))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        }
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl From<&Event> for String {
+    fn from(event: &Event) -> Self {
+        event.as_str().to_string()
+    }
+}
+
+struct EventVisitor;
+
+impl<'de> Visitor<'de> for EventVisitor {
+    type Value = Event;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("the name of an event that triggers a workflow")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        v.parse().map_err(de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Event {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(EventVisitor)
+    }
+}
+
+impl Serialize for Event {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::{from_str, to_string};
+
+    use super::*;
+
+    #[test]
+    fn from_str_all() {
+        for event in Event::ALL {
+            assert_eq!(event.as_str().parse::<Event>().unwrap(), *event);
+        }
+    }
+
+    #[test]
+    fn deserialize() {
+        assert_eq!(
+            from_str::<Event>("pull_request").unwrap(),
+            Event::PullRequest
+        );
+    }
+
+    #[test]
+    fn deserialize_err() {
+        assert_eq!(
+            from_str::<Event>("pull-request").unwrap_err().to_string(),
+            "unknown event `pull-request`"
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!(
+            to_string(&Event::WorkflowDispatch).unwrap(),
+            "workflow_dispatch\n"
+        );
+    }
+
+    #[test]
+    fn check_activity_type_ok() {
+        assert_eq!(
+            Event::PullRequest.check_activity_type("synchronize"),
+            Ok(())
+        );
+        assert_eq!(
+            Event::RepositoryDispatch.check_activity_type("custom"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn check_activity_type_err() {
+        assert_eq!(
+            Event::Watch.check_activity_type("opened"),
+            Err(
+                "unknown activity type `opened` for event `watch`, expected one of `started`"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            Event::Push.check_activity_type("opened"),
+            Err("event `push` does not support activity types".to_string())
+        );
+    }
+}
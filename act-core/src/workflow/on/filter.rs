@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+use serde_with::skip_serializing_none;
+
+/// Filters for the `push` event.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#onpushbranchestagsbranches-ignoretags-ignore>
+#[skip_serializing_none]
+#[derive(Debug, Default, Eq, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+#[rule(branches_exclusive(branches, branches_ignore))]
+#[rule(tags_exclusive(tags, tags_ignore))]
+#[rule(paths_exclusive(paths, paths_ignore))]
+pub struct PushFilter {
+    /// Only run for pushes to branches matching these patterns.
+    pub branches: Option<Vec<String>>,
+
+    /// Don't run for pushes to branches matching these patterns.
+    #[serde(rename = "branches-ignore")]
+    pub branches_ignore: Option<Vec<String>>,
+
+    /// Only run for pushes to tags matching these patterns.
+    pub tags: Option<Vec<String>>,
+
+    /// Don't run for pushes to tags matching these patterns.
+    #[serde(rename = "tags-ignore")]
+    pub tags_ignore: Option<Vec<String>>,
+
+    /// Only run when at least one changed file matches these patterns.
+    pub paths: Option<Vec<String>>,
+
+    /// Don't run when all changed files match these patterns.
+    #[serde(rename = "paths-ignore")]
+    pub paths_ignore: Option<Vec<String>>,
+}
+
+/// Filters for the `pull_request` and `pull_request_target` events.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#onpull_requestpull_request_targetbranchesbranches-ignore>
+#[skip_serializing_none]
+#[derive(Debug, Default, Eq, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+#[rule(branches_exclusive(branches, branches_ignore))]
+#[rule(paths_exclusive(paths, paths_ignore))]
+pub struct PullRequestFilter {
+    /// Only run for these activity types.
+    pub types: Option<Vec<String>>,
+
+    /// Only run for pull requests targeting branches matching these patterns.
+    pub branches: Option<Vec<String>>,
+
+    /// Don't run for pull requests targeting branches matching these patterns.
+    #[serde(rename = "branches-ignore")]
+    pub branches_ignore: Option<Vec<String>>,
+
+    /// Only run when at least one changed file matches these patterns.
+    pub paths: Option<Vec<String>>,
+
+    /// Don't run when all changed files match these patterns.
+    #[serde(rename = "paths-ignore")]
+    pub paths_ignore: Option<Vec<String>>,
+}
+
+/// Activity types filter for events that don't support any other filter.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#oneventnametypes>
+#[skip_serializing_none]
+#[derive(Debug, Default, Eq, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct ActivityTypes {
+    /// Only run for these activity types.
+    pub types: Option<Vec<String>>,
+}
+
+/// Fails when both a filter and its `-ignore` counterpart are set.
+fn exclusive<T>(
+    filter: &Option<T>,
+    ignore: &Option<T>,
+    name: &str,
+) -> Result<(), serde_valid::validation::Error> {
+    if filter.is_some() && ignore.is_some() {
+        Err(serde_valid::validation::Error::Custom(format!(
+            "`{0}` and `{0}-ignore` cannot be used together for the same event",
+            name
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn branches_exclusive(
+    branches: &Option<Vec<String>>,
+    branches_ignore: &Option<Vec<String>>,
+) -> Result<(), serde_valid::validation::Error> {
+    exclusive(branches, branches_ignore, "branches")
+}
+
+fn tags_exclusive(
+    tags: &Option<Vec<String>>,
+    tags_ignore: &Option<Vec<String>>,
+) -> Result<(), serde_valid::validation::Error> {
+    exclusive(tags, tags_ignore, "tags")
+}
+
+fn paths_exclusive(
+    paths: &Option<Vec<String>>,
+    paths_ignore: &Option<Vec<String>>,
+) -> Result<(), serde_valid::validation::Error> {
+    exclusive(paths, paths_ignore, "paths")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_valid::Validate;
+    use serde_yaml::{from_str, to_string};
+
+    use super::*;
+
+    #[test]
+    fn deserialize_push() {
+        let f: PushFilter = from_str(
+            r#"
+        branches: [main, 'releases/**']
+        tags: ['v*']
+        paths-ignore: ['docs/**']
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            f,
+            PushFilter {
+                branches: Some(vec!["main".to_string(), "releases/**".to_string()]),
+                tags: Some(vec!["v*".to_string()]),
+                paths_ignore: Some(vec!["docs/**".to_string()]),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_push_err() {
+        let err = from_str::<PushFilter>("types: [opened]").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("unknown field `types`, expected one of `branches`"));
+    }
+
+    #[test]
+    fn validate_push_err() {
+        let f: PushFilter = from_str(
+            r#"
+        branches: [main]
+        branches-ignore: [dev]
+        tags: ['v*']
+        tags-ignore: ['v0*']
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            f.validate().unwrap_err().to_string(),
+            r#"{"errors":["`branches` and `branches-ignore` cannot be used together for the same event","`tags` and `tags-ignore` cannot be used together for the same event"],"properties":{}}"#
+        );
+    }
+
+    #[test]
+    fn validate_pull_request_err() {
+        let f: PullRequestFilter = from_str(
+            r#"
+        paths: ['src/**']
+        paths-ignore: ['docs/**']
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            f.validate().unwrap_err().to_string(),
+            r#"{"errors":["`paths` and `paths-ignore` cannot be used together for the same event"],"properties":{}}"#
+        );
+    }
+
+    #[test]
+    fn serialize_pull_request() {
+        let f = PullRequestFilter {
+            types: Some(vec!["opened".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(to_string(&f).unwrap(), "types:\n- opened\n");
+    }
+
+    #[test]
+    fn deserialize_activity_types() {
+        let t: ActivityTypes = from_str("types: [created]").unwrap();
+        assert_eq!(t.types, Some(vec!["created".to_string()]));
+    }
+}
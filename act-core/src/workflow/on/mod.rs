@@ -0,0 +1,16 @@
+pub mod event;
+pub mod filter;
+#[allow(clippy::module_inception)]
+pub mod on;
+pub mod schedule;
+pub mod workflow_call;
+pub mod workflow_dispatch;
+pub mod workflow_run;
+
+pub use event::Event;
+pub use filter::{ActivityTypes, PullRequestFilter, PushFilter};
+pub use on::{EventConfig, On};
+pub use schedule::Schedule;
+pub use workflow_call::{WorkflowCall, WorkflowCallInput, WorkflowCallOutput, WorkflowCallSecret};
+pub use workflow_dispatch::{WorkflowDispatch, WorkflowDispatchInput};
+pub use workflow_run::WorkflowRun;
@@ -0,0 +1,412 @@
+use std::fmt;
+
+use indexmap::IndexMap;
+use serde::de::{DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_valid::validation::{ArrayErrors, Error, Errors, ObjectErrors};
+use serde_valid::Validate;
+
+use crate::workflow::on::{
+    ActivityTypes, Event, PullRequestFilter, PushFilter, Schedule, WorkflowCall, WorkflowDispatch,
+    WorkflowRun,
+};
+
+/// The events that trigger a workflow.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#on>
+#[derive(Debug, PartialEq)]
+pub enum On {
+    /// A single event, e.g. `on: push`.
+    Event(Event),
+
+    /// A list of events, e.g. `on: [push, pull_request]`.
+    Events(Vec<Event>),
+
+    /// A map of events to their configuration, e.g. `on: { push: { branches: [main] } }`.
+    Map(IndexMap<Event, EventConfig>),
+}
+
+impl On {
+    /// Returns the events that trigger the workflow.
+    pub fn events(&self) -> Vec<Event> {
+        match self {
+            On::Event(event) => vec![*event],
+            On::Events(events) => events.clone(),
+            On::Map(map) => map.keys().copied().collect(),
+        }
+    }
+}
+
+/// The configuration of an event, its shape depends on the event.
+#[derive(Debug, PartialEq)]
+pub enum EventConfig {
+    /// Activity types of events without any other filter.
+    ActivityTypes(ActivityTypes),
+
+    /// Filters of the `pull_request` and `pull_request_target` events.
+    PullRequest(PullRequestFilter),
+
+    /// Filters of the `push` event.
+    Push(PushFilter),
+
+    /// Cron schedules of the `schedule` event.
+    Schedule(Vec<Schedule>),
+
+    /// Inputs, outputs and secrets of the `workflow_call` event.
+    WorkflowCall(WorkflowCall),
+
+    /// Inputs of the `workflow_dispatch` event.
+    WorkflowDispatch(WorkflowDispatch),
+
+    /// Filters of the `workflow_run` event.
+    WorkflowRun(WorkflowRun),
+}
+
+impl EventConfig {
+    /// Returns the activity types the event is filtered by, if any.
+    pub fn types(&self) -> Option<&[String]> {
+        match self {
+            EventConfig::ActivityTypes(c) => c.types.as_deref(),
+            EventConfig::PullRequest(c) => c.types.as_deref(),
+            EventConfig::WorkflowRun(c) => c.types.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+// Validation is implemented by hand, the derived implementation for newtype variants
+// nests the errors of the inner value as a string and loses their path.
+impl Validate for On {
+    fn validate(&self) -> Result<(), Errors> {
+        let On::Map(events) = self else {
+            return Ok(());
+        };
+        let mut items = IndexMap::new();
+        for (event, config) in events {
+            let types = config
+                .types()
+                .and_then(|types| activity_type_errors(*event, types));
+            let errors = match (config.validate(), types) {
+                (Ok(()), None) => continue,
+                (Err(errors), None) => errors,
+                (Ok(()), Some(types)) => Errors::Object(ObjectErrors::new(
+                    vec![],
+                    IndexMap::from([("types".to_string(), types)]),
+                )),
+                (Err(Errors::Object(mut errors)), Some(types)) => {
+                    errors.properties.insert("types".to_string(), types);
+                    Errors::Object(errors)
+                }
+                (Err(errors), Some(_)) => errors,
+            };
+            items.insert(event.to_string(), errors);
+        }
+        if items.is_empty() {
+            Ok(())
+        } else {
+            Err(Errors::Object(ObjectErrors::new(vec![], items)))
+        }
+    }
+}
+
+/// The errors of the activity types an event cannot be filtered by, at their index in `types`.
+fn activity_type_errors(event: Event, types: &[String]) -> Option<Errors> {
+    let items = types
+        .iter()
+        .enumerate()
+        .filter_map(|(i, activity_type)| {
+            let err = event.check_activity_type(activity_type).err()?;
+            Some((i, Errors::NewType(vec![Error::Custom(err)])))
+        })
+        .collect::<IndexMap<_, _>>();
+    (!items.is_empty()).then(|| Errors::Array(ArrayErrors::new(vec![], items)))
+}
+
+impl Validate for EventConfig {
+    fn validate(&self) -> Result<(), Errors> {
+        match self {
+            EventConfig::ActivityTypes(c) => c.validate(),
+            EventConfig::PullRequest(c) => c.validate(),
+            EventConfig::Push(c) => c.validate(),
+            EventConfig::Schedule(c) => c.validate(),
+            EventConfig::WorkflowCall(c) => c.validate(),
+            EventConfig::WorkflowDispatch(c) => c.validate(),
+            EventConfig::WorkflowRun(c) => c.validate(),
+        }
+    }
+}
+
+/// Deserializes an [EventConfig] in the shape expected by the given event.
+struct EventConfigSeed(Event);
+
+/// Deserializes an optional configuration, a missing configuration is the default one.
+fn config<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de> + Default,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Option::unwrap_or_default)
+}
+
+impl<'de> DeserializeSeed<'de> for EventConfigSeed {
+    type Value = EventConfig;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match self.0 {
+            Event::PullRequest | Event::PullRequestTarget => {
+                config(deserializer).map(EventConfig::PullRequest)
+            }
+            Event::Push => config(deserializer).map(EventConfig::Push),
+            Event::Schedule => Vec::deserialize(deserializer).map(EventConfig::Schedule),
+            Event::WorkflowCall => config(deserializer).map(EventConfig::WorkflowCall),
+            Event::WorkflowDispatch => config(deserializer).map(EventConfig::WorkflowDispatch),
+            Event::WorkflowRun => config(deserializer).map(EventConfig::WorkflowRun),
+            _ => config(deserializer).map(EventConfig::ActivityTypes),
+        }
+    }
+}
+
+struct OnVisitor;
+
+impl<'de> Visitor<'de> for OnVisitor {
+    type Value = On;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an event, a sequence of events or a map of events")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        v.parse().map(On::Event).map_err(de::Error::custom)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Deserialize::deserialize(de::value::SeqAccessDeserializer::new(seq)).map(On::Events)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut events = IndexMap::new();
+        while let Some(event) = map.next_key::<Event>()? {
+            let config = map.next_value_seed(EventConfigSeed(event))?;
+            events.insert(event, config);
+        }
+        Ok(On::Map(events))
+    }
+}
+
+impl<'de> Deserialize<'de> for On {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(OnVisitor)
+    }
+}
+
+impl Serialize for On {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            On::Event(event) => event.serialize(serializer),
+            On::Events(events) => events.serialize(serializer),
+            On::Map(events) => {
+                let mut map = serializer.serialize_map(Some(events.len()))?;
+                for (event, config) in events {
+                    map.serialize_entry(event, config)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl Serialize for EventConfig {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            EventConfig::ActivityTypes(c) if c == &ActivityTypes::default() => {
+                serializer.serialize_unit()
+            }
+            EventConfig::ActivityTypes(c) => c.serialize(serializer),
+            EventConfig::PullRequest(c) if c == &PullRequestFilter::default() => {
+                serializer.serialize_unit()
+            }
+            EventConfig::PullRequest(c) => c.serialize(serializer),
+            EventConfig::Push(c) if c == &PushFilter::default() => serializer.serialize_unit(),
+            EventConfig::Push(c) => c.serialize(serializer),
+            EventConfig::Schedule(c) => c.serialize(serializer),
+            EventConfig::WorkflowCall(c) if c == &WorkflowCall::default() => {
+                serializer.serialize_unit()
+            }
+            EventConfig::WorkflowCall(c) => c.serialize(serializer),
+            EventConfig::WorkflowDispatch(c) if c == &WorkflowDispatch::default() => {
+                serializer.serialize_unit()
+            }
+            EventConfig::WorkflowDispatch(c) => c.serialize(serializer),
+            EventConfig::WorkflowRun(c) => c.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_valid::Validate;
+    use serde_yaml::{from_str, to_string};
+
+    use super::*;
+    use crate::error::ValidationError;
+
+    #[test]
+    fn deserialize_event() {
+        assert_eq!(from_str::<On>("push").unwrap(), On::Event(Event::Push));
+    }
+
+    #[test]
+    fn deserialize_events() {
+        assert_eq!(
+            from_str::<On>("[push, pull_request]").unwrap(),
+            On::Events(vec![Event::Push, Event::PullRequest])
+        );
+    }
+
+    #[test]
+    fn deserialize_map() {
+        let on: On = from_str(
+            r#"
+        push:
+          branches: [main]
+        pull_request:
+        schedule:
+          - cron: '0 0 * * *'
+        issues:
+          types: [opened, labeled]
+        workflow_dispatch:
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            on.events(),
+            vec![
+                Event::Push,
+                Event::PullRequest,
+                Event::Schedule,
+                Event::Issues,
+                Event::WorkflowDispatch
+            ]
+        );
+        let On::Map(map) = on else {
+            panic!("expected a map of events")
+        };
+        assert_eq!(
+            map[&Event::Push],
+            EventConfig::Push(PushFilter {
+                branches: Some(vec!["main".to_string()]),
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            map[&Event::PullRequest],
+            EventConfig::PullRequest(PullRequestFilter::default())
+        );
+    }
+
+    #[test]
+    fn deserialize_unknown_event_err() {
+        let err = from_str::<On>("[push, pull-request]").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            ".[1]: unknown event `pull-request` at line 1 column 8"
+        );
+    }
+
+    #[test]
+    fn deserialize_unknown_event_in_map_err() {
+        let err = from_str::<On>("pushh: {}").unwrap_err();
+        assert_eq!(err.to_string(), "unknown event `pushh`");
+    }
+
+    #[test]
+    fn deserialize_filter_err() {
+        let err = from_str::<On>(
+            r#"
+        pull_request:
+          tags: [v1]
+        "#,
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("pull_request: unknown field `tags`, expected one of `types`"));
+    }
+
+    #[test]
+    fn validate_err() {
+        let on: On = from_str(
+            r#"
+        push:
+          branches: [main]
+          branches-ignore: [dev]
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            on.validate().unwrap_err().to_string(),
+            r#"{"errors":[],"properties":{"push":{"errors":["`branches` and `branches-ignore` cannot be used together for the same event"],"properties":{}}}}"#
+        );
+    }
+
+    #[test]
+    fn validate_activity_types_err() {
+        let on: On = from_str(
+            r#"
+        issues:
+          types: [opened, synchronize]
+        pull_request:
+          types: [opened, synchronize]
+          branches: [main]
+          branches-ignore: [dev]
+        "#,
+        )
+        .unwrap();
+        let errors = ValidationError::flatten(&on.validate().unwrap_err());
+        assert_eq!(
+            errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                "issues.types[1]: unknown activity type `synchronize` for event `issues`, expected one of `opened`, `edited`, `deleted`, `transferred`, `pinned`, `unpinned`, `closed`, `reopened`, `assigned`, `unassigned`, `labeled`, `unlabeled`, `locked`, `unlocked`, `milestoned`, `demilestoned`",
+                "pull_request: `branches` and `branches-ignore` cannot be used together for the same event",
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        let on: On = from_str(
+            r#"
+        push:
+          branches: [main]
+        workflow_dispatch:
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            to_string(&on).unwrap(),
+            "push:\n  branches:\n  - main\nworkflow_dispatch: null\n"
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+
+/// A POSIX cron schedule that triggers a workflow.
+///
+/// <https://docs.github.com/en/actions/using-workflows/events-that-trigger-workflows#schedule>
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct Schedule {
+    /// Five space separated fields: minute, hour, day of month, month and day of week.
+    #[validate(custom(valid_cron))]
+    pub cron: String,
+}
+
+/// A field of a cron expression, with the names its values can also be written with.
+struct Field {
+    name: &'static str,
+    min: u32,
+    max: u32,
+    names: &'static [&'static str],
+}
+
+const FIELDS: [Field; 5] = [
+    Field {
+        name: "minute",
+        min: 0,
+        max: 59,
+        names: &[],
+    },
+    Field {
+        name: "hour",
+        min: 0,
+        max: 23,
+        names: &[],
+    },
+    Field {
+        name: "day of month",
+        min: 1,
+        max: 31,
+        names: &[],
+    },
+    Field {
+        name: "month",
+        min: 1,
+        max: 12,
+        names: &[
+            "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+        ],
+    },
+    Field {
+        name: "day of week",
+        min: 0,
+        max: 6,
+        names: &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"],
+    },
+];
+
+impl Field {
+    /// The value of a number or a name, e.g. `5` or `MAY`, if it is in the range of the field.
+    fn value(&self, s: &str) -> Option<u32> {
+        let value = match number(s) {
+            Some(value) => value,
+            None => {
+                let i = self.names.iter().position(|n| n.eq_ignore_ascii_case(s))?;
+                self.min + i as u32
+            }
+        };
+        (self.min..=self.max).contains(&value).then_some(value)
+    }
+
+    /// Checks an element of the comma separated list of the field, e.g. `*/15` or `MON-FRI`.
+    fn check(&self, element: &str) -> Result<(), String> {
+        let (range, step) = match element.split_once('/') {
+            Some((range, step)) => (range, Some(step)),
+            None => (element, None),
+        };
+        if let Some(step) = step {
+            if !matches!(number(step), Some(step) if step > 0) {
+                return Err(format!(
+                    "`{}` is not a valid step for the {}",
+                    step, self.name
+                ));
+            }
+        }
+        if range == "*" {
+            return Ok(());
+        }
+        let bounds = match range.split_once('-') {
+            Some((start, end)) => self.value(start).zip(self.value(end)),
+            None => self.value(range).map(|value| (value, value)),
+        };
+        match bounds {
+            Some((start, end)) if start <= end => Ok(()),
+            _ => Err(format!(
+                "`{}` is not a valid {}, expected values from {} to {}",
+                range, self.name, self.min, self.max
+            )),
+        }
+    }
+}
+
+/// A number written with ASCII digits only.
+fn number(s: &str) -> Option<u32> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// Checks the fields of a cron expression, separated by single spaces as GitHub requires.
+fn valid_cron(cron: &str) -> Result<(), serde_valid::validation::Error> {
+    let fields = cron
+        .split(|c: char| c.is_ascii_whitespace())
+        .collect::<Vec<_>>();
+    if fields.len() != FIELDS.len() || fields.iter().any(|f| f.is_empty()) {
+        return Err(serde_valid::validation::Error::Custom(
+            "not a valid cron expression, expected five fields: minute, hour, day of month, month and day of week".to_string(),
+        ));
+    }
+    for (field, value) in FIELDS.iter().zip(fields) {
+        for element in value.split(',') {
+            field
+                .check(element)
+                .map_err(serde_valid::validation::Error::Custom)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_valid::Validate;
+    use serde_yaml::from_str;
+
+    use super::*;
+
+    fn schedule(cron: &str) -> Schedule {
+        Schedule {
+            cron: cron.to_string(),
+        }
+    }
+
+    #[test]
+    fn deserialize() {
+        let s: Schedule = from_str("cron: '30 5 * * 1,3'").unwrap();
+        assert_eq!(s, schedule("30 5 * * 1,3"));
+        assert!(s.validate().is_ok());
+    }
+
+    #[test]
+    fn deserialize_err() {
+        let err = from_str::<Schedule>("crons: '* * * * *'").unwrap_err();
+        assert_eq!(err.to_string(), "unknown field `crons`, expected `cron`");
+    }
+
+    #[test]
+    fn validate() {
+        for cron in [
+            "* * * * *",
+            "*/15 0-23/2 1,15 JAN-MAR,dec MON-FRI",
+            "59 23 31 12 6",
+            "0 0 1 */3 SUN",
+        ] {
+            assert!(schedule(cron).validate().is_ok(), "{}", cron);
+        }
+    }
+
+    #[test]
+    fn validate_err() {
+        let s = schedule("@daily");
+        assert_eq!(
+            s.validate().unwrap_err().to_string(),
+            r#"{"errors":[],"properties":{"cron":{"errors":["not a valid cron expression, expected five fields: minute, hour, day of month, month and day of week"]}}}"#
+        );
+        for cron in [
+            "*****",
+            "00 0 * *",
+            "0 0 * * * ",
+            " 0 0 * * *",
+            "0  0 * * *",
+            "0 0 * * * *",
+        ] {
+            assert_eq!(
+                schedule(cron).validate().unwrap_err().to_string(),
+                r#"{"errors":[],"properties":{"cron":{"errors":["not a valid cron expression, expected five fields: minute, hour, day of month, month and day of week"]}}}"#,
+                "{:?}",
+                cron
+            );
+        }
+    }
+
+    #[test]
+    fn validate_field_err() {
+        for (cron, message) in [
+            (
+                "60 * * * *",
+                "`60` is not a valid minute, expected values from 0 to 59",
+            ),
+            (
+                "* 24 * * *",
+                "`24` is not a valid hour, expected values from 0 to 23",
+            ),
+            (
+                "* * 0 * *",
+                "`0` is not a valid day of month, expected values from 1 to 31",
+            ),
+            (
+                "* * * 13 *",
+                "`13` is not a valid month, expected values from 1 to 12",
+            ),
+            (
+                "* * * * 7",
+                "`7` is not a valid day of week, expected values from 0 to 6",
+            ),
+            (
+                "* * * * FRI-MON",
+                "`FRI-MON` is not a valid day of week, expected values from 0 to 6",
+            ),
+            (
+                "1,,2 * * * *",
+                "`` is not a valid minute, expected values from 0 to 59",
+            ),
+            ("*/0 * * * *", "`0` is not a valid step for the minute"),
+            (
+                "* * * * MON/x",
+                "`x` is not a valid step for the day of week",
+            ),
+        ] {
+            let errors = schedule(cron).validate().unwrap_err();
+            assert_eq!(
+                errors.to_string(),
+                format!(
+                    r#"{{"errors":[],"properties":{{"cron":{{"errors":["{}"]}}}}}}"#,
+                    message
+                ),
+                "{}",
+                cron
+            );
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+use serde_with::skip_serializing_none;
+
+/// Configuration of the `workflow_call` event, to make a workflow reusable.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#onworkflow_call>
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct WorkflowCall {
+    /// The inputs passed from the caller workflow.
+    pub inputs: Option<IndexMap<String, WorkflowCallInput>>,
+
+    /// The outputs of the called workflow, available to the caller workflow.
+    pub outputs: Option<IndexMap<String, WorkflowCallOutput>>,
+
+    /// The secrets passed from the caller workflow.
+    pub secrets: Option<IndexMap<String, WorkflowCallSecret>>,
+}
+
+/// An input of a reusable workflow.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#onworkflow_callinputs>
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkflowCallInput {
+    /// A string description of the input.
+    pub description: Option<String>,
+
+    /// A message shown to users using the deprecated input.
+    #[serde(rename = "deprecationMessage")]
+    pub deprecation_message: Option<String>,
+
+    /// Whether the input must be supplied.
+    pub required: Option<bool>,
+
+    /// The default value of the input.
+    pub default: Option<serde_yaml::Value>,
+
+    /// The type of the input.
+    #[serde(rename = "type")]
+    pub input_type: WorkflowCallInputType,
+}
+
+/// The type of a reusable workflow input.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkflowCallInputType {
+    Boolean,
+    Number,
+    String,
+}
+
+/// An output of a reusable workflow.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#onworkflow_calloutputs>
+#[skip_serializing_none]
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkflowCallOutput {
+    /// A string description of the output.
+    pub description: Option<String>,
+
+    /// The value of the output, usually an expression referencing a job output.
+    pub value: String,
+}
+
+/// A secret of a reusable workflow.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#onworkflow_callsecrets>
+#[skip_serializing_none]
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkflowCallSecret {
+    /// A string description of the secret.
+    pub description: Option<String>,
+
+    /// Whether the secret must be supplied.
+    pub required: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::{from_str, to_string};
+
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let w: WorkflowCall = from_str(
+            r#"
+        inputs:
+          config-path:
+            required: true
+            type: string
+        outputs:
+          version:
+            description: The released version
+            value: ${{ jobs.release.outputs.version }}
+        secrets:
+          token:
+            required: true
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            w.inputs.unwrap()["config-path"].input_type,
+            WorkflowCallInputType::String
+        );
+        assert_eq!(
+            w.outputs.unwrap()["version"].value,
+            "${{ jobs.release.outputs.version }}"
+        );
+        assert_eq!(w.secrets.unwrap()["token"].required, Some(true));
+    }
+
+    #[test]
+    fn deserialize_input_type_err() {
+        let err = from_str::<WorkflowCallInput>("type: choice").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "type: unknown variant `choice`, expected one of `boolean`, `number`, `string` at line 1 column 7"
+        );
+    }
+
+    #[test]
+    fn deserialize_input_missing_type_err() {
+        let err = from_str::<WorkflowCallInput>("required: true").unwrap_err();
+        assert_eq!(err.to_string(), "missing field `type`");
+    }
+
+    #[test]
+    fn deserialize_output_err() {
+        let err = from_str::<WorkflowCallOutput>("description: x").unwrap_err();
+        assert_eq!(err.to_string(), "missing field `value`");
+    }
+
+    #[test]
+    fn serialize() {
+        let w = WorkflowCall {
+            secrets: Some(IndexMap::from([(
+                "token".to_string(),
+                WorkflowCallSecret {
+                    description: None,
+                    required: Some(true),
+                },
+            )])),
+            ..Default::default()
+        };
+        assert_eq!(
+            to_string(&w).unwrap(),
+            "secrets:\n  token:\n    required: true\n"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+use serde_with::skip_serializing_none;
+
+/// Configuration of the `workflow_dispatch` event, to manually trigger a workflow.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#onworkflow_dispatch>
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct WorkflowDispatch {
+    /// The inputs the workflow can be triggered with.
+    #[serde(rename = "inputs", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub inputs: Option<IndexMap<String, WorkflowDispatchInput>>,
+}
+
+/// An input of a manually triggered workflow.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#onworkflow_dispatchinputs>
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+#[rule(choice_options(input_type, options))]
+pub struct WorkflowDispatchInput {
+    /// A string description of the input.
+    pub description: Option<String>,
+
+    /// A message shown to users using the deprecated input.
+    #[serde(rename = "deprecationMessage")]
+    pub deprecation_message: Option<String>,
+
+    /// Whether the input must be supplied.
+    pub required: Option<bool>,
+
+    /// The default value of the input.
+    pub default: Option<serde_yaml::Value>,
+
+    /// The type of the input, `string` if not set.
+    #[serde(rename = "type")]
+    pub input_type: Option<WorkflowDispatchInputType>,
+
+    /// The options of a `choice` input.
+    #[validate(min_items = 1)]
+    pub options: Option<Vec<String>>,
+}
+
+/// The type of a manually triggered workflow input.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkflowDispatchInputType {
+    Boolean,
+    Choice,
+    Environment,
+    Number,
+    String,
+}
+
+fn choice_options(
+    input_type: &Option<WorkflowDispatchInputType>,
+    options: &Option<Vec<String>>,
+) -> Result<(), serde_valid::validation::Error> {
+    match (input_type, options) {
+        (Some(WorkflowDispatchInputType::Choice), None) => {
+            Err(serde_valid::validation::Error::Custom(
+                "a `choice` input requires `options`".to_string(),
+            ))
+        }
+        (Some(WorkflowDispatchInputType::Choice), Some(_)) | (_, None) => Ok(()),
+        (_, Some(_)) => Err(serde_valid::validation::Error::Custom(
+            "`options` can only be used with a `choice` input".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_valid::Validate;
+    use serde_yaml::from_str;
+
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let w: WorkflowDispatch = from_str(
+            r#"
+        inputs:
+          log-level:
+            description: Log level
+            required: true
+            default: warning
+            type: choice
+            options: [info, warning, debug]
+          dry-run:
+            type: boolean
+        "#,
+        )
+        .unwrap();
+        let inputs = w.inputs.unwrap();
+        assert_eq!(
+            inputs["log-level"].input_type,
+            Some(WorkflowDispatchInputType::Choice)
+        );
+        assert_eq!(
+            inputs["dry-run"].input_type,
+            Some(WorkflowDispatchInputType::Boolean)
+        );
+    }
+
+    #[test]
+    fn deserialize_type_err() {
+        let err = from_str::<WorkflowDispatchInput>("type: list").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "type: unknown variant `list`, expected one of `boolean`, `choice`, `environment`, `number`, `string` at line 1 column 7"
+        );
+    }
+
+    #[test]
+    fn validate_choice_err() {
+        let w: WorkflowDispatch = from_str(
+            r#"
+        inputs:
+          level:
+            type: choice
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            w.validate().unwrap_err().to_string(),
+            r#"{"errors":[],"properties":{"inputs":{"errors":[],"properties":{"level":{"errors":["a `choice` input requires `options`"],"properties":{}}}}}}"#
+        );
+    }
+
+    #[test]
+    fn validate_options_err() {
+        let i: WorkflowDispatchInput = from_str(
+            r#"
+        type: string
+        options: [a]
+        "#,
+        )
+        .unwrap();
+        assert!(i.validate().is_err());
+    }
+}
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+use serde_with::skip_serializing_none;
+
+use crate::workflow::on::filter::branches_exclusive;
+
+/// Configuration of the `workflow_run` event.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#onworkflow_runbranchesbranches-ignore>
+#[skip_serializing_none]
+#[derive(Debug, Default, Eq, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+#[rule(branches_exclusive(branches, branches_ignore))]
+pub struct WorkflowRun {
+    /// Only run for these activity types.
+    pub types: Option<Vec<String>>,
+
+    /// The names of the workflows that trigger this workflow.
+    #[validate(min_items = 1)]
+    pub workflows: Option<Vec<String>>,
+
+    /// Only run for workflow runs on branches matching these patterns.
+    pub branches: Option<Vec<String>>,
+
+    /// Don't run for workflow runs on branches matching these patterns.
+    #[serde(rename = "branches-ignore")]
+    pub branches_ignore: Option<Vec<String>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_valid::Validate;
+    use serde_yaml::from_str;
+
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let w: WorkflowRun = from_str(
+            r#"
+        workflows: [Build]
+        types: [completed]
+        branches: [main]
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            w,
+            WorkflowRun {
+                types: Some(vec!["completed".to_string()]),
+                workflows: Some(vec!["Build".to_string()]),
+                branches: Some(vec!["main".to_string()]),
+                branches_ignore: None,
+            }
+        );
+    }
+
+    #[test]
+    fn validate_err() {
+        let w: WorkflowRun = from_str(
+            r#"
+        workflows: [Build]
+        branches: [main]
+        branches-ignore: [dev]
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            w.validate().unwrap_err().to_string(),
+            r#"{"errors":["`branches` and `branches-ignore` cannot be used together for the same event"],"properties":{}}"#
+        );
+    }
+}
@@ -0,0 +1,299 @@
+use std::fmt;
+
+use indexmap::IndexMap;
+use serde::de::{MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_valid::validation::{Error, Errors, ObjectErrors};
+use serde_valid::Validate;
+
+/// The permissions granted to the `GITHUB_TOKEN` of a workflow or a job.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#permissions>
+#[derive(Debug, Eq, PartialEq)]
+pub enum Permissions {
+    /// `read-all`, read access to all scopes.
+    ReadAll,
+
+    /// `write-all`, write access to all scopes.
+    WriteAll,
+
+    /// A map of scopes to their access level, scopes that are not listed have no access.
+    /// `{}` disables all permissions.
+    Scopes(IndexMap<PermissionScope, PermissionLevel>),
+}
+
+impl Permissions {
+    /// Returns the access level granted to the given scope.
+    pub fn level(&self, scope: PermissionScope) -> PermissionLevel {
+        match self {
+            Permissions::ReadAll => PermissionLevel::Read,
+            Permissions::WriteAll => PermissionLevel::Write,
+            Permissions::Scopes(scopes) => {
+                scopes.get(&scope).copied().unwrap_or(PermissionLevel::None)
+            }
+        }
+    }
+}
+
+/// A scope of the `GITHUB_TOKEN` permissions.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PermissionScope {
+    Actions,
+    Attestations,
+    Checks,
+    Contents,
+    Deployments,
+    Discussions,
+    IdToken,
+    Issues,
+    Models,
+    Packages,
+    Pages,
+    PullRequests,
+    RepositoryProjects,
+    SecurityEvents,
+    Statuses,
+}
+
+impl PermissionScope {
+    /// The name of the scope as used in a workflow file.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PermissionScope::Actions => "actions",
+            PermissionScope::Attestations => "attestations",
+            PermissionScope::Checks => "checks",
+            PermissionScope::Contents => "contents",
+            PermissionScope::Deployments => "deployments",
+            PermissionScope::Discussions => "discussions",
+            PermissionScope::IdToken => "id-token",
+            PermissionScope::Issues => "issues",
+            PermissionScope::Models => "models",
+            PermissionScope::Packages => "packages",
+            PermissionScope::Pages => "pages",
+            PermissionScope::PullRequests => "pull-requests",
+            PermissionScope::RepositoryProjects => "repository-projects",
+            PermissionScope::SecurityEvents => "security-events",
+            PermissionScope::Statuses => "statuses",
+        }
+    }
+
+    /// The access levels that can be granted to the scope.
+    pub fn levels(&self) -> &'static [PermissionLevel] {
+        match self {
+            PermissionScope::IdToken => &[PermissionLevel::Write, PermissionLevel::None],
+            PermissionScope::Models => &[PermissionLevel::Read, PermissionLevel::None],
+            _ => &[
+                PermissionLevel::Read,
+                PermissionLevel::Write,
+                PermissionLevel::None,
+            ],
+        }
+    }
+}
+
+impl fmt::Display for PermissionScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The access level granted to a scope of the `GITHUB_TOKEN` permissions.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionLevel {
+    None,
+    Read,
+    Write,
+}
+
+impl fmt::Display for PermissionLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PermissionLevel::None => write!(f, "none"),
+            PermissionLevel::Read => write!(f, "read"),
+            PermissionLevel::Write => write!(f, "write"),
+        }
+    }
+}
+
+impl Validate for Permissions {
+    fn validate(&self) -> Result<(), Errors> {
+        let Permissions::Scopes(scopes) = self else {
+            return Ok(());
+        };
+        let mut properties = IndexMap::new();
+        for (scope, level) in scopes {
+            if !scope.levels().contains(level) {
+                properties.insert(
+                    scope.to_string(),
+                    Errors::NewType(vec![Error::Custom(format!(
+                        "`{}` cannot be granted to `{}`, expected one of {}",
+                        level,
+                        scope,
+                        scope
+                            .levels()
+                            .iter()
+                            .map(|l| format!("`{}`", l))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ))]),
+                );
+            }
+        }
+        if properties.is_empty() {
+            Ok(())
+        } else {
+            Err(Errors::Object(ObjectErrors::new(vec![], properties)))
+        }
+    }
+}
+
+struct PermissionsVisitor;
+
+impl<'de> Visitor<'de> for PermissionsVisitor {
+    type Value = Permissions;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("`read-all`, `write-all` or a map of scopes to access levels")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match v {
+            "read-all" => Ok(Permissions::ReadAll),
+            "write-all" => Ok(Permissions::WriteAll),
+            _ => Err(de::Error::invalid_value(de::Unexpected::Str(v), &self)),
+        }
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        Deserialize::deserialize(de::value::MapAccessDeserializer::new(map))
+            .map(Permissions::Scopes)
+    }
+}
+
+impl<'de> Deserialize<'de> for Permissions {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(PermissionsVisitor)
+    }
+}
+
+impl Serialize for Permissions {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Permissions::ReadAll => serializer.serialize_str("read-all"),
+            Permissions::WriteAll => serializer.serialize_str("write-all"),
+            Permissions::Scopes(scopes) => {
+                let mut map = serializer.serialize_map(Some(scopes.len()))?;
+                for (scope, level) in scopes {
+                    map.serialize_entry(scope, level)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::{from_str, to_string};
+
+    use super::*;
+
+    #[test]
+    fn deserialize_read_all() {
+        assert_eq!(
+            from_str::<Permissions>("read-all").unwrap(),
+            Permissions::ReadAll
+        );
+        assert_eq!(
+            from_str::<Permissions>("write-all").unwrap(),
+            Permissions::WriteAll
+        );
+    }
+
+    #[test]
+    fn deserialize_empty() {
+        assert_eq!(
+            from_str::<Permissions>("{}").unwrap(),
+            Permissions::Scopes(IndexMap::new())
+        );
+    }
+
+    #[test]
+    fn deserialize_scopes() {
+        let p: Permissions = from_str(
+            r#"
+        contents: read
+        pull-requests: write
+        id-token: write
+        "#,
+        )
+        .unwrap();
+        assert_eq!(p.level(PermissionScope::Contents), PermissionLevel::Read);
+        assert_eq!(
+            p.level(PermissionScope::PullRequests),
+            PermissionLevel::Write
+        );
+        assert_eq!(p.level(PermissionScope::Packages), PermissionLevel::None);
+    }
+
+    #[test]
+    fn deserialize_string_err() {
+        let err = from_str::<Permissions>("read").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value: string \"read\", expected `read-all`, `write-all` or a map of scopes to access levels"
+        );
+    }
+
+    #[test]
+    fn deserialize_unknown_scope_err() {
+        let err = from_str::<Permissions>("content: read").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("unknown variant `content`, expected one of `actions`"));
+    }
+
+    #[test]
+    fn deserialize_level_err() {
+        let err = from_str::<Permissions>("contents: admin").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "contents: unknown variant `admin`, expected one of `none`, `read`, `write` at line 1 column 11"
+        );
+    }
+
+    #[test]
+    fn validate_err() {
+        let p: Permissions = from_str("id-token: read").unwrap();
+        assert_eq!(
+            p.validate().unwrap_err().to_string(),
+            r#"{"errors":[],"properties":{"id-token":{"errors":["`read` cannot be granted to `id-token`, expected one of `write`, `none`"]}}}"#
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        let p: Permissions = from_str("contents: read").unwrap();
+        assert_eq!(to_string(&p).unwrap(), "contents: read\n");
+        assert_eq!(to_string(&Permissions::ReadAll).unwrap(), "read-all\n");
+        assert_eq!(
+            to_string(&Permissions::Scopes(IndexMap::new())).unwrap(),
+            "{}\n"
+        );
+    }
+}
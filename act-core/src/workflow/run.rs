@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use serde_valid::{MinPropertiesError, Validate, ValidateMinProperties};
 use serde_with::skip_serializing_none;
 
+use crate::workflow::Shell;
+
 /// A map of default settings that will apply to all jobs in the workflow
 #[skip_serializing_none]
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -16,7 +18,7 @@ pub struct Run {
     pub working_directory: Option<String>,
 
     /// The shell to use with the run key. The default shell is bash on Linux and macOS and cmd on Windows.
-    pub shell: Option<String>,
+    pub shell: Option<Shell>,
 }
 
 impl ValidateMinProperties for Run {
@@ -49,7 +51,7 @@ mod tests {
             s,
             Run {
                 working_directory: Some("/home/runner/work/my-repo/my-repo".to_string()),
-                shell: Some("bash".to_string()),
+                shell: Some(Shell::Bash),
             }
         );
     }
@@ -64,7 +66,7 @@ mod tests {
     fn serialize() {
         let s = Run {
             working_directory: Some("/home/runner/work/my-repo/my-repo".to_string()),
-            shell: Some("bash".to_string()),
+            shell: Some(Shell::Bash),
         };
         assert_eq!(
             serde_yaml::to_string(&s).unwrap(),
@@ -0,0 +1,237 @@
+use std::fmt;
+
+use serde::de::{MapAccess, SeqAccess, Visitor};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_valid::validation::{Error, Errors};
+use serde_valid::Validate;
+use serde_with::skip_serializing_none;
+
+use crate::expression::ExpressionInString;
+
+/// The runner of a job, chosen by labels or by group.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idruns-on>
+#[derive(Debug, PartialEq)]
+pub enum RunsOn {
+    /// A single label or an expression, e.g. `runs-on: ubuntu-latest`.
+    Label(ExpressionInString),
+
+    /// A runner with every label, e.g. `runs-on: [self-hosted, linux]`.
+    Labels(Vec<ExpressionInString>),
+
+    /// A runner of a group, e.g. `runs-on: { group: large-runners, labels: [linux] }`.
+    Group(RunnerGroup),
+}
+
+/// A runner group, optionally restricted to the runners with some labels.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#choosing-runners-in-a-group>
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(expecting = "a map with `group` or `labels`", deny_unknown_fields)]
+pub struct RunnerGroup {
+    pub group: Option<ExpressionInString>,
+
+    /// The labels the runner must have, a single label or a list.
+    #[serde(default, deserialize_with = "labels")]
+    pub labels: Option<Vec<ExpressionInString>>,
+}
+
+impl RunsOn {
+    /// The labels the runner must have, in order.
+    pub fn labels(&self) -> &[ExpressionInString] {
+        match self {
+            RunsOn::Label(label) => std::slice::from_ref(label),
+            RunsOn::Labels(labels) => labels,
+            RunsOn::Group(group) => group.labels.as_deref().unwrap_or_default(),
+        }
+    }
+}
+
+impl Validate for RunsOn {
+    fn validate(&self) -> Result<(), Errors> {
+        let message = match self {
+            RunsOn::Label(label) if label.as_str().is_empty() => "the label must not be empty",
+            RunsOn::Labels(labels) if labels.is_empty() => "at least one label must be given",
+            RunsOn::Group(RunnerGroup {
+                group: None,
+                labels: None,
+            }) => "at least one of `group` or `labels` must be specified",
+            _ => return Ok(()),
+        };
+        Err(Errors::NewType(vec![Error::Custom(message.to_string())]))
+    }
+}
+
+/// A single label or a list of labels.
+fn labels<'de, D>(deserializer: D) -> Result<Option<Vec<ExpressionInString>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(LabelsVisitor).map(Some)
+}
+
+struct LabelsVisitor;
+
+impl<'de> Visitor<'de> for LabelsVisitor {
+    type Value = Vec<ExpressionInString>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a label or a sequence of labels")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        ExpressionInString::deserialize(de::value::StrDeserializer::new(v)).map(|label| vec![label])
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Deserialize::deserialize(de::value::SeqAccessDeserializer::new(seq))
+    }
+}
+
+struct RunsOnVisitor;
+
+impl<'de> Visitor<'de> for RunsOnVisitor {
+    type Value = RunsOn;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a label, a sequence of labels or a map with `group` or `labels`")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        ExpressionInString::deserialize(de::value::StrDeserializer::new(v)).map(RunsOn::Label)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Deserialize::deserialize(de::value::SeqAccessDeserializer::new(seq)).map(RunsOn::Labels)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        Deserialize::deserialize(de::value::MapAccessDeserializer::new(map)).map(RunsOn::Group)
+    }
+}
+
+impl<'de> Deserialize<'de> for RunsOn {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(RunsOnVisitor)
+    }
+}
+
+impl Serialize for RunsOn {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            RunsOn::Label(label) => label.serialize(serializer),
+            RunsOn::Labels(labels) => labels.serialize(serializer),
+            RunsOn::Group(group) => group.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::{from_str, to_string};
+
+    use super::*;
+    use crate::error::ValidationError;
+
+    fn labels(runs_on: &RunsOn) -> Vec<&str> {
+        runs_on
+            .labels()
+            .iter()
+            .map(ExpressionInString::as_str)
+            .collect()
+    }
+
+    #[test]
+    fn deserialize() {
+        let r: RunsOn = from_str("ubuntu-latest").unwrap();
+        assert_eq!(labels(&r), vec!["ubuntu-latest"]);
+        let r: RunsOn = from_str("[self-hosted, linux, '${{ matrix.arch }}']").unwrap();
+        assert_eq!(
+            labels(&r),
+            vec!["self-hosted", "linux", "${{ matrix.arch }}"]
+        );
+        assert_eq!(r.labels()[2].expressions().count(), 1);
+    }
+
+    #[test]
+    fn deserialize_group() {
+        let r: RunsOn = from_str("group: large-runners\nlabels: linux\n").unwrap();
+        assert_eq!(labels(&r), vec!["linux"]);
+        let RunsOn::Group(group) = &r else {
+            panic!("not a group: {:?}", r);
+        };
+        assert_eq!(group.group.as_ref().unwrap().as_str(), "large-runners");
+        let r: RunsOn = from_str("group: large-runners").unwrap();
+        assert!(r.labels().is_empty());
+    }
+
+    #[test]
+    fn deserialize_err() {
+        assert_eq!(
+            from_str::<RunsOn>("true").unwrap_err().to_string(),
+            "invalid type: boolean `true`, expected a label, a sequence of labels or a map with `group` or `labels`"
+        );
+        assert!(from_str::<RunsOn>("group: g\nlabel: linux\n")
+            .unwrap_err()
+            .to_string()
+            .starts_with("unknown field `label`, expected `group` or `labels`"));
+        assert!(from_str::<RunsOn>("labels:\n  os: linux\n")
+            .unwrap_err()
+            .to_string()
+            .starts_with("labels: invalid type: map, expected a label or a sequence of labels"));
+    }
+
+    #[test]
+    fn serialize() {
+        let r: RunsOn = from_str("group: large-runners\nlabels: linux\n").unwrap();
+        assert_eq!(
+            to_string(&r).unwrap(),
+            "group: large-runners\nlabels:\n- linux\n"
+        );
+    }
+
+    #[test]
+    fn validate_err() {
+        for (runs_on, message) in [
+            ("''", "the label must not be empty"),
+            ("[]", "at least one label must be given"),
+            (
+                "{}",
+                "at least one of `group` or `labels` must be specified",
+            ),
+        ] {
+            let errors = from_str::<RunsOn>(runs_on).unwrap().validate().unwrap_err();
+            assert_eq!(
+                ValidationError::flatten(&errors)
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>(),
+                vec![message],
+                "{}",
+                runs_on
+            );
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt;
+
+use serde::de::Visitor;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The shells GitHub knows by name.
+const NAMES: [&str; 6] = ["bash", "pwsh", "python", "sh", "cmd", "powershell"];
+
+/// The shell a `run` script is executed with.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstepsshell>
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Shell {
+    Bash,
+    Pwsh,
+    Python,
+    Sh,
+    Cmd,
+    Powershell,
+
+    /// A command template, `{0}` is replaced by the path of the script, e.g. `perl {0}`.
+    Custom(String),
+}
+
+impl Shell {
+    pub fn new(s: &str) -> Result<Self, String> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "pwsh" => Ok(Shell::Pwsh),
+            "python" => Ok(Shell::Python),
+            "sh" => Ok(Shell::Sh),
+            "cmd" => Ok(Shell::Cmd),
+            "powershell" => Ok(Shell::Powershell),
+            _ if s.contains("{0}") => Ok(Shell::Custom(s.to_string())),
+            _ => Err(format!(
+                "unknown shell `{}`, expected one of {} or a command with `{{0}}` for the script",
+                s,
+                NAMES
+                    .iter()
+                    .map(|name| format!("`{}`", name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Pwsh => "pwsh",
+            Shell::Python => "python",
+            Shell::Sh => "sh",
+            Shell::Cmd => "cmd",
+            Shell::Powershell => "powershell",
+            Shell::Custom(command) => command,
+        }
+    }
+}
+
+impl fmt::Display for Shell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+struct ShellVisitor;
+
+impl<'de> Visitor<'de> for ShellVisitor {
+    type Value = Shell;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a shell name or a command with `{0}` for the script")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Shell::new(v).map_err(de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Shell {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(ShellVisitor)
+    }
+}
+
+impl Serialize for Shell {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::{from_str, to_string};
+
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        assert_eq!(from_str::<Shell>("pwsh").unwrap(), Shell::Pwsh);
+        assert_eq!(
+            from_str::<Shell>("perl {0}").unwrap(),
+            Shell::Custom("perl {0}".to_string())
+        );
+    }
+
+    #[test]
+    fn deserialize_err() {
+        assert_eq!(
+            from_str::<Shell>("zsh").unwrap_err().to_string(),
+            "unknown shell `zsh`, expected one of `bash`, `pwsh`, `python`, `sh`, `cmd`, `powershell` or a command with `{0}` for the script"
+        );
+        assert_eq!(
+            from_str::<Shell>("[bash]").unwrap_err().to_string(),
+            "invalid type: sequence, expected a shell name or a command with `{0}` for the script"
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!(to_string(&Shell::Bash).unwrap(), "bash\n");
+        assert_eq!(
+            to_string(&Shell::Custom("bash -e {0}".to_string())).unwrap(),
+            "bash -e {0}\n"
+        );
+    }
+}
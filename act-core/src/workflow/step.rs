@@ -0,0 +1,312 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+use serde_with::skip_serializing_none;
+
+use crate::expression::{Condition, ExpressionInString};
+use crate::workflow::job::{ContinueOnError, TimeoutMinutes};
+use crate::workflow::{ActionRef, Defaults, Env, EnvValue, Run, Shell};
+
+/// A step runs a command or an action, a job runs its steps in order.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idsteps>
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+#[rule(uses_or_run(uses, run))]
+#[rule(with_uses(with, uses))]
+#[rule(shell_run(shell, run))]
+#[rule(working_directory_run(working_directory, run))]
+pub struct Step {
+    /// Prevents a job from failing when the step fails.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstepscontinue-on-error>
+    #[serde(rename = "continue-on-error")]
+    pub continue_on_error: Option<ContinueOnError>,
+
+    /// Environment variables for the step, they override those of the job and the workflow.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstepsenv>
+    #[serde(rename = "env", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub env: Option<Env>,
+
+    /// A unique identifier for the step, used to reference it in expressions.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstepsid>
+    #[serde(rename = "id", skip_serializing_if = "Option::is_none")]
+    #[validate(
+        pattern = r"^[_a-zA-Z][a-zA-Z0-9_-]*$",
+        message = "a step id must start with a letter or `_` and contain only alphanumeric characters, `-` or `_`"
+    )]
+    pub id: Option<String>,
+
+    /// A conditional to prevent a step from running unless a condition is met.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstepsif>
+    #[serde(rename = "if")]
+    pub r#if: Option<Condition>,
+
+    /// The name of the step displayed on GitHub.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstepsname>
+    pub name: Option<ExpressionInString>,
+
+    /// Command-line programs to run using the shell of the step.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstepsrun>
+    pub run: Option<ExpressionInString>,
+
+    /// The shell to run the `run` script with, it overrides the defaults of the job and the workflow.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstepsshell>
+    pub shell: Option<Shell>,
+
+    /// The maximum number of minutes to run the step before killing the process.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstepstimeout-minutes>
+    #[serde(rename = "timeout-minutes")]
+    pub timeout_minutes: Option<TimeoutMinutes>,
+
+    /// The action to run.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstepsuses>
+    pub uses: Option<ActionRef>,
+
+    /// Inputs passed to the action, strings can contain expressions.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstepswith>
+    pub with: Option<IndexMap<String, EnvValue>>,
+
+    /// The directory to run the `run` script in, it overrides the defaults of the job and the workflow.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstepsrun>
+    #[serde(rename = "working-directory")]
+    pub working_directory: Option<ExpressionInString>,
+}
+
+impl Step {
+    /// The shell and working directory of the `run` script, each setting the step lacks is
+    /// taken from the first defaults that have it, e.g. those of the job then of the workflow.
+    pub fn run_settings<'a>(&self, defaults: impl IntoIterator<Item = &'a Defaults>) -> Run {
+        let mut run = Run {
+            working_directory: self
+                .working_directory
+                .as_ref()
+                .map(|dir| dir.as_str().to_string()),
+            shell: self.shell.clone(),
+        };
+        for defaults in defaults {
+            if run.working_directory.is_none() {
+                run.working_directory = defaults.run.working_directory.clone();
+            }
+            if run.shell.is_none() {
+                run.shell = defaults.run.shell.clone();
+            }
+        }
+        run
+    }
+}
+
+fn uses_or_run(
+    uses: &Option<ActionRef>,
+    run: &Option<ExpressionInString>,
+) -> Result<(), serde_valid::validation::Error> {
+    match (uses, run) {
+        (Some(_), Some(_)) => Err(serde_valid::validation::Error::Custom(
+            "`uses` and `run` cannot be used together in a step".to_string(),
+        )),
+        (None, None) => Err(serde_valid::validation::Error::Custom(
+            "a step must have either `uses` or `run`".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn with_uses(
+    with: &Option<IndexMap<String, EnvValue>>,
+    uses: &Option<ActionRef>,
+) -> Result<(), serde_valid::validation::Error> {
+    if with.is_some() && uses.is_none() {
+        Err(serde_valid::validation::Error::Custom(
+            "`with` can only be used with `uses`".to_string(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Fails when a setting of the `run` script is used in a step without one.
+fn requires_run<T>(
+    setting: &Option<T>,
+    run: &Option<ExpressionInString>,
+    name: &str,
+) -> Result<(), serde_valid::validation::Error> {
+    if setting.is_some() && run.is_none() {
+        Err(serde_valid::validation::Error::Custom(format!(
+            "`{}` can only be used with `run`",
+            name
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+fn shell_run(
+    shell: &Option<Shell>,
+    run: &Option<ExpressionInString>,
+) -> Result<(), serde_valid::validation::Error> {
+    requires_run(shell, run, "shell")
+}
+
+fn working_directory_run(
+    working_directory: &Option<ExpressionInString>,
+    run: &Option<ExpressionInString>,
+) -> Result<(), serde_valid::validation::Error> {
+    requires_run(working_directory, run, "working-directory")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_valid::Validate;
+    use serde_yaml::{from_str, to_string};
+
+    use super::*;
+    use crate::error::ValidationError;
+
+    fn messages(step: &str) -> Vec<String> {
+        match from_str::<Step>(step).unwrap().validate() {
+            Ok(()) => vec![],
+            Err(errors) => ValidationError::flatten(&errors)
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn deserialize() {
+        let s: Step = from_str(
+            r#"
+        id: build
+        name: Build ${{ matrix.os }}
+        run: make
+        shell: bash -e {0}
+        working-directory: src
+        continue-on-error: true
+        timeout-minutes: 5
+        env:
+          CC: clang
+        "#,
+        )
+        .unwrap();
+        assert_eq!(s.id, Some("build".to_string()));
+        assert_eq!(s.run.unwrap().as_str(), "make");
+        assert_eq!(s.shell, Some(Shell::Custom("bash -e {0}".to_string())));
+        assert_eq!(s.working_directory.unwrap().as_str(), "src");
+        assert_eq!(s.continue_on_error, Some(ContinueOnError::Bool(true)));
+        assert_eq!(s.timeout_minutes, Some(TimeoutMinutes::Number(5)));
+    }
+
+    #[test]
+    fn deserialize_with() {
+        let s: Step = from_str(
+            r#"
+        uses: actions/setup-node@v4
+        with:
+          node-version: 20
+          cache: npm
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            s.uses,
+            Some(ActionRef::new("actions/setup-node@v4").unwrap())
+        );
+        let with = s.with.unwrap();
+        assert_eq!(with["node-version"], EnvValue::Number(20.0));
+        assert_eq!(with["cache"].to_string(), "npm");
+    }
+
+    #[test]
+    fn deserialize_expression_err() {
+        for step in [
+            "if: ${{ a == }}\nrun: make\n",
+            "uses: actions/checkout@v4\nwith:\n  ref: ${{ github.ref == }}\n",
+            "run: make\nworking-directory: ${{ 1 + }}\n",
+        ] {
+            assert!(from_str::<Step>(step).is_err(), "{}", step);
+        }
+        let s: Step = from_str("if: github.event_name == 'push'\nrun: make\n").unwrap();
+        assert_eq!(s.r#if.unwrap().as_str(), "github.event_name == 'push'");
+    }
+
+    #[test]
+    fn deserialize_shell_err() {
+        let err = from_str::<Step>("run: make\nshell: zsh").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("shell: unknown shell `zsh`, expected one of `bash`"));
+    }
+
+    #[test]
+    fn serialize() {
+        let s: Step = from_str("run: make\nshell: pwsh\n").unwrap();
+        assert_eq!(to_string(&s).unwrap(), "run: make\nshell: pwsh\n");
+    }
+
+    #[test]
+    fn validate() {
+        assert!(messages("uses: actions/checkout@v4\nwith:\n  fetch-depth: 0\n").is_empty());
+        assert!(messages("run: make\nworking-directory: src\nshell: sh\n").is_empty());
+    }
+
+    #[test]
+    fn validate_uses_or_run_err() {
+        assert_eq!(
+            messages("uses: actions/checkout@v4\nrun: make\n"),
+            vec!["`uses` and `run` cannot be used together in a step"]
+        );
+        assert_eq!(
+            messages("name: Build\n"),
+            vec!["a step must have either `uses` or `run`"]
+        );
+    }
+
+    #[test]
+    fn validate_run_settings_err() {
+        assert_eq!(
+            messages("uses: actions/checkout@v4\nshell: bash\nworking-directory: src\n"),
+            vec![
+                "`shell` can only be used with `run`",
+                "`working-directory` can only be used with `run`"
+            ]
+        );
+        assert_eq!(
+            messages("run: make\nwith:\n  a: 1\n"),
+            vec!["`with` can only be used with `uses`"]
+        );
+    }
+
+    #[test]
+    fn validate_id_err() {
+        assert_eq!(
+            messages("id: 1st\nrun: make\n"),
+            vec!["id: a step id must start with a letter or `_` and contain only alphanumeric characters, `-` or `_`"]
+        );
+    }
+
+    #[test]
+    fn run_settings() {
+        let step: Step = from_str("run: make\nworking-directory: src\n").unwrap();
+        let job: Defaults = from_str("run:\n  working-directory: app\n").unwrap();
+        let workflow: Defaults = from_str("run:\n  shell: pwsh\n").unwrap();
+        assert_eq!(
+            step.run_settings([&job, &workflow]),
+            Run {
+                working_directory: Some("src".to_string()),
+                shell: Some(Shell::Pwsh),
+            }
+        );
+    }
+}
@@ -0,0 +1,762 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use indexmap::IndexMap;
+use serde::de::{MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_valid::validation::{Error, Errors};
+use serde_valid::Validate;
+use serde_with::skip_serializing_none;
+
+use crate::Expression;
+
+/// The most jobs GitHub creates for a matrix.
+pub const MAX_COMBINATIONS: usize = 256;
+
+/// The most combinations of the axes [Matrix::expand] lists, larger matrices are only
+/// counted.
+const MAX_EXPANDED: usize = 65536;
+
+/// A matrix strategy to run a job once for each combination of some values.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstrategy>
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct Strategy {
+    /// Cancel the other jobs of the matrix when one of them fails, true by default.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstrategyfail-fast>
+    #[serde(rename = "fail-fast")]
+    pub fail_fast: Option<FailFast>,
+
+    /// The most jobs of the matrix that run at the same time.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstrategymax-parallel>
+    #[serde(rename = "max-parallel")]
+    pub max_parallel: Option<MaxParallel>,
+
+    /// The values to combine.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobsjob_idstrategymatrix>
+    #[serde(rename = "matrix", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub matrix: Option<Matrix>,
+}
+
+/// A boolean or an expression that evaluates to a boolean.
+#[derive(Debug, Eq, PartialEq)]
+pub enum FailFast {
+    Bool(bool),
+    Expression(Expression),
+}
+
+/// A number of jobs or an expression that evaluates to a number.
+#[derive(Debug, Eq, PartialEq)]
+pub enum MaxParallel {
+    Number(u64),
+    Expression(Expression),
+}
+
+/// The matrix of a strategy.
+#[derive(Debug, PartialEq)]
+pub enum Matrix {
+    /// An expression that evaluates to the whole matrix, e.g. `${{ fromJSON(needs.setup.outputs.matrix) }}`.
+    Expression(Expression),
+
+    /// Axes with their values, and combinations to add or remove.
+    Map(MatrixMap),
+}
+
+/// The axes of a matrix with the `include` and `exclude` combinations.
+#[derive(Debug, Default, PartialEq)]
+pub struct MatrixMap {
+    /// The values of each axis, in order.
+    pub axes: IndexMap<String, Axis>,
+
+    /// Combinations to extend or to add to those of the axes.
+    pub include: Option<Combinations>,
+
+    /// Combinations to remove from those of the axes, an entry removes every combination
+    /// it is a part of.
+    pub exclude: Option<Combinations>,
+}
+
+/// The values of an axis of a matrix.
+#[derive(Debug, PartialEq)]
+pub enum Axis {
+    Values(Vec<serde_yaml::Value>),
+    Expression(Expression),
+}
+
+/// The `include` or `exclude` entries of a matrix.
+#[derive(Debug, PartialEq)]
+pub enum Combinations {
+    List(Vec<Combination>),
+    Expression(Expression),
+}
+
+/// A value for some axes of a matrix.
+pub type Combination = IndexMap<String, serde_yaml::Value>;
+
+/// The combinations of a matrix, with the problems found computing them.
+#[derive(Debug, Default, PartialEq)]
+pub struct Expansion {
+    /// The combinations the jobs run with, in the order GitHub creates them.
+    pub combinations: Vec<Combination>,
+
+    pub problems: Vec<MatrixProblem>,
+}
+
+/// A problem of a matrix that GitHub does not report, or reports when the workflow runs.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MatrixProblem {
+    /// The matrix has more than [MAX_COMBINATIONS] combinations.
+    TooManyCombinations(usize),
+
+    /// The `exclude` entry at this index matches no combination.
+    UnmatchedExclude(usize),
+
+    /// The `include` entry at `index` overwrites the value of `key` added by the `include`
+    /// entry at `overwritten`.
+    IncludeOverwrite {
+        index: usize,
+        key: String,
+        overwritten: usize,
+    },
+}
+
+impl Matrix {
+    /// List the combinations of a matrix following the rules of GitHub: the combinations of
+    /// the axes less those matching an `exclude` entry, then the `include` entries are
+    /// added to every combination whose axes they don't change, or as new combinations.
+    ///
+    /// There is none when the matrix depends on an expression. The `include` and `exclude`
+    /// entries with an expression are compared as written, their problems are not reported
+    /// since their values are only known when the workflow runs.
+    ///
+    /// <https://docs.github.com/en/actions/using-jobs/using-a-matrix-for-your-jobs#expanding-or-adding-matrix-configurations>
+    pub fn expand(&self) -> Option<Expansion> {
+        let Matrix::Map(map) = self else {
+            return None;
+        };
+        let mut axes = vec![];
+        for (name, axis) in &map.axes {
+            match axis {
+                Axis::Values(values) => axes.push((name, values)),
+                Axis::Expression(_) => return None,
+            }
+        }
+        let include = match &map.include {
+            Some(Combinations::List(include)) => &include[..],
+            Some(Combinations::Expression(_)) => return None,
+            None => &[],
+        };
+        let exclude = match &map.exclude {
+            Some(Combinations::List(exclude)) => &exclude[..],
+            Some(Combinations::Expression(_)) => return None,
+            None => &[],
+        };
+
+        let mut expansion = Expansion::default();
+        let count = axes
+            .iter()
+            .try_fold(1usize, |count, (_, values)| count.checked_mul(values.len()))
+            .unwrap_or(usize::MAX);
+        if count > MAX_EXPANDED {
+            expansion
+                .problems
+                .push(MatrixProblem::TooManyCombinations(count));
+            return Some(expansion);
+        }
+
+        let mut combinations = vec![];
+        if !axes.is_empty() {
+            combinations.push(Combination::new());
+            for (name, values) in &axes {
+                combinations = combinations
+                    .into_iter()
+                    .flat_map(|combination| {
+                        values.iter().map(move |value| {
+                            let mut combination = combination.clone();
+                            combination.insert(name.to_string(), value.clone());
+                            combination
+                        })
+                    })
+                    .collect();
+            }
+        }
+
+        for (index, entry) in exclude.iter().enumerate() {
+            let before = combinations.len();
+            combinations.retain(|combination| {
+                !entry
+                    .iter()
+                    .all(|(key, value)| combination.get(key) == Some(value))
+            });
+            if combinations.len() == before && !has_expression(entry) {
+                expansion
+                    .problems
+                    .push(MatrixProblem::UnmatchedExclude(index));
+            }
+        }
+
+        // the index of the `include` entry that added each value that is not an axis
+        let mut added: Vec<HashMap<String, usize>> = vec![HashMap::new(); combinations.len()];
+        let original = combinations.len();
+        let mut extra = vec![];
+        for (index, entry) in include.iter().enumerate() {
+            let mut matched = false;
+            for (combination, added) in combinations[..original].iter_mut().zip(&mut added) {
+                let keeps_axes = entry.iter().all(|(key, value)| {
+                    !map.axes.contains_key(key) || combination.get(key) == Some(value)
+                });
+                if !keeps_axes {
+                    continue;
+                }
+                matched = true;
+                for (key, value) in entry {
+                    if map.axes.contains_key(key) {
+                        continue;
+                    }
+                    if let Some(&overwritten) = added.get(key) {
+                        let problem = MatrixProblem::IncludeOverwrite {
+                            index,
+                            key: key.clone(),
+                            overwritten,
+                        };
+                        if combination.get(key) != Some(value)
+                            && !has_expression(entry)
+                            && !has_expression(&include[overwritten])
+                            && !expansion.problems.contains(&problem)
+                        {
+                            expansion.problems.push(problem);
+                        }
+                    }
+                    combination.insert(key.clone(), value.clone());
+                    added.insert(key.clone(), index);
+                }
+            }
+            if !matched {
+                extra.push(entry.clone());
+            }
+        }
+        combinations.extend(extra);
+
+        if combinations.len() > MAX_COMBINATIONS {
+            expansion
+                .problems
+                .push(MatrixProblem::TooManyCombinations(combinations.len()));
+        }
+        expansion.combinations = combinations;
+        Some(expansion)
+    }
+}
+
+/// Returns true for an `include` or `exclude` entry with a `${{ }}` expression in a value.
+fn has_expression(entry: &Combination) -> bool {
+    fn is_expression(value: &serde_yaml::Value) -> bool {
+        match value {
+            serde_yaml::Value::String(s) => s.contains("${{"),
+            serde_yaml::Value::Sequence(values) => values.iter().any(is_expression),
+            serde_yaml::Value::Mapping(map) => map.values().any(is_expression),
+            _ => false,
+        }
+    }
+    entry.values().any(is_expression)
+}
+
+impl Validate for Matrix {
+    fn validate(&self) -> Result<(), Errors> {
+        let Some(expansion) = self.expand() else {
+            return Ok(());
+        };
+        match expansion.problems.iter().find_map(|problem| match problem {
+            MatrixProblem::TooManyCombinations(count) => Some(count),
+            _ => None,
+        }) {
+            Some(count) => Err(Errors::NewType(vec![Error::Custom(format!(
+                "the matrix has {} combinations, GitHub runs at most {} jobs for a matrix",
+                count, MAX_COMBINATIONS
+            ))])),
+            None => Ok(()),
+        }
+    }
+}
+
+struct FailFastVisitor;
+
+impl<'de> Visitor<'de> for FailFastVisitor {
+    type Value = FailFast;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a boolean or an expression")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(FailFast::Bool(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Expression::deserialize(de::value::StrDeserializer::new(v)).map(FailFast::Expression)
+    }
+}
+
+impl<'de> Deserialize<'de> for FailFast {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(FailFastVisitor)
+    }
+}
+
+impl Serialize for FailFast {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            FailFast::Bool(b) => serializer.serialize_bool(*b),
+            FailFast::Expression(e) => e.serialize(serializer),
+        }
+    }
+}
+
+struct MaxParallelVisitor;
+
+impl<'de> Visitor<'de> for MaxParallelVisitor {
+    type Value = MaxParallel;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a number greater than 0 or an expression")
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        if v == 0 {
+            Err(de::Error::invalid_value(de::Unexpected::Unsigned(v), &self))
+        } else {
+            Ok(MaxParallel::Number(v))
+        }
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match u64::try_from(v) {
+            Ok(v) => self.visit_u64(v),
+            Err(_) => Err(de::Error::invalid_value(de::Unexpected::Signed(v), &self)),
+        }
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Expression::deserialize(de::value::StrDeserializer::new(v)).map(MaxParallel::Expression)
+    }
+}
+
+impl<'de> Deserialize<'de> for MaxParallel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(MaxParallelVisitor)
+    }
+}
+
+impl Serialize for MaxParallel {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            MaxParallel::Number(n) => serializer.serialize_u64(*n),
+            MaxParallel::Expression(e) => e.serialize(serializer),
+        }
+    }
+}
+
+struct MatrixVisitor;
+
+impl<'de> Visitor<'de> for MatrixVisitor {
+    type Value = Matrix;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of axes to their values or an expression")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Expression::deserialize(de::value::StrDeserializer::new(v)).map(Matrix::Expression)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut matrix = MatrixMap::default();
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "include" => matrix.include = Some(map.next_value()?),
+                "exclude" => matrix.exclude = Some(map.next_value()?),
+                _ => {
+                    let axis = map.next_value()?;
+                    matrix.axes.insert(key, axis);
+                }
+            }
+        }
+        Ok(Matrix::Map(matrix))
+    }
+}
+
+impl<'de> Deserialize<'de> for Matrix {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(MatrixVisitor)
+    }
+}
+
+impl Serialize for Matrix {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Matrix::Expression(e) => e.serialize(serializer),
+            Matrix::Map(matrix) => {
+                let mut map = serializer.serialize_map(None)?;
+                for (name, axis) in &matrix.axes {
+                    map.serialize_entry(name, axis)?;
+                }
+                if let Some(include) = &matrix.include {
+                    map.serialize_entry("include", include)?;
+                }
+                if let Some(exclude) = &matrix.exclude {
+                    map.serialize_entry("exclude", exclude)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+struct AxisVisitor;
+
+impl<'de> Visitor<'de> for AxisVisitor {
+    type Value = Axis;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of values or an expression")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Expression::deserialize(de::value::StrDeserializer::new(v)).map(Axis::Expression)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Deserialize::deserialize(de::value::SeqAccessDeserializer::new(seq)).map(Axis::Values)
+    }
+}
+
+impl<'de> Deserialize<'de> for Axis {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(AxisVisitor)
+    }
+}
+
+impl Serialize for Axis {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Axis::Values(values) => values.serialize(serializer),
+            Axis::Expression(e) => e.serialize(serializer),
+        }
+    }
+}
+
+struct CombinationsVisitor;
+
+impl<'de> Visitor<'de> for CombinationsVisitor {
+    type Value = Combinations;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of maps or an expression")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Expression::deserialize(de::value::StrDeserializer::new(v)).map(Combinations::Expression)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Deserialize::deserialize(de::value::SeqAccessDeserializer::new(seq)).map(Combinations::List)
+    }
+}
+
+impl<'de> Deserialize<'de> for Combinations {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(CombinationsVisitor)
+    }
+}
+
+impl Serialize for Combinations {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Combinations::List(list) => list.serialize(serializer),
+            Combinations::Expression(e) => e.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::{from_str, to_string};
+
+    use super::*;
+    use crate::error::ValidationError;
+
+    fn expand(matrix: &str) -> Expansion {
+        from_str::<Matrix>(matrix).unwrap().expand().unwrap()
+    }
+
+    /// The combinations as flow mappings, e.g. `{os: linux, node: 20}`.
+    fn combinations(expansion: &Expansion) -> Vec<String> {
+        expansion
+            .combinations
+            .iter()
+            .map(|combination| {
+                let values = combination
+                    .iter()
+                    .map(|(key, value)| {
+                        format!("{}: {}", key, to_string(value).unwrap().trim_end())
+                    })
+                    .collect::<Vec<_>>();
+                format!("{{{}}}", values.join(", "))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn deserialize() {
+        let s: Strategy = from_str(
+            r#"
+        fail-fast: false
+        max-parallel: 2
+        matrix:
+          os: [ubuntu-latest, windows-latest]
+          node: ${{ fromJSON(inputs.versions) }}
+          include:
+            - os: ubuntu-latest
+              experimental: true
+        "#,
+        )
+        .unwrap();
+        assert_eq!(s.fail_fast, Some(FailFast::Bool(false)));
+        assert_eq!(s.max_parallel, Some(MaxParallel::Number(2)));
+        let Some(Matrix::Map(matrix)) = &s.matrix else {
+            panic!("not a map: {:?}", s.matrix);
+        };
+        assert_eq!(matrix.axes.len(), 2);
+        assert!(matches!(matrix.axes["node"], Axis::Expression(_)));
+        assert!(matches!(&matrix.include, Some(Combinations::List(list)) if list.len() == 1));
+        assert!(s.matrix.unwrap().expand().is_none());
+    }
+
+    #[test]
+    fn deserialize_expression() {
+        let m: Matrix = from_str("${{ fromJSON(needs.setup.outputs.matrix) }}").unwrap();
+        assert!(matches!(m, Matrix::Expression(_)));
+        assert!(m.expand().is_none());
+    }
+
+    #[test]
+    fn deserialize_err() {
+        assert_eq!(
+            from_str::<Strategy>("max-parallel: 0")
+                .unwrap_err()
+                .to_string(),
+            "max-parallel: invalid value: integer `0`, expected a number greater than 0 or an expression at line 1 column 15"
+        );
+        assert_eq!(
+            from_str::<Matrix>("os: ubuntu-latest").unwrap_err().to_string(),
+            "os: invalid value: string \"ubuntu-latest\", expected a string matching ^\\$\\{\\{(.|[\\r\\n])*\\}\\}$ at line 1 column 5"
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        let source = "fail-fast: false\nmatrix:\n  os:\n  - linux\n  include:\n  - os: mac\n";
+        let s: Strategy = from_str(source).unwrap();
+        assert_eq!(to_string(&s).unwrap(), source);
+    }
+
+    #[test]
+    fn expand_axes() {
+        let e = expand("os: [linux, windows]\nnode: [18, 20]\n");
+        assert_eq!(
+            combinations(&e),
+            vec![
+                "{os: linux, node: 18}",
+                "{os: linux, node: 20}",
+                "{os: windows, node: 18}",
+                "{os: windows, node: 20}",
+            ]
+        );
+        assert!(e.problems.is_empty());
+    }
+
+    #[test]
+    fn expand_include() {
+        // the example of the GitHub documentation
+        let e = expand(
+            r#"
+        fruit: [apple, pear]
+        animal: [cat, dog]
+        include:
+          - color: green
+          - color: pink
+            animal: cat
+          - fruit: apple
+            shape: circle
+          - fruit: banana
+          - fruit: banana
+            animal: cat
+        "#,
+        );
+        assert_eq!(
+            combinations(&e),
+            vec![
+                "{fruit: apple, animal: cat, color: pink, shape: circle}",
+                "{fruit: apple, animal: dog, color: green, shape: circle}",
+                "{fruit: pear, animal: cat, color: pink}",
+                "{fruit: pear, animal: dog, color: green}",
+                "{fruit: banana}",
+                "{fruit: banana, animal: cat}",
+            ]
+        );
+        assert_eq!(
+            e.problems,
+            vec![MatrixProblem::IncludeOverwrite {
+                index: 1,
+                key: "color".to_string(),
+                overwritten: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn expand_include_only() {
+        let e = expand("include:\n  - os: linux\n  - os: mac\n");
+        assert_eq!(combinations(&e), vec!["{os: linux}", "{os: mac}"]);
+    }
+
+    #[test]
+    fn expand_exclude() {
+        let e = expand(
+            r#"
+        os: [linux, windows]
+        node: [18, 20]
+        exclude:
+          - os: windows
+            node: 18
+          - os: mac
+        "#,
+        );
+        assert_eq!(
+            combinations(&e),
+            vec![
+                "{os: linux, node: 18}",
+                "{os: linux, node: 20}",
+                "{os: windows, node: 20}",
+            ]
+        );
+        assert_eq!(e.problems, vec![MatrixProblem::UnmatchedExclude(1)]);
+    }
+
+    #[test]
+    fn expand_expression_entries() {
+        let e = expand(
+            r#"
+        os: [linux, windows]
+        include:
+          - os: linux
+            experimental: ${{ vars.EXPERIMENTAL }}
+          - os: linux
+            experimental: true
+          - os: windows
+            experimental: false
+          - os: windows
+            experimental: ${{ vars.EXPERIMENTAL }}
+        exclude:
+          - os: ${{ vars.SKIP_OS }}
+        "#,
+        );
+        assert_eq!(combinations(&e).len(), 2);
+        assert_eq!(e.problems, vec![]);
+    }
+
+    #[test]
+    fn expand_too_many() {
+        let e = expand("a: [1, 2, 3, 4]\nb: [1, 2, 3, 4]\nc: [1, 2, 3, 4]\nd: [1, 2, 3, 4, 5]\n");
+        assert_eq!(e.combinations.len(), 320);
+        assert_eq!(e.problems, vec![MatrixProblem::TooManyCombinations(320)]);
+        let values = format!("[{}]", vec!["1"; 100].join(", "));
+        let e = expand(&format!("a: {0}\nb: {0}\nc: {0}\n", values));
+        assert!(e.combinations.is_empty());
+        assert_eq!(
+            e.problems,
+            vec![MatrixProblem::TooManyCombinations(1_000_000)]
+        );
+    }
+
+    #[test]
+    fn validate_err() {
+        let m: Matrix =
+            from_str("a: [1, 2, 3, 4]\nb: [1, 2, 3, 4]\nc: [1, 2, 3, 4]\nd: [1, 2, 3, 4, 5]\n")
+                .unwrap();
+        let errors = ValidationError::flatten(&m.validate().unwrap_err());
+        assert_eq!(
+            errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["the matrix has 320 combinations, GitHub runs at most 256 jobs for a matrix"]
+        );
+    }
+}
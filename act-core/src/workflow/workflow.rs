@@ -1,11 +1,20 @@
+use std::collections::HashSet;
+
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
+use serde_valid::validation::{ArrayErrors, Errors, ObjectErrors};
+use serde_valid::Validate;
 use serde_with::skip_serializing_none;
 
-use crate::workflow::{Concurrency, Defaults, Env};
-use crate::Expression;
+use crate::error::ValidationError;
+use crate::expression::ExpressionInString;
+use crate::workflow::job::Needs;
+use crate::workflow::{Concurrency, Defaults, Env, Job, JobId, On, Permissions};
+use crate::{Document, Error};
 
 #[skip_serializing_none]
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Validate)]
+#[rule(valid_needs(jobs))]
 pub struct Workflow {
     /// Concurrency ensures that only a single job or workflow using the same concurrency group will run at a time.
     /// A concurrency group can be any string or expression. The expression can use any context except for the secrets context.
@@ -15,29 +24,238 @@ pub struct Workflow {
     /// To also cancel any currently running job or workflow in the same concurrency group, specify cancel-in-progress: true.
     ///
     /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#concurrency>
+    // serde_valid takes the first string in a `serde` attribute as the field name,
+    // so validated fields spell out their name before `skip_serializing_if`.
+    #[serde(rename = "concurrency", skip_serializing_if = "Option::is_none")]
+    #[validate]
     pub concurrency: Option<Concurrency>,
 
     /// "A map of default settings that will apply to all jobs in the workflow
     ///
     /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#defaults>
+    #[serde(rename = "defaults", skip_serializing_if = "Option::is_none")]
+    #[validate]
     pub defaults: Option<Defaults>,
 
     /// A map of environment variables that are available to all jobs and steps in the workflow
     ///
     /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#env>
+    #[serde(rename = "env", skip_serializing_if = "Option::is_none")]
+    #[validate]
     pub env: Option<Env>,
-    // jobs
-    pub name: String,
-    // on
-    // permissions
-    pub run_name: Option<Expression>,
+
+    /// A workflow run is made up of one or more jobs. Jobs run in parallel by default.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#jobs>
+    #[serde(rename = "jobs", default, skip_serializing_if = "IndexMap::is_empty")]
+    #[validate]
+    pub jobs: IndexMap<JobId, Job>,
+
+    /// The name of the workflow. GitHub displays the names of your workflows under your repository's "Actions" tab.
+    /// If you omit name, GitHub displays the workflow file path relative to the root of the repository.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#name>
+    pub name: Option<String>,
+
+    /// The events that trigger the workflow.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#on>
+    #[serde(rename = "on", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub on: Option<On>,
+
+    /// Modify the default permissions granted to the `GITHUB_TOKEN` for all jobs in the workflow.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#permissions>
+    #[serde(rename = "permissions", skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub permissions: Option<Permissions>,
+
+    /// The name for workflow runs generated from the workflow, it can use expressions
+    /// referencing the `github` and `inputs` contexts.
+    ///
+    /// <https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#run-name>
+    #[serde(rename = "run-name")]
+    pub run_name: Option<ExpressionInString>,
+}
+
+impl Workflow {
+    /// Deserialize and validate a workflow from a YAML document.
+    ///
+    /// Every node that fails to deserialize and every broken rule is reported, each error
+    /// reports its location in the document.
+    pub fn load(document: &Document) -> Result<Workflow, Vec<Error>> {
+        match Self::load_all(document) {
+            (Some(workflow), errors) if errors.is_empty() => Ok(workflow),
+            (_, errors) => Err(errors),
+        }
+    }
+
+    /// Deserialize and validate a workflow from a YAML document, reporting every error
+    /// instead of failing at the first one.
+    ///
+    /// The workflow lacks the nodes that failed to deserialize, there is none when the
+    /// document root is not a workflow.
+    pub fn load_all(document: &Document) -> (Option<Workflow>, Vec<Error>) {
+        let (workflow, mut errors, skipped) = document.deserialize_skipping::<Workflow>();
+        // the rules of an object that lost a field see it as missing, e.g. a step whose
+        // `uses` failed has neither `uses` nor `run`, they are checked once the field is fixed
+        let incomplete = skipped
+            .iter()
+            .map(|path| parent(path))
+            .collect::<HashSet<_>>();
+        if let Some(Err(validation)) = workflow.as_ref().map(Validate::validate) {
+            errors.extend(
+                ValidationError::flatten(&validation)
+                    .into_iter()
+                    .filter(|err| !incomplete.contains(err.path()))
+                    .map(|err| {
+                        let span = document.path_span(err.path());
+                        let location = document.location(span.start);
+                        Error::Validation(err.with_location(location).with_span(span))
+                    }),
+            );
+        }
+        (workflow, errors)
+    }
+}
+
+/// Fails when a job needs a job that is not in the workflow, or when jobs need each other.
+fn valid_needs(jobs: &IndexMap<JobId, Job>) -> Result<(), serde_valid::validation::Error> {
+    // the problems of each `needs` entry: the job, the index in the list if it is one
+    let mut problems = IndexMap::<&JobId, IndexMap<Option<usize>, Vec<String>>>::new();
+    let mut push = |id, index, message| {
+        problems
+            .entry(id)
+            .or_default()
+            .entry(index)
+            .or_default()
+            .push(message)
+    };
+    for (id, job) in jobs {
+        let Some(needs) = &job.needs else {
+            continue;
+        };
+        for (i, needed) in needs.job_ids().iter().enumerate() {
+            if !jobs.contains_key(needed) {
+                push(
+                    id,
+                    needs_index(needs, i),
+                    format!("the job `{}` is not defined in the workflow", needed),
+                );
+            }
+        }
+    }
+    for (id, index, cycle) in cycles(jobs) {
+        let path = cycle
+            .iter()
+            .map(|id| format!("`{}`", id))
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        push(id, index, format!("the jobs need each other: {}", path));
+    }
+    if problems.is_empty() {
+        return Ok(());
+    }
+    let jobs = problems
+        .into_iter()
+        .map(|(id, entries)| {
+            let errors = |messages: Vec<String>| {
+                Errors::NewType(
+                    messages
+                        .into_iter()
+                        .map(serde_valid::validation::Error::Custom)
+                        .collect(),
+                )
+            };
+            let needs = match entries.get(&None) {
+                Some(_) => errors(entries.into_values().flatten().collect()),
+                None => Errors::Array(ArrayErrors::new(
+                    vec![],
+                    entries
+                        .into_iter()
+                        .map(|(i, messages)| (i.unwrap_or_default(), errors(messages)))
+                        .collect(),
+                )),
+            };
+            let properties = IndexMap::from([("needs".to_string(), needs)]);
+            (
+                id.to_string(),
+                Errors::Object(ObjectErrors::new(vec![], properties)),
+            )
+        })
+        .collect();
+    let properties = IndexMap::from([(
+        "jobs".to_string(),
+        Errors::Object(ObjectErrors::new(vec![], jobs)),
+    )]);
+    Err(serde_valid::validation::Error::Properties(
+        ObjectErrors::new(vec![], properties),
+    ))
+}
+
+/// The index of a needed job in `needs`, none when `needs` is a single job id.
+fn needs_index(needs: &Needs, i: usize) -> Option<usize> {
+    match needs {
+        Needs::JobId(_) => None,
+        Needs::JobIds(_) => Some(i),
+    }
+}
+
+/// The dependency cycles of the jobs, each with the job and `needs` entry that closes it.
+///
+/// A depth-first search reports a cycle when it reaches a job that is still on its path.
+fn cycles(jobs: &IndexMap<JobId, Job>) -> Vec<(&JobId, Option<usize>, Vec<&JobId>)> {
+    fn visit<'a>(
+        jobs: &'a IndexMap<JobId, Job>,
+        id: &'a JobId,
+        path: &mut Vec<&'a JobId>,
+        done: &mut HashSet<&'a JobId>,
+        cycles: &mut Vec<(&'a JobId, Option<usize>, Vec<&'a JobId>)>,
+    ) {
+        path.push(id);
+        if let Some(needs) = jobs.get(id).and_then(|job| job.needs.as_ref()) {
+            for (i, needed) in needs.job_ids().iter().enumerate() {
+                let Some((needed, _)) = jobs.get_key_value(needed) else {
+                    continue;
+                };
+                if let Some(start) = path.iter().position(|id| *id == needed) {
+                    let mut cycle = path[start..].to_vec();
+                    cycle.push(needed);
+                    cycles.push((id, needs_index(needs, i), cycle));
+                } else if !done.contains(needed) {
+                    visit(jobs, needed, path, done, cycles);
+                }
+            }
+        }
+        path.pop();
+        done.insert(id);
+    }
+
+    let mut done = HashSet::new();
+    let mut cycles = vec![];
+    for id in jobs.keys() {
+        if !done.contains(id) {
+            visit(jobs, id, &mut vec![], &mut done, &mut cycles);
+        }
+    }
+    cycles
+}
+
+/// The path of the parent of a node, e.g. `jobs.build` for `jobs.build.steps[0]`.
+fn parent(path: &str) -> &str {
+    path.rfind(['.', '[']).map_or("", |i| &path[..i])
 }
 
 #[cfg(test)]
 mod tests {
+    use indexmap::IndexMap;
+    use serde_valid::Validate;
     use serde_yaml::{from_str, to_string};
 
-    use crate::workflow::Workflow;
+    use crate::workflow::on::Event;
+    use crate::workflow::{JobId, On, Permissions, Workflow};
+    use crate::Document;
 
     #[test]
     fn deserialize() {
@@ -53,7 +271,10 @@ mod tests {
                 concurrency: None,
                 defaults: None,
                 env: None,
-                name: "CI".to_string(),
+                jobs: IndexMap::new(),
+                name: Some("CI".to_string()),
+                on: None,
+                permissions: None,
                 run_name: None,
             }
         );
@@ -65,9 +286,278 @@ mod tests {
             concurrency: None,
             defaults: None,
             env: None,
-            name: "CI".to_string(),
+            jobs: IndexMap::new(),
+            name: Some("CI".to_string()),
+            on: None,
+            permissions: None,
             run_name: None,
         };
         assert_eq!(to_string(&w).unwrap(), "name: CI\n")
     }
+
+    #[test]
+    fn deserialize_run_name() {
+        let w: Workflow = from_str("run-name: Deploy to ${{ inputs.deploy_target }}").unwrap();
+        assert_eq!(
+            w.run_name.unwrap().as_str(),
+            "Deploy to ${{ inputs.deploy_target }}"
+        );
+    }
+
+    #[test]
+    fn deserialize_run_name_err() {
+        assert_eq!(
+            from_str::<Workflow>("run-name: Deploy ${{ inputs.target")
+                .unwrap_err()
+                .to_string(),
+            r#"run-name: not a valid string expression: "Deploy ${{ inputs.target", unclosed `${{`, expected `}}` at position 7 at line 1 column 11"#
+        );
+    }
+
+    #[test]
+    fn load() {
+        let document =
+            Document::parse("name: CI\non: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n")
+                .unwrap();
+        let w = Workflow::load(&document).unwrap();
+        assert_eq!(w.name, Some("CI".to_string()));
+    }
+
+    #[test]
+    fn load_deserialize_err() {
+        let document = Document::parse("on: push\njobs:\n  build:\n    step: []\n")
+            .unwrap()
+            .with_file("ci.yml");
+        let errors = Workflow::load(&document).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0]
+            .to_string()
+            .starts_with("jobs.build.step: unknown field `step`, expected one of"));
+        assert_eq!(errors[0].location().unwrap().to_string(), "ci.yml:4:5");
+    }
+
+    #[test]
+    fn load_all_err() {
+        let document =
+            Document::parse("on: push\nname: [CI]\njobs:\n  build:\n    step: []\n").unwrap();
+        let errors = Workflow::load(&document).unwrap_err();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|err| err.location().unwrap().to_string())
+                .collect::<Vec<_>>(),
+            vec!["2:7", "5:5"]
+        );
+    }
+
+    #[test]
+    fn load_validation_err() {
+        let document =
+            Document::parse("concurrency:\n  group: ''\ndefaults:\n  run: {}\n").unwrap();
+        let errors = Workflow::load(&document).unwrap_err();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|err| format!("{}: {}", err.location().unwrap(), err))
+                .collect::<Vec<_>>(),
+            vec![
+                "2:10: concurrency.group: The length of the value must be `>= 1`.",
+                "4:8: defaults.run: at least one of `shell` or `working-directory` must be specified",
+            ]
+        );
+    }
+
+    #[test]
+    fn load_on_err() {
+        let document = Document::parse(
+            "on:\n  issues:\n    types: [opened, synchronize]\n  push:\n    branches: [main]\n    branches-ignore: [dev]\n  schedule:\n    - cron: '61 * * * *'\n",
+        )
+        .unwrap();
+        let errors = Workflow::load(&document).unwrap_err();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|err| format!("{}: {}", err.location().unwrap(), err))
+                .collect::<Vec<_>>(),
+            vec![
+                "3:21: on.issues.types[1]: unknown activity type `synchronize` for event `issues`, expected one of `opened`, `edited`, `deleted`, `transferred`, `pinned`, `unpinned`, `closed`, `reopened`, `assigned`, `unassigned`, `labeled`, `unlabeled`, `locked`, `unlocked`, `milestoned`, `demilestoned`",
+                "5:5: on.push: `branches` and `branches-ignore` cannot be used together for the same event",
+                "8:13: on.schedule[0].cron: `61` is not a valid minute, expected values from 0 to 59",
+            ]
+        );
+    }
+
+    #[test]
+    fn load_env_err() {
+        let document =
+            Document::parse("on: push\nenv:\n  X: a\n  1Y: b\n  X: c\n  Z=: d\n").unwrap();
+        let errors = Workflow::load(&document).unwrap_err();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|err| format!("{}: {}", err.location().unwrap(), err))
+                .collect::<Vec<_>>(),
+            vec![
+                "4:7: env.1Y: invalid environment variable name `1Y`, a name cannot be empty, start with a digit or contain `=`",
+                "5:6: env.X: duplicate environment variable `X`",
+                "6:7: env.Z=: invalid environment variable name `Z=`, a name cannot be empty, start with a digit or contain `=`",
+            ]
+        );
+    }
+
+    #[test]
+    fn load_step_err() {
+        let document = Document::parse(
+            "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      - id: setup\n        run: make\n      - uses: actions/checkout@v4\n        run: make\n      - id: setup\n        run: make test\n",
+        )
+        .unwrap();
+        let errors = Workflow::load(&document).unwrap_err();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|err| format!("{}: {}", err.location().unwrap(), err))
+                .collect::<Vec<_>>(),
+            vec![
+                "10:13: jobs.build.steps[2].id: the step id `setup` is already used by `steps[0]`",
+                "8:9: jobs.build.steps[1]: `uses` and `run` cannot be used together in a step",
+            ]
+        );
+    }
+
+    #[test]
+    fn load_needs_err() {
+        let document = Document::parse(
+            "on: push\njobs:\n  a:\n    runs-on: ubuntu-latest\n    needs: nope\n  b:\n    runs-on: ubuntu-latest\n    needs: [a, c]\n  c:\n    runs-on: ubuntu-latest\n    needs: [b]\n  d:\n    steps:\n      - run: make\n",
+        )
+        .unwrap();
+        let errors = Workflow::load(&document).unwrap_err();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|err| format!("{}: {}", err.location().unwrap(), err))
+                .collect::<Vec<_>>(),
+            vec![
+                "5:12: jobs.a.needs: the job `nope` is not defined in the workflow",
+                "11:13: jobs.c.needs[0]: the jobs need each other: `b` -> `c` -> `b`",
+                "13:5: jobs.d: a job must have `runs-on`, or `uses` to call a reusable workflow",
+            ]
+        );
+    }
+
+    #[test]
+    fn load_step_field_err() {
+        let document = Document::parse(
+            "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      - uses: actions/checkout\n      - run: echo ${{ a == }}\n      - with: {a: 1}\n",
+        )
+        .unwrap();
+        let errors = Workflow::load(&document).unwrap_err();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|err| format!("{}: {}", err.location().unwrap(), err))
+                .collect::<Vec<_>>(),
+            vec![
+                "6:15: jobs.build.steps[0].uses: invalid reference `actions/checkout`: missing `@ref`, e.g. `actions/checkout@v4`, or `./` for a local path",
+                "7:28: not a valid string expression: \"echo ${{ a == }}\", unexpected end of expression, expected an operand at position 14",
+                "8:9: jobs.build.steps[2]: a step must have either `uses` or `run`",
+                "8:9: jobs.build.steps[2]: `with` can only be used with `uses`",
+            ]
+        );
+    }
+
+    #[test]
+    fn load_expression_err() {
+        let document = Document::parse("run-name: Deploy ${{ inputs.target = 'x' }}\n").unwrap();
+        let errors = Workflow::load(&document).unwrap_err();
+        assert_eq!(errors[0].location().unwrap().to_string(), "1:36");
+    }
+
+    #[test]
+    fn deserialize_without_name() {
+        let w: Workflow = from_str("env: FOO=${{ inputs.foo }}").unwrap();
+        assert_eq!(w.name, None);
+    }
+
+    #[test]
+    fn deserialize_jobs() {
+        let w: Workflow = from_str(
+            r#"
+        jobs:
+          build:
+            runs-on: ubuntu-latest
+          test:
+            needs: build
+            runs-on: ubuntu-latest
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            w.jobs.keys().collect::<Vec<_>>(),
+            vec![&JobId::new("build").unwrap(), &JobId::new("test").unwrap()]
+        );
+    }
+
+    #[test]
+    fn deserialize_jobs_err() {
+        let err = from_str::<Workflow>(
+            r#"
+        jobs:
+          build:
+            runs-on: ubuntu-latest
+            step: []
+        "#,
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("jobs.build: unknown field `step`"));
+    }
+
+    #[test]
+    fn validate_jobs_err() {
+        let w: Workflow = from_str(
+            r#"
+        jobs:
+          build:
+            runs-on: ubuntu-latest
+            environment: ''
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            w.validate().unwrap_err().to_string(),
+            r#"{"errors":[],"properties":{"jobs":{"errors":[],"properties":{"build":{"errors":[],"properties":{"environment":{"errors":["The length of the value must be `>= 1`."]}}}}}}}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_on() {
+        let w: Workflow = from_str(
+            r#"
+        on: [push, pull_request]
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            w.on,
+            Some(On::Events(vec![Event::Push, Event::PullRequest]))
+        );
+    }
+
+    #[test]
+    fn validate_permissions_err() {
+        let w: Workflow = from_str(
+            r#"
+        permissions: read-all
+        jobs:
+          release:
+            runs-on: ubuntu-latest
+            permissions:
+              id-token: read
+        "#,
+        )
+        .unwrap();
+        assert_eq!(w.permissions, Some(Permissions::ReadAll));
+        assert!(w.validate().is_err());
+    }
 }
@@ -0,0 +1,90 @@
+use crate::expression::Span;
+use crate::yaml::{Node, NodeKind};
+
+/// A `# ...` comment of a YAML source.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Comment {
+    /// The text after the `#`, with surrounding whitespace trimmed.
+    pub text: String,
+
+    /// The byte offsets of the comment from its `#` to the end of its line.
+    pub span: Span,
+
+    /// Whether the comment is alone on its line, rather than after a value.
+    pub own_line: bool,
+}
+
+/// Find the comments of a source, a `#` starts a comment at the start of a line or after
+/// whitespace, unless it is in a scalar of the document.
+pub(crate) fn comments(source: &str, root: &Node) -> Vec<Comment> {
+    let mut scalars = vec![];
+    scalar_spans(root, &mut scalars);
+    scalars.sort_by_key(|span| span.start);
+
+    let mut comments = vec![];
+    let mut line_start = 0;
+    for line in source.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        let hash = content.char_indices().find(|&(i, c)| {
+            let offset = line_start + i;
+            c == '#'
+                && content[..i].chars().last().is_none_or(char::is_whitespace)
+                && !in_scalar(&scalars, offset)
+        });
+        if let Some((i, _)) = hash {
+            comments.push(Comment {
+                text: content[i + 1..].trim().to_string(),
+                span: Span::new(line_start + i, line_start + content.len()),
+                own_line: content[..i].trim().is_empty(),
+            });
+        }
+        line_start += line.len();
+    }
+    comments
+}
+
+fn scalar_spans(node: &Node, spans: &mut Vec<Span>) {
+    match &node.kind {
+        NodeKind::Scalar(..) => spans.push(node.span),
+        NodeKind::Sequence(items) => items.iter().for_each(|item| scalar_spans(item, spans)),
+        NodeKind::Mapping(entries) => {
+            for (key, value) in entries {
+                scalar_spans(key, spans);
+                scalar_spans(value, spans);
+            }
+        }
+    }
+}
+
+/// Whether an offset is in one of the spans, sorted by start.
+fn in_scalar(scalars: &[Span], offset: usize) -> bool {
+    let after = scalars.partition_point(|span| span.start <= offset);
+    scalars[..after].iter().any(|span| offset < span.end)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::yaml::Document;
+
+    #[test]
+    fn comments() {
+        let source = "# top\non: push # trailing\nname: 'a # b'\nrun: |\n  echo # not a comment\n# after\nurl: a#b\n";
+        let document = Document::parse(source).unwrap();
+        assert_eq!(
+            document
+                .comments()
+                .iter()
+                .map(|comment| (
+                    comment.text.as_str(),
+                    &source[comment.span.start..comment.span.end],
+                    comment.own_line
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("top", "# top", true),
+                ("trailing", "# trailing", false),
+                ("after", "# after", true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,478 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+
+use crate::expression::{Condition, ExpressionInString, Span};
+use crate::yaml::{Node, NodeKind, ScalarStyle};
+use crate::ExpressionError;
+
+/// An error raised while deserializing a [Node], with the path and span of the offending node.
+#[derive(Debug)]
+pub struct Error {
+    pub message: String,
+    pub path: Option<String>,
+    pub span: Option<Span>,
+    /// The expression syntax error the error was raised for, if any.
+    pub expression: Option<Box<ExpressionError>>,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.path {
+            Some(path) if !path.is_empty() => write!(f, "{}: {}", path, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error {
+            message: msg.to_string(),
+            path: None,
+            span: None,
+            expression: None,
+        }
+    }
+}
+
+/// A serde deserializer for a YAML [Node], resolving plain scalars like the YAML 1.2 core schema.
+pub struct Deserializer<'a> {
+    source: &'a str,
+    node: &'a Node,
+    path: String,
+    /// Paths of the nodes to leave out, as if they were not in the source.
+    skipped: Option<&'a HashSet<String>>,
+}
+
+impl<'a> Deserializer<'a> {
+    pub fn new(source: &'a str, node: &'a Node) -> Self {
+        Self {
+            source,
+            node,
+            path: String::new(),
+            skipped: None,
+        }
+    }
+
+    /// Leave out the nodes at the given paths, e.g. to carry on after an error in one of them.
+    pub fn skipping(mut self, paths: &'a HashSet<String>) -> Self {
+        self.skipped = Some(paths);
+        self
+    }
+
+    fn child(&self, node: &'a Node, path: String) -> Self {
+        Self {
+            source: self.source,
+            node,
+            path,
+            skipped: self.skipped,
+        }
+    }
+
+    /// The path of the value of a key of this mapping node.
+    fn key_path(&self, key: &Node) -> String {
+        let key = key.as_str().unwrap_or("?");
+        if self.path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", self.path, key)
+        }
+    }
+
+    fn is_skipped(&self, path: &str) -> bool {
+        self.skipped.is_some_and(|paths| paths.contains(path))
+    }
+
+    /// Attach the path and span of this node to errors raised by its own visitor; errors of
+    /// nested nodes already carry theirs. Expression syntax errors point into the scalar.
+    fn locate<T>(&self, result: Result<T, Error>) -> Result<T, Error> {
+        result.map_err(|mut err| {
+            if err.span.is_none() {
+                err.span = Some(self.node.span);
+                if let Some(expression) = self.expression_error(&err.message) {
+                    if let Some(span) = expression.span() {
+                        err.span = Some(self.node.value_span(self.source, span));
+                    }
+                    err.expression = Some(Box::new(expression));
+                }
+            }
+            if err.path.is_none() {
+                err.path = Some(self.path.clone());
+            }
+            err
+        })
+    }
+
+    /// The expression syntax error of a scalar, when it is the error with the given message.
+    ///
+    /// The scalar is parsed as a string with expressions and as an `if:` condition, whose
+    /// expression may be written without `${{ }}`.
+    fn expression_error(&self, message: &str) -> Option<ExpressionError> {
+        let value = self.node.as_str()?;
+        [
+            ExpressionInString::new(value).err(),
+            Condition::new(value).err(),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|err| match err {
+            crate::Error::Expression(err) if err.to_string() == message => Some(err),
+            _ => None,
+        })
+    }
+
+    fn visit<V: Visitor<'a>>(&self, visitor: V) -> Result<V::Value, Error> {
+        match &self.node.kind {
+            NodeKind::Scalar(value, ScalarStyle::Plain) => visit_plain(value, visitor),
+            NodeKind::Scalar(value, _) => visitor.visit_str(value),
+            NodeKind::Sequence(items) => {
+                let items = items
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| !self.is_skipped(&format!("{}[{}]", self.path, index)))
+                    .collect::<Vec<_>>();
+                let len = items.len();
+                let mut seq = SeqAccess {
+                    de: self,
+                    items: items.into_iter(),
+                };
+                let value = visitor.visit_seq(&mut seq)?;
+                match seq.items.len() {
+                    0 => Ok(value),
+                    _ => Err(de::Error::invalid_length(len, &"fewer elements")),
+                }
+            }
+            NodeKind::Mapping(entries) => visitor.visit_map(MapAccess {
+                de: self,
+                entries: entries
+                    .iter()
+                    .filter(|(key, _)| !self.is_skipped(&self.key_path(key)))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+                value: None,
+            }),
+        }
+    }
+}
+
+/// Visit a plain scalar as `null`, a boolean, an integer, a float or a string.
+fn visit_plain<'de, V: Visitor<'de>>(value: &str, visitor: V) -> Result<V::Value, Error> {
+    match value {
+        "" | "~" | "null" | "Null" | "NULL" => return visitor.visit_unit(),
+        "true" | "True" | "TRUE" => return visitor.visit_bool(true),
+        "false" | "False" | "FALSE" => return visitor.visit_bool(false),
+        ".inf" | ".Inf" | ".INF" | "+.inf" | "+.Inf" | "+.INF" => {
+            return visitor.visit_f64(f64::INFINITY)
+        }
+        "-.inf" | "-.Inf" | "-.INF" => return visitor.visit_f64(f64::NEG_INFINITY),
+        ".nan" | ".NaN" | ".NAN" => return visitor.visit_f64(f64::NAN),
+        _ => {}
+    }
+    if let Some(hex) = value.strip_prefix("0x") {
+        if let Ok(n) = u64::from_str_radix(hex, 16) {
+            return visitor.visit_u64(n);
+        }
+    }
+    if let Some(octal) = value.strip_prefix("0o") {
+        if let Ok(n) = u64::from_str_radix(octal, 8) {
+            return visitor.visit_u64(n);
+        }
+    }
+    let digits = value.strip_prefix(['-', '+']).unwrap_or(value);
+    if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
+        if let Ok(n) = value.parse::<u64>() {
+            return visitor.visit_u64(n);
+        }
+        if let Ok(n) = value.parse::<i64>() {
+            return visitor.visit_i64(n);
+        }
+    }
+    if digits.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        && digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '-' | '+'))
+    {
+        if let Ok(n) = value.parse::<f64>() {
+            return visitor.visit_f64(n);
+        }
+    }
+    visitor.visit_str(value)
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.locate(self.visit(visitor))
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match &self.node.kind {
+            NodeKind::Scalar(value, _) => self.locate(visitor.visit_str(value)),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.node.is_null() {
+            self.locate(visitor.visit_none())
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let result = match &self.node.kind {
+            NodeKind::Scalar(value, _) => visitor.visit_enum(value.as_str().into_deserializer()),
+            NodeKind::Mapping(entries) if entries.len() == 1 => {
+                let (key, value) = &entries[0];
+                visitor.visit_enum(EnumAccess {
+                    de: &self,
+                    key,
+                    value,
+                })
+            }
+            _ => self.visit(visitor),
+        };
+        self.locate(result)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct
+    }
+}
+
+struct SeqAccess<'a, 'b, I> {
+    de: &'b Deserializer<'a>,
+    items: I,
+}
+
+impl<'de, I> de::SeqAccess<'de> for SeqAccess<'de, '_, I>
+where
+    I: ExactSizeIterator<Item = (usize, &'de Node)>,
+{
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        match self.items.next() {
+            Some((index, node)) => {
+                let path = format!("{}[{}]", self.de.path, index);
+                seed.deserialize(self.de.child(node, path)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct MapAccess<'a, 'b> {
+    de: &'b Deserializer<'a>,
+    entries: std::vec::IntoIter<&'a (Node, Node)>,
+    value: Option<(&'a Node, &'a Node)>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'de, '_> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some((key, value));
+                let path = self.de.key_path(key);
+                seed.deserialize(self.de.child(key, path)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let (key, value) = self
+            .value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        let path = self.de.key_path(key);
+        seed.deserialize(self.de.child(value, path))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct EnumAccess<'a, 'b> {
+    de: &'b Deserializer<'a>,
+    key: &'a Node,
+    value: &'a Node,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess<'de, '_> {
+    type Error = Error;
+    type Variant = Deserializer<'de>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Error> {
+        let path = match self.key.as_str() {
+            Some(key) if !self.de.path.is_empty() => format!("{}.{}", self.de.path, key),
+            Some(key) => key.to_string(),
+            None => self.de.path.clone(),
+        };
+        let variant = seed.deserialize(self.de.child(self.key, path.clone()))?;
+        Ok((variant, self.de.child(self.value, path)))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde::Deserialize;
+
+    use crate::yaml::Document;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Step {
+        name: Option<String>,
+        run: String,
+        #[serde(rename = "timeout-minutes")]
+        timeout_minutes: Option<u64>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Job {
+        steps: Vec<Step>,
+    }
+
+    fn load<T: for<'de> Deserialize<'de>>(source: &str) -> Result<T, crate::Error> {
+        Document::parse(source).unwrap().deserialize()
+    }
+
+    #[test]
+    fn deserialize() {
+        let job: Job =
+            load("steps:\n  - run: make\n    timeout-minutes: 5\n  - name: 42\n    run: ''\n")
+                .unwrap();
+        assert_eq!(
+            job.steps,
+            vec![
+                Step {
+                    name: None,
+                    run: "make".to_string(),
+                    timeout_minutes: Some(5)
+                },
+                Step {
+                    name: Some("42".to_string()),
+                    run: String::new(),
+                    timeout_minutes: None
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn deserialize_scalars() {
+        let map: IndexMap<String, serde_yaml::Value> =
+            load("a: ~\nb: TRUE\nc: -12\nd: 0x1f\ne: 1.5e3\nf: .inf\ng: 'true'\nh: yes\n").unwrap();
+        assert_eq!(
+            serde_yaml::to_string(&map).unwrap(),
+            "a: null\nb: true\nc: -12\nd: 31\ne: 1500.0\nf: .inf\ng: 'true'\nh: yes\n"
+        );
+    }
+
+    #[test]
+    fn deserialize_unknown_field_err() {
+        let err = load::<Job>("steps:\n  - run: make\n    step: x\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "steps[0].step: unknown field `step`, expected one of `name`, `run`, `timeout-minutes`"
+        );
+        assert_eq!(err.location().unwrap().to_string(), "3:5");
+    }
+
+    #[test]
+    fn deserialize_invalid_type_err() {
+        let err = load::<Job>("steps:\n  - run: make\n    timeout-minutes: soon\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "steps[0].timeout-minutes: invalid type: string \"soon\", expected u64"
+        );
+        assert_eq!(err.location().unwrap().to_string(), "3:22");
+    }
+
+    #[test]
+    fn deserialize_missing_field_err() {
+        let err = load::<Job>("steps:\n  - name: build\n").unwrap_err();
+        assert_eq!(err.to_string(), "steps[0]: missing field `run`");
+        assert_eq!(err.location().unwrap().to_string(), "2:5");
+    }
+
+    #[test]
+    fn deserialize_expression_err() {
+        let err = load::<IndexMap<String, crate::Expression>>("if: ${{ a = 'b' }}\n").unwrap_err();
+        assert_eq!(err.location().unwrap().to_string(), "1:11");
+    }
+}
@@ -0,0 +1,548 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use yaml_rust2::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust2::scanner::{Marker, TScalarStyle};
+
+use crate::error::{DeserializeError, YamlError};
+use crate::expression::Span;
+use crate::yaml::de::{self, Deserializer};
+use crate::yaml::{comment, Comment, Location, Node, NodeKind, ScalarStyle};
+use crate::Error;
+
+/// A parsed YAML document that keeps the location of every node.
+///
+/// Types deserialized from a document report errors with the path and location of the
+/// offending node, and the location of any node can be looked up by its path.
+#[derive(Debug, Clone)]
+pub struct Document {
+    file: Option<PathBuf>,
+    source: String,
+    root: Node,
+    comments: Vec<Comment>,
+}
+
+impl Document {
+    /// Parse the first document of a YAML source, an empty source is a `null` document.
+    pub fn parse(source: &str) -> Result<Self, Error> {
+        let mut builder = Builder {
+            source,
+            offsets: char_offsets(source),
+            stack: vec![],
+            anchors: HashMap::new(),
+            root: None,
+        };
+        let mut document = Document {
+            file: None,
+            source: source.to_string(),
+            root: Node::new(
+                NodeKind::Scalar(String::new(), ScalarStyle::Plain),
+                Span::default(),
+            ),
+            comments: vec![],
+        };
+        if let Err(err) = Parser::new_from_str(source).load(&mut builder, false) {
+            let offset = builder.offset(*err.marker());
+            return Err(Error::Yaml(YamlError::new(
+                err.info(),
+                document.location(offset),
+            )));
+        }
+        if let Some(root) = builder.root {
+            document.root = root;
+        }
+        document.comments = comment::comments(source, &document.root);
+        Ok(document)
+    }
+
+    /// Read and parse a YAML file, locations of the document include the file path.
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let source = std::fs::read_to_string(path).map_err(|err| {
+            Error::Yaml(YamlError::new(
+                err.to_string(),
+                Location {
+                    file: Some(path.to_path_buf()),
+                    line: 1,
+                    column: 1,
+                    offset: 0,
+                },
+            ))
+        })?;
+        Self::parse(&source)
+            .map(|document| document.with_file(path))
+            .map_err(|err| match err {
+                Error::Yaml(err) => Error::Yaml(err.with_file(path)),
+                err => err,
+            })
+    }
+
+    /// Set the file the document was read from, it is included in locations.
+    pub fn with_file(mut self, file: impl Into<PathBuf>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn root(&self) -> &Node {
+        &self.root
+    }
+
+    /// The comments of the source, in order.
+    pub fn comments(&self) -> &[Comment] {
+        &self.comments
+    }
+
+    /// The line and column of a byte offset in the source.
+    ///
+    /// An offset inside a character is the location of that character.
+    pub fn location(&self, offset: usize) -> Location {
+        Location {
+            file: self.file.clone(),
+            ..Location::from_offset(&self.source, offset)
+        }
+    }
+
+    /// The location of the node at a path, e.g. `jobs.build.steps[0].run`.
+    ///
+    /// When the path does not exist, e.g. for a missing field, the location of the closest
+    /// existing parent is returned.
+    pub fn path_location(&self, path: &str) -> Location {
+        self.location(self.path_span(path).start)
+    }
+
+    /// The span of the node at a path, or of its closest existing parent.
+    pub fn path_span(&self, path: &str) -> Span {
+        let mut path = path;
+        loop {
+            if let Some(node) = self.root.at_path(path) {
+                return node.span;
+            }
+            match path.rfind(['.', '[']) {
+                Some(i) => path = &path[..i],
+                None => return self.root.span,
+            }
+        }
+    }
+
+    /// The span of the key of the mapping entry at a path, e.g. the name of a variable for
+    /// `env.CI`, or the span of [Document::path_span] when the path is not a mapping entry.
+    pub fn key_span(&self, path: &str) -> Span {
+        let key = path
+            .rsplit_once('.')
+            .and_then(|(parent, key)| Some((self.root.at_path(parent)?, key)))
+            .and_then(|(parent, key)| match &parent.kind {
+                NodeKind::Mapping(entries) => entries
+                    .iter()
+                    .rfind(|(k, _)| k.as_str() == Some(key))
+                    .map(|(k, _)| k.span),
+                _ => None,
+            });
+        key.unwrap_or_else(|| self.path_span(path))
+    }
+
+    /// Deserialize the document, errors report the path and location of the offending node.
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        T::deserialize(Deserializer::new(&self.source, &self.root)).map_err(|err| self.error(err))
+    }
+
+    /// Deserialize the document, carrying on after errors to report every offending node.
+    ///
+    /// A node that fails is left out and the document is deserialized again, so the value
+    /// lacks the offending nodes. There is no value when the root node itself fails.
+    pub fn deserialize_all<T: DeserializeOwned>(&self) -> (Option<T>, Vec<Error>) {
+        let (value, errors, _) = self.deserialize_skipping();
+        (value, errors)
+    }
+
+    /// Like [Document::deserialize_all], with the paths of the nodes left out.
+    pub(crate) fn deserialize_skipping<T: DeserializeOwned>(
+        &self,
+    ) -> (Option<T>, Vec<Error>, HashSet<String>) {
+        let mut skipped = HashSet::new();
+        let mut errors = vec![];
+        loop {
+            let deserializer = Deserializer::new(&self.source, &self.root).skipping(&skipped);
+            let err = match T::deserialize(deserializer) {
+                Ok(value) => return (Some(value), errors, skipped),
+                Err(err) => err,
+            };
+            let path = err.path.clone().unwrap_or_default();
+            // leaving out a required field makes its parent fail, that is not another error
+            if !skipped.iter().any(|skipped| is_ancestor(&path, skipped)) {
+                errors.push(self.error(err));
+            }
+            if path.is_empty() || !skipped.insert(path) {
+                return (None, errors, skipped);
+            }
+        }
+    }
+
+    fn error(&self, err: de::Error) -> Error {
+        let span = err.span.unwrap_or(self.root.span);
+        if let Some(expression) = err.expression {
+            return Error::Expression((*expression).with_location(self.location(span.start)));
+        }
+        Error::Deserialize(
+            DeserializeError::new(
+                err.path.unwrap_or_default(),
+                err.message,
+                Some(self.location(span.start)),
+            )
+            .with_span(span),
+        )
+    }
+}
+
+/// Whether a path is a parent of another path, the empty path is the parent of every path.
+fn is_ancestor(path: &str, of: &str) -> bool {
+    path.is_empty()
+        || of
+            .strip_prefix(path)
+            .is_some_and(|rest| rest.starts_with(['.', '[']))
+}
+
+/// The byte offset of each character of a source, followed by the length of the source.
+fn char_offsets(source: &str) -> Vec<usize> {
+    source
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(source.len()))
+        .collect()
+}
+
+/// Builds the node tree from parser events.
+struct Builder<'a> {
+    source: &'a str,
+    /// Byte offsets of the characters, parser markers count characters.
+    offsets: Vec<usize>,
+    /// Collections being built.
+    stack: Vec<Frame>,
+    anchors: HashMap<usize, Node>,
+    root: Option<Node>,
+}
+
+/// A collection being built, with its anchor id and start offset.
+enum Frame {
+    Sequence(Vec<Node>, usize, usize),
+    /// Entries and the key waiting for its value.
+    Mapping(Vec<(Node, Node)>, Option<Node>, usize, usize),
+}
+
+impl Builder<'_> {
+    /// The byte offset of a parser marker.
+    fn offset(&self, mark: Marker) -> usize {
+        self.offsets
+            .get(mark.index())
+            .copied()
+            .unwrap_or(self.source.len())
+    }
+
+    fn push(&mut self, node: Node, anchor: usize) {
+        if anchor > 0 {
+            self.anchors.insert(anchor, node.clone());
+        }
+        match self.stack.last_mut() {
+            Some(Frame::Sequence(items, ..)) => items.push(node),
+            Some(Frame::Mapping(entries, key, ..)) => match key.take() {
+                Some(key) => entries.push((key, node)),
+                None => *key = Some(node),
+            },
+            None if self.root.is_none() => self.root = Some(node),
+            None => {}
+        }
+    }
+
+    /// The end of a scalar starting at `start`, scalars only have a start mark.
+    fn scalar_end(&self, start: usize, value: &str, style: ScalarStyle) -> usize {
+        let rest = &self.source[start..];
+        if style == ScalarStyle::Plain && rest.starts_with(value) {
+            return start + value.len();
+        }
+        let quote = match style {
+            ScalarStyle::SingleQuoted => Some('\''),
+            ScalarStyle::DoubleQuoted => Some('"'),
+            _ => None,
+        };
+        if matches!(style, ScalarStyle::Literal | ScalarStyle::Folded) {
+            return self.block_scalar_end(start, value);
+        }
+        if let Some(quote) = quote {
+            let mut chars = rest.char_indices().skip(1);
+            while let Some((i, c)) = chars.next() {
+                if c == '\\' && quote == '"' {
+                    chars.next();
+                } else if c == quote {
+                    if quote == '\'' && rest[i + 1..].starts_with('\'') {
+                        chars.next();
+                    } else {
+                        return start + i + 1;
+                    }
+                }
+            }
+        }
+        start + rest.find('\n').unwrap_or(rest.len())
+    }
+}
+
+impl Builder<'_> {
+    /// The end of the last line of a `|` or `>` scalar starting at its first line of text,
+    /// the other lines are indented at least as much as the first one.
+    fn block_scalar_end(&self, start: usize, value: &str) -> usize {
+        let line_start = self.source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let indent = start - line_start;
+        let rest = &self.source[start..];
+        let mut end = start + rest.find('\n').unwrap_or(rest.len());
+        if value.is_empty() {
+            return end;
+        }
+        let mut offset = (end + 1).min(self.source.len());
+        for line in self.source[offset..].split_inclusive('\n') {
+            let content = line.trim_end_matches(['\n', '\r']);
+            if !content.trim().is_empty() {
+                if content.len() - content.trim_start_matches(' ').len() < indent {
+                    break;
+                }
+                end = offset + content.len();
+            }
+            offset += line.len();
+        }
+        end
+    }
+}
+
+impl MarkedEventReceiver for Builder<'_> {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        let offset = self.offset(mark);
+        match event {
+            Event::Scalar(value, style, anchor, _) => {
+                let style = match style {
+                    TScalarStyle::Plain => ScalarStyle::Plain,
+                    TScalarStyle::SingleQuoted => ScalarStyle::SingleQuoted,
+                    TScalarStyle::DoubleQuoted => ScalarStyle::DoubleQuoted,
+                    TScalarStyle::Literal => ScalarStyle::Literal,
+                    TScalarStyle::Folded => ScalarStyle::Folded,
+                };
+                let end = if value.is_empty() && style == ScalarStyle::Plain {
+                    offset
+                } else {
+                    self.scalar_end(offset, &value, style)
+                };
+                let node = Node::new(NodeKind::Scalar(value, style), Span::new(offset, end));
+                self.push(node, anchor);
+            }
+            Event::SequenceStart(anchor, _) => {
+                self.stack.push(Frame::Sequence(vec![], anchor, offset))
+            }
+            Event::MappingStart(anchor, _) => {
+                self.stack
+                    .push(Frame::Mapping(vec![], None, anchor, offset))
+            }
+            Event::SequenceEnd | Event::MappingEnd => {
+                let (kind, anchor, start) = match self.stack.pop() {
+                    Some(Frame::Sequence(items, anchor, start)) => {
+                        (NodeKind::Sequence(items), anchor, start)
+                    }
+                    // block mappings are marked at the first `:`, they start at their first key
+                    Some(Frame::Mapping(entries, _, anchor, start)) => {
+                        let start = entries
+                            .first()
+                            .map_or(start, |(key, _)| key.span.start.min(start));
+                        (NodeKind::Mapping(entries), anchor, start)
+                    }
+                    None => return,
+                };
+                let last_end = match &kind {
+                    NodeKind::Sequence(items) => items.last().map(|node| node.span.end),
+                    NodeKind::Mapping(entries) => entries.last().map(|(_, node)| node.span.end),
+                    NodeKind::Scalar(..) => None,
+                };
+                // flow collections end with their closing bracket
+                let end = if matches!(self.source[offset..].chars().next(), Some(']' | '}')) {
+                    offset + 1
+                } else {
+                    last_end.unwrap_or(start)
+                };
+                self.push(Node::new(kind, Span::new(start, end)), anchor);
+            }
+            Event::Alias(anchor) => {
+                if let Some(node) = self.anchors.get(&anchor).cloned() {
+                    self.push(node, 0);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let document = Document::parse("name: CI\non: [push, pull_request]\n").unwrap();
+        let on = document.root().get("on").unwrap();
+        assert_eq!(on.span, Span::new(13, 33));
+        assert_eq!(on.index(1).unwrap().as_str(), Some("pull_request"));
+        assert_eq!(on.index(1).unwrap().span, Span::new(20, 32));
+    }
+
+    #[test]
+    fn parse_non_ascii() {
+        let source =
+            "# 日本語のコメント\nname: 🚀 Release\non: push\njobs:\n  build:\n    runs-on: 'ünï'\n";
+        let document = Document::parse(source).unwrap();
+        let text = |path: &str| {
+            let span = document.path_span(path);
+            &source[span.start..span.end]
+        };
+        assert_eq!(text("name"), "🚀 Release");
+        assert_eq!(text("on"), "push");
+        assert_eq!(text("jobs.build.runs-on"), "'ünï'");
+        assert_eq!(document.path_location("on").to_string(), "3:5");
+        assert_eq!(
+            document.path_location("jobs.build.runs-on").to_string(),
+            "6:14"
+        );
+    }
+
+    #[test]
+    fn parse_non_ascii_err() {
+        let Error::Yaml(err) = Document::parse("# 日本語\na: [🚀, b\n").unwrap_err() else {
+            panic!("expected a YAML error")
+        };
+        assert_eq!(err.location().line, 3);
+    }
+
+    #[test]
+    fn parse_quoted() {
+        let document = Document::parse("a: 'it''s'\nb: \"x\\\"y\"\n").unwrap();
+        assert_eq!(document.root().get("a").unwrap().span, Span::new(3, 10));
+        assert_eq!(document.root().get("b").unwrap().span, Span::new(14, 20));
+    }
+
+    #[test]
+    fn parse_block_scalar() {
+        let source = "run: |\n  make\n\n    make test\n# done\nname: CI\n";
+        let document = Document::parse(source).unwrap();
+        let span = document.root().get("run").unwrap().span;
+        assert_eq!(&source[span.start..span.end], "make\n\n    make test");
+    }
+
+    #[test]
+    fn parse_alias() {
+        let document = Document::parse("a: &x [1]\nb: *x\n").unwrap();
+        assert_eq!(
+            document.root().get("b").unwrap().index(0).unwrap().as_str(),
+            Some("1")
+        );
+    }
+
+    #[test]
+    fn parse_empty() {
+        assert!(Document::parse("").unwrap().root().is_null());
+    }
+
+    #[test]
+    fn parse_err() {
+        let err = Document::parse("a: [b\n").unwrap_err();
+        let Error::Yaml(err) = err else {
+            panic!("expected a YAML error")
+        };
+        assert_eq!(err.location().line, 2);
+    }
+
+    #[test]
+    fn deserialize_all() {
+        #[derive(Debug, serde::Deserialize)]
+        #[serde(deny_unknown_fields)]
+        #[allow(dead_code)]
+        struct Step {
+            run: String,
+            #[serde(rename = "timeout-minutes")]
+            timeout_minutes: Option<u64>,
+        }
+        let document =
+            Document::parse("- run: a\n  timeout-minutes: soon\n- run: [b]\n- step: c\n- run: d\n")
+                .unwrap();
+        let (steps, errors) = document.deserialize_all::<Vec<Step>>();
+        assert_eq!(steps.unwrap().len(), 2);
+        assert_eq!(
+            errors
+                .iter()
+                .map(|err| format!("{}: {}", err.location().unwrap(), err))
+                .collect::<Vec<_>>(),
+            vec![
+                "2:20: [0].timeout-minutes: invalid type: string \"soon\", expected u64",
+                "3:8: [1].run: invalid type: sequence, expected a string",
+                "4:3: [2].step: unknown field `step`, expected `run` or `timeout-minutes`",
+            ]
+        );
+    }
+
+    #[test]
+    fn deserialize_all_root_err() {
+        let (value, errors) = Document::parse("a")
+            .unwrap()
+            .deserialize_all::<Vec<String>>();
+        assert!(value.is_none());
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn location() {
+        let document = Document::parse("name: CI\non: push # é\njobs:\n").unwrap();
+        assert_eq!(
+            document.location(13),
+            Location {
+                file: None,
+                line: 2,
+                column: 5,
+                offset: 13
+            }
+        );
+        assert_eq!(document.location(document.source().len()).line, 4);
+        // inside `é`, which is two bytes long
+        assert_eq!(document.location(20).column, 12);
+        for offset in 0..=document.source().len() + 1 {
+            assert_eq!(
+                document.location(offset),
+                Location::from_offset(document.source(), offset),
+                "{}",
+                offset
+            );
+        }
+        let document = document.with_file("ci.yml");
+        assert_eq!(document.location(23).to_string(), "ci.yml:3:1");
+    }
+
+    #[test]
+    fn path_location() {
+        let document = Document::parse("jobs:\n  build:\n    runs-on: ubuntu-latest\n").unwrap();
+        assert_eq!(
+            document.path_location("jobs.build.runs-on").to_string(),
+            "3:14"
+        );
+        assert_eq!(
+            document.path_location("jobs.build.steps").to_string(),
+            "3:5"
+        );
+        assert_eq!(document.path_span("jobs.build.runs-on"), Span::new(28, 41));
+    }
+
+    #[test]
+    fn key_span() {
+        let document = Document::parse("env:\n  X: a\n  X: b\njobs: [build]\n").unwrap();
+        assert_eq!(document.key_span("env.X"), Span::new(14, 15));
+        assert_eq!(document.key_span("jobs[0]"), document.path_span("jobs[0]"));
+        assert_eq!(document.key_span("env.Y"), document.path_span("env"));
+    }
+}
@@ -0,0 +1,76 @@
+use std::fmt;
+use std::path::PathBuf;
+
+/// A position in a YAML source file.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Location {
+    /// The file the source was read from, if any.
+    pub file: Option<PathBuf>,
+
+    /// The line, starting at 1.
+    pub line: usize,
+
+    /// The column in characters, starting at 1.
+    pub column: usize,
+
+    /// The byte offset in the source.
+    pub offset: usize,
+}
+
+impl Location {
+    /// The location of a byte offset in a source, without a file.
+    ///
+    /// An offset inside a character is moved to the start of the character, an offset past
+    /// the end of the source to its end.
+    pub fn from_offset(source: &str, offset: usize) -> Self {
+        let mut offset = offset.min(source.len());
+        while !source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Location {
+            file: None,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            offset,
+        }
+    }
+}
+
+/// `file:line:column`, or `line:column` when the file is unknown.
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let mut location = Location {
+            file: None,
+            line: 3,
+            column: 7,
+            offset: 30,
+        };
+        assert_eq!(location.to_string(), "3:7");
+        location.file = Some(PathBuf::from(".github/workflows/ci.yml"));
+        assert_eq!(location.to_string(), ".github/workflows/ci.yml:3:7");
+    }
+
+    #[test]
+    fn from_offset() {
+        let source = "on: push # é\njobs:\n";
+        assert_eq!(Location::from_offset(source, 0).to_string(), "1:1");
+        assert_eq!(Location::from_offset(source, 13).to_string(), "1:13");
+        assert_eq!(Location::from_offset(source, 14).to_string(), "2:1");
+        assert_eq!(Location::from_offset(source, 100).to_string(), "3:1");
+    }
+}
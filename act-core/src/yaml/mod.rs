@@ -0,0 +1,11 @@
+pub use comment::Comment;
+pub use de::Deserializer;
+pub use document::Document;
+pub use location::Location;
+pub use node::{Node, NodeKind, ScalarStyle};
+
+pub mod comment;
+pub mod de;
+pub mod document;
+pub mod location;
+pub mod node;
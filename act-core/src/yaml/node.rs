@@ -0,0 +1,147 @@
+use crate::expression::Span;
+
+/// A node of a YAML document, with the byte offsets it spans in the source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub kind: NodeKind,
+    pub span: Span,
+}
+
+/// The kind of a YAML node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeKind {
+    /// A scalar, with its value after unquoting and folding.
+    Scalar(String, ScalarStyle),
+
+    /// A sequence of nodes.
+    Sequence(Vec<Node>),
+
+    /// The key and value nodes of a mapping, in the order they appear.
+    Mapping(Vec<(Node, Node)>),
+}
+
+/// How a scalar is written, only plain scalars can be something else than a string.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ScalarStyle {
+    Plain,
+    SingleQuoted,
+    DoubleQuoted,
+    Literal,
+    Folded,
+}
+
+impl Node {
+    pub fn new(kind: NodeKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    /// The value of a scalar node.
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.kind {
+            NodeKind::Scalar(value, _) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Whether the node is a plain `null`, `~` or empty scalar.
+    pub fn is_null(&self) -> bool {
+        matches!(
+            &self.kind,
+            NodeKind::Scalar(value, ScalarStyle::Plain) if matches!(value.as_str(), "" | "~" | "null" | "Null" | "NULL")
+        )
+    }
+
+    /// The value of the given key of a mapping node, the last one when the key is repeated.
+    pub fn get(&self, key: &str) -> Option<&Node> {
+        match &self.kind {
+            NodeKind::Mapping(entries) => entries
+                .iter()
+                .rfind(|(k, _)| k.as_str() == Some(key))
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// The item at the given index of a sequence node.
+    pub fn index(&self, index: usize) -> Option<&Node> {
+        match &self.kind {
+            NodeKind::Sequence(items) => items.get(index),
+            _ => None,
+        }
+    }
+
+    /// The node at a path relative to this node, keys are separated by `.` and sequence
+    /// indices are written `[0]`, e.g. `jobs.build.steps[0].run`.
+    pub fn at_path(&self, path: &str) -> Option<&Node> {
+        let mut node = self;
+        for segment in path.split('.').filter(|s| !s.is_empty()) {
+            let (key, mut indices) = match segment.find('[') {
+                Some(i) => segment.split_at(i),
+                None => (segment, ""),
+            };
+            if !key.is_empty() {
+                node = node.get(key)?;
+            }
+            while let Some(rest) = indices.strip_prefix('[') {
+                let end = rest.find(']')?;
+                node = node.index(rest[..end].parse().ok()?)?;
+                indices = &rest[end + 1..];
+            }
+        }
+        Some(node)
+    }
+
+    /// The span in the source of a range of a scalar's value, e.g. an expression in a string.
+    ///
+    /// The range can only be mapped when the value appears verbatim in the source, otherwise,
+    /// e.g. for escaped or multi-line scalars, the span of the whole node is returned.
+    pub fn value_span(&self, source: &str, range: Span) -> Span {
+        let Some(value) = self.as_str() else {
+            return self.span;
+        };
+        match source
+            .get(self.span.start..self.span.end)
+            .and_then(|text| text.find(value))
+        {
+            Some(start) if !value.is_empty() && range.end <= value.len() => Span::new(
+                self.span.start + start + range.start,
+                self.span.start + start + range.end,
+            ),
+            _ => self.span,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::yaml::Document;
+
+    #[test]
+    fn at_path() {
+        let document = Document::parse("jobs:\n  build:\n    steps:\n      - run: make\n").unwrap();
+        let node = document.root().at_path("jobs.build.steps[0].run").unwrap();
+        assert_eq!(node.as_str(), Some("make"));
+        assert_eq!(node.span, Span::new(39, 43));
+        assert_eq!(document.root().at_path(""), Some(document.root()));
+        assert_eq!(document.root().at_path("jobs.test"), None);
+        assert_eq!(document.root().at_path("jobs.build.steps[1]"), None);
+    }
+
+    #[test]
+    fn is_null() {
+        let document = Document::parse("a:\nb: ~\nc: 'null'\n").unwrap();
+        assert!(document.root().get("a").unwrap().is_null());
+        assert!(document.root().get("b").unwrap().is_null());
+        assert!(!document.root().get("c").unwrap().is_null());
+    }
+
+    #[test]
+    fn value_span() {
+        let source = "if: \"${{ a == }}\"\n";
+        let document = Document::parse(source).unwrap();
+        let node = document.root().get("if").unwrap();
+        assert_eq!(node.span, Span::new(4, 17));
+        assert_eq!(node.value_span(source, Span::new(3, 5)), Span::new(8, 10));
+    }
+}
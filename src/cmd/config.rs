@@ -0,0 +1,153 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use act_core::error::ValidationError;
+use act_core::{Config, Document, Error, Registry};
+use clap::ValueEnum;
+
+use crate::cmd::lint::Format;
+
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(clap::Subcommand)]
+pub enum Command {
+    /// Check the configuration file, reporting unknown keys and rules with their location
+    Validate {
+        /// The configuration file, by default `.act-lint.yml` in the current directory or
+        /// the closest of its parents
+        #[arg(long, value_name = "PATH")]
+        config: Option<PathBuf>,
+    },
+}
+
+pub fn run(args: &Args) -> ExitCode {
+    match &args.command {
+        Command::Validate { config } => validate(config.as_deref()),
+    }
+}
+
+fn validate(path: Option<&Path>) -> ExitCode {
+    let path = match find(path) {
+        Ok(Some(path)) => path,
+        Ok(None) => {
+            eprintln!("error: no configuration file found");
+            return ExitCode::FAILURE;
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    match read(&path, &Registry::builtin()) {
+        Ok(_) => {
+            println!("{}: ok", path.display());
+            ExitCode::SUCCESS
+        }
+        Err(errors) => {
+            print_errors(&errors);
+            eprintln!("{} problem(s) found", errors.len());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The configuration file given with `--config`, otherwise the closest to the current
+/// directory.
+pub fn find(path: Option<&Path>) -> io::Result<Option<PathBuf>> {
+    match path {
+        Some(path) if path.is_file() => Ok(Some(path.to_path_buf())),
+        Some(path) => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{}: no such file", path.display()),
+        )),
+        None => Ok(Config::find(&std::env::current_dir()?)),
+    }
+}
+
+/// Read a configuration file, reporting every problem including an unknown `format`.
+pub fn read(path: &Path, registry: &Registry) -> Result<Config, Vec<Error>> {
+    let document = Document::read(path).map_err(|err| vec![err])?;
+    let (config, mut errors) = Config::load_all(&document, registry);
+    if let Some(format) = config.as_ref().and_then(|config| config.format.as_deref()) {
+        if Format::from_str(format, false).is_err() {
+            let expected = Format::value_variants()
+                .iter()
+                .filter_map(|format| format.to_possible_value())
+                .map(|value| format!("`{}`", value.get_name()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let span = document.path_span("format");
+            errors.push(Error::Validation(
+                ValidationError::new(
+                    "format",
+                    format!("unknown format `{}`, expected one of {}", format, expected),
+                    Some(document.location(span.start)),
+                )
+                .with_span(span),
+            ));
+        }
+    }
+    match config {
+        Some(config) if errors.is_empty() => Ok(config),
+        _ => Err(errors),
+    }
+}
+
+pub fn print_errors(errors: &[Error]) {
+    for err in errors {
+        match err.location() {
+            Some(location) => eprintln!("{}: error: {}", location, err),
+            None => eprintln!("error: {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn read_str(name: &str, source: &str) -> Result<Config, Vec<String>> {
+        let path = std::env::temp_dir().join(name);
+        fs::write(&path, source).unwrap();
+        let config = read(&path, &Registry::builtin());
+        fs::remove_file(&path).unwrap();
+        config.map_err(|errors| {
+            errors
+                .iter()
+                .map(|err| {
+                    let location = err.location().unwrap();
+                    format!("{}:{}: {}", location.line, location.column, err)
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn read_ok() {
+        let config = read_str("act-lint-read-ok.yml", "format: sarif\n").unwrap();
+        assert_eq!(config.format.as_deref(), Some("sarif"));
+    }
+
+    #[test]
+    fn read_err() {
+        assert_eq!(
+            read_str("act-lint-read-err.yml", "format: xml\nrule: {}\n").unwrap_err(),
+            vec![
+                "2:1: rule: unknown field `rule`, expected one of `rules`, `ignore`, `self-hosted-runner`, `secrets`, `vars`, `format`",
+                "1:9: format: unknown format `xml`, expected one of `pretty`, `json`, `jsonl`, `sarif`, `github`, `checkstyle`, `junit`",
+            ]
+        );
+    }
+
+    #[test]
+    fn find_missing() {
+        assert!(find(Some(Path::new("does-not-exist.yml"))).is_err());
+    }
+}
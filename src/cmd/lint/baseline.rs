@@ -0,0 +1,102 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use act_core::baseline::{Baseline, Entry};
+
+use crate::cmd::lint::{relative, LintedFile};
+
+/// Read a baseline file written by `--write-baseline`.
+pub fn read(path: &Path) -> io::Result<Baseline> {
+    let json = fs::read_to_string(path)?;
+    serde_json::from_str(&json).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Write the problems of the linted files to a baseline file.
+pub fn write(path: &Path, linted: &[LintedFile]) -> io::Result<Baseline> {
+    let root = parent(path);
+    let mut baseline = Baseline::new();
+    for file in linted {
+        baseline.add(&key(root, &file.file), &file.source, &file.diagnostics);
+    }
+    let json = serde_json::to_string_pretty(&baseline).expect("a baseline is valid JSON");
+    fs::write(path, json + "\n")?;
+    Ok(baseline)
+}
+
+/// Leave out the problems of the baseline, returns the entries that no longer match.
+pub fn apply<'a>(baseline: &'a Baseline, path: &Path, linted: &mut [LintedFile]) -> Vec<&'a Entry> {
+    let root = parent(path);
+    let mut matcher = baseline.matcher();
+    for file in linted {
+        let diagnostics = std::mem::take(&mut file.diagnostics);
+        file.diagnostics = matcher.filter(&key(root, &file.file), &file.source, diagnostics);
+    }
+    matcher.stale()
+}
+
+/// Files are recorded relative to the directory of the baseline, so that it matches
+/// wherever act-lint runs from.
+fn key(root: &Path, file: &Path) -> String {
+    let file = relative(root, file).unwrap_or_else(|| file.to_path_buf());
+    let file = file.to_string_lossy().replace('\\', "/");
+    file.strip_prefix("./").unwrap_or(&file).to_string()
+}
+
+fn parent(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use act_core::Registry;
+
+    use super::*;
+    use crate::cmd::lint::lint_file;
+
+    #[test]
+    fn write_apply() {
+        let root = std::env::temp_dir().join("act-lint-baseline");
+        let workflows = root.join(".github/workflows");
+        fs::create_dir_all(&workflows).unwrap();
+        let workflow = workflows.join("ci.yml");
+        let baseline_path = root.join(".act-lint-baseline.json");
+        let registry = Registry::builtin();
+
+        fs::write(&workflow, "on: push\nenv:\n  GITHUB_A: 1\n  GITHUB_B: 2\n").unwrap();
+        let linted = lint_file(&registry, &workflow).unwrap();
+        let baseline = write(&baseline_path, &[linted]).unwrap();
+        assert_eq!(baseline.entries[0].file, ".github/workflows/ci.yml");
+
+        fs::write(&workflow, "on: push\nenv:\n  GITHUB_B: 2\n  GITHUB_C: 3\n").unwrap();
+        let mut linted = [lint_file(&registry, &workflow).unwrap()];
+        let baseline = read(&baseline_path).unwrap();
+        let stale = apply(&baseline, &baseline_path, &mut linted)
+            .iter()
+            .map(|entry| entry.message.clone())
+            .collect::<Vec<_>>();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(linted[0].diagnostics.len(), 1);
+        assert!(linted[0].diagnostics[0].message.starts_with("env.GITHUB_C"));
+        assert_eq!(stale.len(), 1);
+        assert!(stale[0].starts_with("env.GITHUB_A"));
+    }
+
+    #[test]
+    fn read_err() {
+        let path = std::env::temp_dir().join("act-lint-baseline-err.json");
+        fs::write(&path, "{").unwrap();
+        let err = read(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn key_relative() {
+        assert_eq!(key(Path::new("."), Path::new("./ci.yml")), "ci.yml");
+    }
+}
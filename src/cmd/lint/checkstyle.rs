@@ -0,0 +1,76 @@
+use std::fmt::Write;
+
+use act_core::report::Range;
+use act_core::Severity;
+
+use crate::cmd::lint::xml::escape;
+use crate::cmd::lint::LintedFile;
+
+/// A Checkstyle XML report with a `file` element per linted file.
+///
+/// <https://checkstyle.org/>
+pub fn report(linted: &[LintedFile]) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<checkstyle version=\"4.3\">\n");
+    for file in linted {
+        let _ = writeln!(out, "  <file name=\"{}\">", escape(&file.name()));
+        for diagnostic in &file.diagnostics {
+            let range = Range::new(&file.source, diagnostic.span);
+            let severity = match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Note => "info",
+            };
+            let _ = writeln!(
+                out,
+                "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"act-lint.{}\"/>",
+                range.start.line,
+                range.start.column,
+                severity,
+                escape(&diagnostic.message),
+                diagnostic.rule
+            );
+        }
+        out.push_str("  </file>\n");
+    }
+    out.push_str("</checkstyle>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use act_core::Registry;
+
+    use super::*;
+    use crate::cmd::lint::lint_source;
+
+    #[test]
+    fn checkstyle() {
+        let linted = [
+            lint_source(
+                &Registry::builtin(),
+                Path::new("ci.yml"),
+                "name: [CI]\non: push\n".to_string(),
+            ),
+            lint_source(
+                &Registry::builtin(),
+                Path::new("ok.yml"),
+                "on: push\n".to_string(),
+            ),
+        ];
+        assert_eq!(
+            report(&linted),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<checkstyle version="4.3">
+  <file name="ci.yml">
+    <error line="1" column="7" severity="error" message="name: invalid type: sequence, expected a string" source="act-lint.AL002"/>
+  </file>
+  <file name="ok.yml">
+  </file>
+</checkstyle>
+"#
+        );
+    }
+}
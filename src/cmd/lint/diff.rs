@@ -0,0 +1,157 @@
+/// Lines of context around the changes of a hunk.
+const CONTEXT: usize = 3;
+
+/// A unified diff of two versions of a file, empty when they are the same.
+pub fn unified(name: &str, old: &str, new: &str) -> String {
+    let old_lines = old.split_inclusive('\n').collect::<Vec<_>>();
+    let new_lines = new.split_inclusive('\n').collect::<Vec<_>>();
+    let ops = diff(&old_lines, &new_lines);
+    if ops.iter().all(|op| matches!(op, Op::Equal(..))) {
+        return String::new();
+    }
+
+    let mut out = format!("--- a/{}\n+++ b/{}\n", name, name);
+    let changed = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| !matches!(op, Op::Equal(..)))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    // group changes closer than twice the context into one hunk
+    let mut start = 0;
+    while start < changed.len() {
+        let mut end = start;
+        while end + 1 < changed.len() && changed[end + 1] - changed[end] <= 2 * CONTEXT {
+            end += 1;
+        }
+        let from = changed[start].saturating_sub(CONTEXT);
+        let to = (changed[end] + CONTEXT + 1).min(ops.len());
+        out.push_str(&hunk(&ops[from..to], &ops[..from]));
+        start = end + 1;
+    }
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+/// The operations turning `old` into `new`, from their longest common subsequence.
+fn diff<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Op<'a>> {
+    let (n, m) = (old.len(), new.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut ops = vec![];
+    while i < n || j < m {
+        if i < n && j < m && old[i] == new[j] {
+            ops.push(Op::Equal(old[i]));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(Op::Delete(old[i]));
+            i += 1;
+        } else {
+            ops.push(Op::Insert(new[j]));
+            j += 1;
+        }
+    }
+    ops
+}
+
+/// A hunk of operations, `before` are the operations before it to number its lines.
+fn hunk(ops: &[Op], before: &[Op]) -> String {
+    let count = |ops: &[Op], old: bool| {
+        ops.iter()
+            .filter(|op| match op {
+                Op::Equal(_) => true,
+                Op::Delete(_) => old,
+                Op::Insert(_) => !old,
+            })
+            .count()
+    };
+    let (old_start, new_start) = (count(before, true), count(before, false));
+    let (old_len, new_len) = (count(ops, true), count(ops, false));
+    // an empty range is numbered by the line before it
+    let start = |start: usize, len: usize| if len == 0 { start } else { start + 1 };
+    let mut out = format!(
+        "@@ -{},{} +{},{} @@\n",
+        start(old_start, old_len),
+        old_len,
+        start(new_start, new_len),
+        new_len
+    );
+    for op in ops {
+        let (prefix, line) = match op {
+            Op::Equal(line) => (' ', line),
+            Op::Delete(line) => ('-', line),
+            Op::Insert(line) => ('+', line),
+        };
+        out.push(prefix);
+        out.push_str(line);
+        if !line.ends_with('\n') {
+            out.push_str("\n\\ No newline at end of file\n");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same() {
+        assert_eq!(unified("ci.yml", "a\n", "a\n"), "");
+    }
+
+    #[test]
+    fn one_hunk() {
+        assert_eq!(
+            unified(
+                "ci.yml",
+                "a\nb\nc\nd\ne\nf\ng\nh\n",
+                "a\nb\nc\nd\nE\nf\ng\nh\n"
+            ),
+            "--- a/ci.yml\n+++ b/ci.yml\n@@ -2,7 +2,7 @@\n b\n c\n d\n-e\n+E\n f\n g\n h\n"
+        );
+    }
+
+    #[test]
+    fn two_hunks() {
+        let old = (1..=20).map(|i| format!("{}\n", i)).collect::<String>();
+        let new = (1..=20)
+            .filter(|&i| i != 2)
+            .map(|i| {
+                if i == 19 {
+                    "19\nx\n".to_string()
+                } else {
+                    format!("{}\n", i)
+                }
+            })
+            .collect::<String>();
+        assert_eq!(
+            unified("ci.yml", &old, &new),
+            "--- a/ci.yml\n+++ b/ci.yml\n@@ -1,5 +1,4 @@\n 1\n-2\n 3\n 4\n 5\n@@ -17,4 +16,5 @@\n 17\n 18\n 19\n+x\n 20\n"
+        );
+    }
+
+    #[test]
+    fn no_newline_at_end() {
+        assert_eq!(
+            unified("ci.yml", "a", "b\n"),
+            "--- a/ci.yml\n+++ b/ci.yml\n@@ -1,1 +1,1 @@\n-a\n\\ No newline at end of file\n+b\n"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use act_core::report::Range;
+use act_core::{Diagnostic, Registry, Severity};
+
+use crate::cmd::lint::LintedFile;
+
+/// A workflow command annotating the source of a diagnostic, e.g.
+/// `::error file=ci.yml,line=2,col=11,endLine=2,endColumn=15,title=AL002 schema::message`.
+///
+/// <https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions#setting-an-error-message>
+pub fn annotation(file: &LintedFile, diagnostic: &Diagnostic, registry: &Registry) -> String {
+    let command = match diagnostic.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "notice",
+    };
+    let range = Range::new(&file.source, diagnostic.span);
+    let title = match registry.get(diagnostic.rule) {
+        Some(rule) => format!("{} {}", rule.id(), rule.name()),
+        None => diagnostic.rule.to_string(),
+    };
+    let mut message = diagnostic.message.clone();
+    if let Some(help) = &diagnostic.help {
+        message = format!("{}\nhelp: {}", message, help);
+    }
+    format!(
+        "::{} file={},line={},col={},endLine={},endColumn={},title={}::{}",
+        command,
+        escape_property(&file.name()),
+        range.start.line,
+        range.start.column,
+        range.end.line,
+        range.end.column.max(range.start.column + 1),
+        escape_property(&title),
+        escape_data(&message)
+    )
+}
+
+/// Escape the message of a workflow command.
+fn escape_data(data: &str) -> String {
+    data.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a property value of a workflow command, which also ends at `,` and `::`.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use act_core::Registry;
+
+    use super::*;
+    use crate::cmd::lint::lint_source;
+
+    #[test]
+    fn annotation_error() {
+        let file = lint_source(
+            &Registry::builtin(),
+            Path::new("ci.yml"),
+            "name: [CI]\non: push\n".to_string(),
+        );
+        assert_eq!(
+            annotation(&file, &file.diagnostics[0], &Registry::builtin()),
+            "::error file=ci.yml,line=1,col=7,endLine=1,endColumn=11,title=AL002 schema::name: invalid type: sequence, expected a string"
+        );
+    }
+
+    #[test]
+    fn annotation_warning() {
+        let file = lint_source(
+            &Registry::builtin(),
+            Path::new("ci.yml"),
+            "on: push\nenv:\n  GITHUB_SHA: x\n".to_string(),
+        );
+        assert_eq!(
+            annotation(&file, &file.diagnostics[0], &Registry::builtin()),
+            "::warning file=ci.yml,line=3,col=3,endLine=3,endColumn=13,title=AL005 reserved-env-name::env.GITHUB_SHA: names starting with `GITHUB_` are reserved for the default environment variables%0Ahelp: rename the variable, GitHub may override it"
+        );
+    }
+
+    #[test]
+    fn escape() {
+        assert_eq!(escape_data("100% a\r\nb: c, d"), "100%25 a%0D%0Ab: c, d");
+        assert_eq!(escape_property("a:b,c%"), "a%3Ab%2Cc%25");
+    }
+}
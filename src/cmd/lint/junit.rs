@@ -0,0 +1,117 @@
+use std::fmt::Write;
+
+use act_core::report::Range;
+
+use crate::cmd::lint::xml::escape;
+use crate::cmd::lint::LintedFile;
+
+/// A JUnit XML report with a test suite per linted file and a failing test case per
+/// diagnostic, a file without diagnostics has one passing test case.
+pub fn report(linted: &[LintedFile]) -> String {
+    let tests = linted
+        .iter()
+        .map(|file| file.diagnostics.len().max(1))
+        .sum::<usize>();
+    let failures = linted
+        .iter()
+        .map(|file| file.diagnostics.len())
+        .sum::<usize>();
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"act-lint\" tests=\"{}\" failures=\"{}\">",
+        tests, failures
+    );
+    for file in linted {
+        let name = escape(&file.name());
+        let _ = writeln!(
+            out,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\">",
+            name,
+            file.diagnostics.len().max(1),
+            file.diagnostics.len()
+        );
+        if file.diagnostics.is_empty() {
+            let _ = writeln!(
+                out,
+                "    <testcase name=\"act-lint\" classname=\"{}\"/>",
+                name
+            );
+        }
+        for diagnostic in &file.diagnostics {
+            let range = Range::new(&file.source, diagnostic.span);
+            let location = format!(
+                "{}:{}:{}",
+                file.name(),
+                range.start.line,
+                range.start.column
+            );
+            let mut details = format!(
+                "{}: {}[{}]: {}",
+                location, diagnostic.severity, diagnostic.rule, diagnostic.message
+            );
+            if let Some(help) = &diagnostic.help {
+                let _ = write!(details, "\nhelp: {}", help);
+            }
+            let _ = writeln!(
+                out,
+                "    <testcase name=\"{} {}\" classname=\"{}\">",
+                diagnostic.rule,
+                escape(&location),
+                name
+            );
+            let _ = writeln!(
+                out,
+                "      <failure message=\"{}\" type=\"{}\">{}</failure>",
+                escape(&diagnostic.message),
+                diagnostic.severity,
+                escape(&details)
+            );
+            out.push_str("    </testcase>\n");
+        }
+        out.push_str("  </testsuite>\n");
+    }
+    out.push_str("</testsuites>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use act_core::Registry;
+
+    use super::*;
+    use crate::cmd::lint::lint_source;
+
+    #[test]
+    fn junit() {
+        let linted = [
+            lint_source(
+                &Registry::builtin(),
+                Path::new("ci.yml"),
+                "name: [CI]\non: push\n".to_string(),
+            ),
+            lint_source(
+                &Registry::builtin(),
+                Path::new("ok.yml"),
+                "on: push\n".to_string(),
+            ),
+        ];
+        assert_eq!(
+            report(&linted),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="act-lint" tests="2" failures="1">
+  <testsuite name="ci.yml" tests="1" failures="1" errors="0">
+    <testcase name="AL002 ci.yml:1:7" classname="ci.yml">
+      <failure message="name: invalid type: sequence, expected a string" type="error">ci.yml:1:7: error[AL002]: name: invalid type: sequence, expected a string</failure>
+    </testcase>
+  </testsuite>
+  <testsuite name="ok.yml" tests="1" failures="0" errors="0">
+    <testcase name="act-lint" classname="ok.yml"/>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+}
@@ -1,6 +1,555 @@
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use act_core::report::{FileReport, Report};
+use act_core::{Config, Diagnostic, Registry, Severity};
+use clap::ValueEnum;
+
+use crate::cmd::config;
+use pretty::Renderer;
+
+mod baseline;
+mod checkstyle;
+mod diff;
+mod github;
+mod junit;
+mod pretty;
+mod sarif;
+mod xml;
+
+/// Directory searched for workflow files when no path is given.
+const DEFAULT_WORKFLOWS_DIR: &str = ".github/workflows";
+
 #[derive(clap::Args)]
-pub struct Args {}
+pub struct Args {
+    /// Workflow files, or directories containing `*.yml` / `*.yaml` workflow files
+    #[arg(value_name = "PATH", default_value = DEFAULT_WORKFLOWS_DIR)]
+    pub paths: Vec<PathBuf>,
+
+    /// The output format, by default the `format` of the configuration file or `pretty`
+    #[arg(long, value_enum)]
+    pub format: Option<Format>,
+
+    /// When to color the output, `auto` colors a terminal unless `NO_COLOR` is set
+    #[arg(long, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
+    /// The configuration file, by default `.act-lint.yml` in the current directory or the
+    /// closest of its parents
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Leave out the problems recorded in a baseline file, only new problems are reported
+    #[arg(long, value_name = "PATH")]
+    pub baseline: Option<PathBuf>,
+
+    /// Record the problems found to a baseline file instead of reporting them
+    #[arg(long, value_name = "PATH", conflicts_with = "baseline")]
+    pub write_baseline: Option<PathBuf>,
+
+    /// Apply the fixes of the problems to the files, then report the remaining problems
+    #[arg(long)]
+    pub fix: bool,
+
+    /// Print the changes `--fix` would make as a unified diff instead of the problems
+    #[arg(long, conflicts_with = "fix")]
+    pub fix_dry_run: bool,
+}
+
+impl Args {
+    /// The output format given on the command line, or else in the configuration.
+    fn format(&self, config: &Config) -> Format {
+        self.format
+            .or_else(|| {
+                let format = config.format.as_deref()?;
+                Format::from_str(format, false).ok()
+            })
+            .unwrap_or(Format::Pretty)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum Format {
+    /// Source snippets with the problems underlined
+    Pretty,
+
+    /// A JSON document with the diagnostics of every file, see `act_core::report::Report`
+    Json,
+
+    /// A JSON object per line for each diagnostic, see `act_core::report::Record`
+    Jsonl,
+
+    /// A SARIF 2.1.0 log, e.g. for code scanning
+    Sarif,
+
+    /// Workflow commands that annotate the source when run in a GitHub Actions workflow
+    Github,
+
+    /// A Checkstyle XML report
+    Checkstyle,
+
+    /// A JUnit XML report with a test suite per file and a failing test case per diagnostic
+    Junit,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+/// The diagnostics of a workflow file, with its source to locate them.
+struct LintedFile {
+    file: PathBuf,
+    source: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+pub fn run(args: &Args) -> ExitCode {
+    let mut registry = Registry::builtin();
+    let config_path = match config::find(args.config.as_deref()) {
+        Ok(path) => path,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(path) = &config_path {
+        match config::read(path, &registry) {
+            Ok(config) => registry.configure(config),
+            Err(errors) => {
+                config::print_errors(&errors);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let mut files = match workflow_files(&args.paths) {
+        Ok(files) => files,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(root) = config_path.as_deref().and_then(Path::parent) {
+        files.retain(|file| !is_ignored(registry.config(), root, file));
+    }
+    if files.is_empty() {
+        eprintln!("error: no workflow files found");
+        return ExitCode::FAILURE;
+    }
+
+    let mut failed = 0;
+    let mut linted = vec![];
+    let mut fixes = 0;
+    for file in &files {
+        let result = if args.fix || args.fix_dry_run {
+            fix_file(&registry, file, args.fix).map(|(file, diff, fixed)| {
+                fixes += fixed;
+                if args.fix_dry_run {
+                    print!("{}", diff);
+                }
+                file
+            })
+        } else {
+            lint_file(&registry, file)
+        };
+        match result {
+            Ok(file) => linted.push(file),
+            Err(err) => {
+                failed += 1;
+                eprintln!("error: {}: {}", file.display(), err);
+            }
+        }
+    }
+    if args.fix {
+        eprintln!("applied {} fix(es)", fixes);
+    }
+
+    if let Some(path) = &args.write_baseline {
+        return match baseline::write(path, &linted) {
+            Ok(baseline) => {
+                eprintln!(
+                    "recorded {} problem(s) to {}",
+                    baseline
+                        .entries
+                        .iter()
+                        .map(|entry| entry.count)
+                        .sum::<usize>(),
+                    path.display()
+                );
+                if failed > 0 {
+                    ExitCode::FAILURE
+                } else {
+                    ExitCode::SUCCESS
+                }
+            }
+            Err(err) => {
+                eprintln!("error: {}: {}", path.display(), err);
+                ExitCode::FAILURE
+            }
+        };
+    }
+    if let Some(path) = &args.baseline {
+        let baseline = match baseline::read(path) {
+            Ok(baseline) => baseline,
+            Err(err) => {
+                eprintln!("error: {}: {}", path.display(), err);
+                return ExitCode::FAILURE;
+            }
+        };
+        let stale = baseline::apply(&baseline, path, &mut linted);
+        for entry in &stale {
+            eprintln!(
+                "warning: {}: baseline entry of {} no longer matches: {}",
+                entry.file, entry.rule, entry.message
+            );
+        }
+        if !stale.is_empty() {
+            eprintln!(
+                "{} baseline entries no longer match, update the baseline with --write-baseline",
+                stale.len()
+            );
+        }
+    }
+    failed += linted
+        .iter()
+        .filter(|file| {
+            file.diagnostics
+                .iter()
+                .any(|diagnostic| diagnostic.severity == Severity::Error)
+        })
+        .count();
+
+    match args.format(registry.config()) {
+        // the output is the diff
+        _ if args.fix_dry_run => {}
+        Format::Pretty => {
+            let renderer = renderer(args.color);
+            for file in &linted {
+                for diagnostic in &file.diagnostics {
+                    println!(
+                        "{}",
+                        renderer.render(&file.name(), &file.source, diagnostic)
+                    );
+                }
+            }
+        }
+        Format::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&report(&linted)).expect("a report is valid JSON")
+        ),
+        Format::Sarif => println!(
+            "{}",
+            serde_json::to_string_pretty(&sarif::Log::new(&linted, &registry))
+                .expect("a log is valid JSON")
+        ),
+        Format::Github => {
+            for file in &linted {
+                for diagnostic in &file.diagnostics {
+                    println!("{}", github::annotation(file, diagnostic, &registry));
+                }
+            }
+        }
+        Format::Checkstyle => print!("{}", checkstyle::report(&linted)),
+        Format::Junit => print!("{}", junit::report(&linted)),
+        Format::Jsonl => {
+            for record in report(&linted).records() {
+                println!(
+                    "{}",
+                    serde_json::to_string(&record).expect("a record is valid JSON")
+                );
+            }
+        }
+    }
+
+    if failed > 0 {
+        eprintln!("{} of {} workflow file(s) failed", failed, files.len());
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn renderer(color: ColorChoice) -> Renderer {
+    let stdout = io::stdout();
+    let color = match color {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                && stdout.is_terminal()
+        }
+    };
+    // messages are only wrapped for a terminal, other readers may expect one line each
+    let width = terminal_size::terminal_size_of(&stdout).map(|(width, _)| width.0 as usize);
+    Renderer::new(color, width)
+}
+
+fn report(linted: &[LintedFile]) -> Report {
+    Report::new(
+        linted
+            .iter()
+            .map(|file| FileReport::new(file.name(), &file.source, &file.diagnostics))
+            .collect(),
+    )
+}
+
+/// Expand the given paths into the list of workflow files to lint.
+///
+/// Directories are not searched recursively, GitHub only picks up workflows
+/// located directly in `.github/workflows`.
+fn workflow_files(paths: &[PathBuf]) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut entries = fs::read_dir(path)?
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<io::Result<Vec<_>>>()?;
+            entries.retain(|p| p.is_file() && is_yaml(p));
+            entries.sort();
+            files.extend(entries);
+        } else if path.exists() {
+            files.push(path.clone());
+        } else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{}: no such file or directory", path.display()),
+            ));
+        }
+    }
+    Ok(files)
+}
+
+/// Whether the configuration ignores a file, globs are relative to the directory of the
+/// configuration file.
+fn is_ignored(config: &Config, root: &Path, file: &Path) -> bool {
+    config.is_ignored(relative(root, file).as_deref().unwrap_or(file))
+}
+
+/// The path of a file relative to a directory, if the file is in it.
+fn relative(root: &Path, file: &Path) -> Option<PathBuf> {
+    let root = root.canonicalize().ok()?;
+    let file = file.canonicalize().ok()?;
+    file.strip_prefix(root).map(Path::to_path_buf).ok()
+}
+
+fn is_yaml(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("yml") | Some("yaml")
+    )
+}
+
+/// Lint a single workflow file, reporting every problem found.
+fn lint_file(registry: &Registry, path: &Path) -> io::Result<LintedFile> {
+    let source = fs::read_to_string(path)?;
+    Ok(lint_source(registry, path, source))
+}
+
+/// Fix the problems of a workflow file, writing it back unless `write` is false.
+///
+/// Returns the remaining problems, the diff of the changes and how many fixes were applied.
+fn fix_file(
+    registry: &Registry,
+    path: &Path,
+    write: bool,
+) -> io::Result<(LintedFile, String, usize)> {
+    let source = fs::read_to_string(path)?;
+    let fixed = act_core::fix::fix(registry, &source, Some(path));
+    let name = path.display().to_string();
+    let diff = diff::unified(
+        name.strip_prefix("./").unwrap_or(&name),
+        &source,
+        &fixed.source,
+    );
+    if write && fixed.source != source {
+        fs::write(path, &fixed.source)?;
+    }
+    let file = LintedFile {
+        file: path.to_path_buf(),
+        source: fixed.source,
+        diagnostics: fixed.diagnostics.into_iter().collect(),
+    };
+    Ok((file, diff, fixed.fixes))
+}
+
+fn lint_source(registry: &Registry, path: &Path, source: String) -> LintedFile {
+    let diagnostics = registry.lint(&source, Some(path)).into_iter().collect();
+    LintedFile {
+        file: path.to_path_buf(),
+        source,
+        diagnostics,
+    }
+}
+
+impl LintedFile {
+    /// The path of the file, as given on the command line.
+    fn name(&self) -> String {
+        self.file.display().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use act_core::Document;
+
+    use super::*;
+
+    fn lint_str(source: &str) -> Vec<String> {
+        let document = Document::parse(source).unwrap();
+        lint_source(
+            &Registry::builtin(),
+            Path::new("ci.yml"),
+            source.to_string(),
+        )
+        .diagnostics
+        .iter()
+        .map(|diagnostic| {
+            format!(
+                "{}: {}[{}]: {}",
+                document.location(diagnostic.span.start),
+                diagnostic.severity,
+                diagnostic.rule,
+                diagnostic.message
+            )
+        })
+        .collect()
+    }
+
+    #[test]
+    fn lint_str_ok() {
+        assert!(lint_str("name: CI\non: push\n").is_empty());
+    }
+
+    #[test]
+    fn lint_str_warnings() {
+        assert_eq!(
+            lint_str("on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    env:\n      GITHUB_SHA: x\n"),
+            vec!["6:7: warning[AL005]: jobs.build.env.GITHUB_SHA: names starting with `GITHUB_` are reserved for the default environment variables"]
+        );
+    }
+
+    #[test]
+    fn lint_str_deserialize_err() {
+        assert_eq!(
+            lint_str("name: CI\ndefaults: true\n"),
+            vec!["2:11: error[AL002]: defaults: invalid type: boolean `true`, expected a map with a `run` key"]
+        );
+    }
+
+    #[test]
+    fn lint_str_validation_err() {
+        assert_eq!(
+            lint_str("name: CI\nconcurrency:\n  group: ''\n"),
+            vec!["3:10: error[AL003]: concurrency.group: The length of the value must be `>= 1`."]
+        );
+    }
+
+    #[test]
+    fn lint_str_all_err() {
+        assert_eq!(
+            lint_str("name: [CI]\nrun-name: ${{ a = b }}\nconcurrency:\n  group: ''\n").len(),
+            3
+        );
+    }
+
+    #[test]
+    fn lint_file_yaml_err() {
+        let path = std::env::temp_dir().join("act-lint-yaml-err.yml");
+        fs::write(&path, "on: [push\n").unwrap();
+        let report = lint_file(&Registry::builtin(), &path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(report.source, "on: [push\n");
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(report.diagnostics[0].rule, "AL001");
+    }
+
+    #[test]
+    fn report_json() {
+        let linted = lint_source(
+            &Registry::builtin(),
+            Path::new("ci.yml"),
+            "name: [CI]\non: push\n".to_string(),
+        );
+        let json = serde_json::to_value(report(&[linted])).unwrap();
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["files"][0]["file"], "ci.yml");
+        assert_eq!(json["files"][0]["diagnostics"][0]["rule"], "AL002");
+        assert_eq!(
+            json["files"][0]["diagnostics"][0]["range"]["start"],
+            serde_json::json!({"line": 1, "column": 7, "offset": 6})
+        );
+    }
+
+    #[test]
+    fn format_merge() {
+        let mut args = Args {
+            paths: vec![],
+            format: None,
+            color: ColorChoice::Never,
+            config: None,
+            baseline: None,
+            write_baseline: None,
+            fix: false,
+            fix_dry_run: false,
+        };
+        let config = Config {
+            format: Some("sarif".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(args.format(&Config::default()), Format::Pretty);
+        assert_eq!(args.format(&config), Format::Sarif);
+        args.format = Some(Format::Json);
+        assert_eq!(args.format(&config), Format::Json);
+    }
+
+    #[test]
+    fn is_ignored_relative_to_config() {
+        let root = std::env::temp_dir().join("act-lint-ignored");
+        let workflows = root.join(".github/workflows");
+        fs::create_dir_all(&workflows).unwrap();
+        fs::write(workflows.join("gen-a.yml"), "on: push\n").unwrap();
+        fs::write(workflows.join("ci.yml"), "on: push\n").unwrap();
+        let config = Config {
+            ignore: vec![".github/workflows/gen-*.yml".to_string()],
+            ..Config::default()
+        };
+        let ignored = is_ignored(&config, &root, &workflows.join("gen-a.yml"));
+        let linted = is_ignored(&config, &root, &workflows.join("ci.yml"));
+        fs::remove_dir_all(&root).unwrap();
+        assert!(ignored);
+        assert!(!linted);
+    }
+
+    #[test]
+    fn fix_file_dry_run() {
+        let path = std::env::temp_dir().join("act-lint-fix.yml");
+        let source = "on: push # act-lint-disable-next-line\nname: CI\nrun-name: [CI]\n";
+        fs::write(&path, source).unwrap();
+        let (file, diff, fixes) = fix_file(&Registry::builtin(), &path, false).unwrap();
+        let unchanged = fs::read_to_string(&path).unwrap();
+        fix_file(&Registry::builtin(), &path, true).unwrap();
+        let fixed = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(fixes, 1);
+        assert!(diff.ends_with(
+            "@@ -1,3 +1,3 @@\n-on: push # act-lint-disable-next-line\n+on: push\n name: CI\n run-name: [CI]\n"
+        ));
+        assert_eq!(unchanged, source);
+        assert_eq!(fixed, "on: push\nname: CI\nrun-name: [CI]\n");
+        assert_eq!(file.diagnostics.len(), 1);
+        assert_eq!(file.diagnostics[0].rule, "AL002");
+    }
 
-pub fn run(_: &Args) {
-    println!("🚧 hold on! not yet implemented")
+    #[test]
+    fn is_yaml_ok() {
+        assert!(is_yaml(Path::new("ci.yml")));
+        assert!(is_yaml(Path::new("ci.yaml")));
+        assert!(!is_yaml(Path::new("README.md")));
+    }
 }
@@ -0,0 +1,319 @@
+use std::fmt::Write;
+
+use act_core::expression::Span;
+use act_core::{Diagnostic, Severity};
+use anstyle::{AnsiColor, Style};
+use unicode_width::UnicodeWidthStr;
+
+/// Tabs are expanded so that carets line up with the source.
+const TAB: &str = "    ";
+
+/// Renders diagnostics like rustc: a header, the location, the offending source lines with
+/// the spans underlined and the help text.
+///
+/// ```text
+/// error[AL002]: defaults: invalid type: boolean `true`, expected a map with a `run` key
+///  --> ci.yml:2:11
+///   |
+/// 2 | defaults: true
+///   |           ^^^^
+/// ```
+pub struct Renderer {
+    color: bool,
+
+    /// The width to wrap messages to, messages are not wrapped without one.
+    width: Option<usize>,
+}
+
+/// An underlined span of a source line.
+struct Mark<'a> {
+    line: usize,
+    /// Display columns of the underline, starting at 0.
+    start: usize,
+    end: usize,
+    primary: bool,
+    message: Option<&'a str>,
+}
+
+impl Renderer {
+    pub fn new(color: bool, width: Option<usize>) -> Self {
+        Self { color, width }
+    }
+
+    pub fn render(&self, file: &str, source: &str, diagnostic: &Diagnostic) -> String {
+        let severity = match diagnostic.severity {
+            Severity::Error => AnsiColor::BrightRed,
+            Severity::Warning => AnsiColor::BrightYellow,
+            Severity::Note => AnsiColor::BrightCyan,
+        };
+        let severity = Style::new().fg_color(Some(severity.into())).bold();
+        let gutter = Style::new()
+            .fg_color(Some(AnsiColor::BrightBlue.into()))
+            .bold();
+        let bold = Style::new().bold();
+
+        let lines = source.split('\n').collect::<Vec<_>>();
+        let mut marks = vec![mark(&lines, diagnostic.span, true, None)];
+        marks.extend(
+            diagnostic
+                .labels
+                .iter()
+                .map(|label| mark(&lines, label.span, false, Some(&label.message))),
+        );
+        let last_line = marks.iter().map(|mark| mark.line).max().unwrap_or(0);
+        let number_width = (last_line + 1).to_string().len();
+        let pad = " ".repeat(number_width);
+
+        let mut out = String::new();
+        let header = format!("{}[{}]", diagnostic.severity, diagnostic.rule);
+        let message = self.wrap(&diagnostic.message, header.width() + 2);
+        let _ = writeln!(
+            out,
+            "{}{}",
+            self.paint(severity, &header),
+            self.paint(bold, &format!(": {}", message))
+        );
+        let _ = writeln!(
+            out,
+            "{}{} {}:{}:{}",
+            pad,
+            self.paint(gutter, "-->"),
+            file,
+            marks[0].line + 1,
+            column(
+                lines.get(marks[0].line).copied().unwrap_or(""),
+                marks[0].start
+            ) + 1
+        );
+        let empty_gutter = format!("{} {}", pad, self.paint(gutter, "|"));
+        let _ = writeln!(out, "{}", empty_gutter);
+
+        let mut shown = marks.iter().map(|mark| mark.line).collect::<Vec<_>>();
+        shown.sort_unstable();
+        shown.dedup();
+        for (i, &line) in shown.iter().enumerate() {
+            if i > 0 && line > shown[i - 1] + 1 {
+                let _ = writeln!(out, "{}", self.paint(gutter, "..."));
+            }
+            let text = lines.get(line).copied().unwrap_or("").replace('\t', TAB);
+            let number = format!("{:>width$} |", line + 1, width = number_width);
+            let _ = writeln!(
+                out,
+                "{} {}",
+                self.paint(gutter, &number),
+                text.trim_end_matches('\r')
+            );
+            // the primary mark first, then labels in the order they were added
+            let mut line_marks = marks
+                .iter()
+                .filter(|mark| mark.line == line)
+                .collect::<Vec<_>>();
+            line_marks.sort_by_key(|mark| !mark.primary);
+            for mark in line_marks {
+                let (style, underline) = if mark.primary {
+                    (severity, "^")
+                } else {
+                    (gutter, "-")
+                };
+                let mut underline = underline.repeat(mark.end - mark.start);
+                if let Some(message) = mark.message {
+                    underline.push(' ');
+                    underline.push_str(&self.wrap(message, number_width + 4 + mark.end + 1));
+                }
+                let _ = writeln!(
+                    out,
+                    "{} {}{}",
+                    empty_gutter,
+                    " ".repeat(mark.start),
+                    self.paint(style, &underline)
+                );
+            }
+        }
+
+        let mut notes = vec![];
+        if let Some(help) = &diagnostic.help {
+            notes.push(help.as_str());
+        }
+        if let Some(fix) = &diagnostic.fix {
+            if diagnostic.help.as_deref() != Some(fix.message.as_str()) {
+                notes.push(fix.message.as_str());
+            }
+        }
+        if !notes.is_empty() {
+            let _ = writeln!(out, "{}", empty_gutter);
+        }
+        for note in notes {
+            let _ = writeln!(
+                out,
+                "{} {} {}",
+                pad,
+                self.paint(gutter, "="),
+                self.paint(bold, "help:") + " " + &self.wrap(note, number_width + 9)
+            );
+        }
+        out
+    }
+
+    fn paint(&self, style: Style, text: &str) -> String {
+        if self.color {
+            format!("{}{}{}", style.render(), text, style.render_reset())
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Wrap text that starts at the given display column, continuation lines are indented to
+    /// that column.
+    fn wrap(&self, text: &str, indent: usize) -> String {
+        let Some(width) = self.width.filter(|width| *width > indent + 20) else {
+            return text.to_string();
+        };
+        let mut out = String::new();
+        let mut line_width = 0;
+        for word in text.split(' ') {
+            let word_width = word.width();
+            if line_width > 0 && indent + line_width + 1 + word_width > width {
+                out.push('\n');
+                out.push_str(&" ".repeat(indent));
+                line_width = 0;
+            } else if line_width > 0 {
+                out.push(' ');
+                line_width += 1;
+            }
+            out.push_str(word);
+            line_width += word_width;
+        }
+        out
+    }
+}
+
+/// Locate a span in the source lines, a span covering several lines is underlined to the
+/// end of its first line and an empty span is underlined by one caret.
+fn mark<'a>(lines: &[&str], span: Span, primary: bool, message: Option<&'a str>) -> Mark<'a> {
+    let mut line = 0;
+    let mut line_start = 0;
+    for (i, text) in lines.iter().enumerate() {
+        // `split('\n')` drops the newlines, each line ends one byte after its text
+        if line_start + text.len() >= span.start || i + 1 == lines.len() {
+            line = i;
+            break;
+        }
+        line_start += text.len() + 1;
+    }
+    let text = lines.get(line).copied().unwrap_or("");
+    let from = span.start.saturating_sub(line_start).min(text.len());
+    let to = span.end.saturating_sub(line_start).clamp(from, text.len());
+    let start = display_width(text.get(..from).unwrap_or(text));
+    let end = display_width(text.get(..to).unwrap_or(text)).max(start + 1);
+    Mark {
+        line,
+        start,
+        end,
+        primary,
+        message,
+    }
+}
+
+fn display_width(text: &str) -> usize {
+    text.replace('\t', TAB).width()
+}
+
+/// The character column of a display column of a line, for the `-->` location.
+fn column(text: &str, display: usize) -> usize {
+    let mut width = 0;
+    for (i, c) in text.chars().enumerate() {
+        if width >= display {
+            return i;
+        }
+        width += display_width(c.encode_utf8(&mut [0; 4]));
+    }
+    text.chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use act_core::diagnostic::Fix;
+
+    use super::*;
+
+    #[test]
+    fn render() {
+        let source = "name: CI\ndefaults: true\n";
+        let diagnostic = Diagnostic::error("AL002", "defaults: invalid type", Span::new(19, 23))
+            .with_help("use a map with a `run` key");
+        assert_eq!(
+            Renderer::new(false, None).render("ci.yml", source, &diagnostic),
+            "error[AL002]: defaults: invalid type
+ --> ci.yml:2:11
+  |
+2 | defaults: true
+  |           ^^^^
+  |
+  = help: use a map with a `run` key
+"
+        );
+    }
+
+    #[test]
+    fn render_labels() {
+        let source = "env:\n  GITHUB_SHA: x\njobs: {}\n";
+        let diagnostic = Diagnostic::warning("AL005", "reserved name", Span::new(7, 17))
+            .with_label(Span::new(0, 3), "in this map")
+            .with_label(Span::new(21, 25), "not here")
+            .with_fix(Fix::new("rename the variable", vec![]));
+        assert_eq!(
+            Renderer::new(false, None).render("ci.yml", source, &diagnostic),
+            "warning[AL005]: reserved name
+ --> ci.yml:2:3
+  |
+1 | env:
+  | --- in this map
+2 |   GITHUB_SHA: x
+  |   ^^^^^^^^^^
+3 | jobs: {}
+  | ---- not here
+  |
+  = help: rename the variable
+"
+        );
+    }
+
+    #[test]
+    fn render_empty_span() {
+        let source = "on: [push\n";
+        let diagnostic = Diagnostic::error("AL001", "invalid YAML", Span::new(10, 10));
+        assert_eq!(
+            Renderer::new(false, None).render("ci.yml", source, &diagnostic),
+            "error[AL001]: invalid YAML
+ --> ci.yml:2:1
+  |
+2 | 
+  | ^
+"
+        );
+    }
+
+    #[test]
+    fn render_color() {
+        let diagnostic = Diagnostic::error("AL002", "x", Span::new(0, 1));
+        let out = Renderer::new(true, None).render("ci.yml", "a\n", &diagnostic);
+        assert!(out.starts_with("\u{1b}[1m\u{1b}[91merror[AL002]\u{1b}[0m"));
+    }
+
+    #[test]
+    fn wrap() {
+        let renderer = Renderer::new(false, Some(40));
+        assert_eq!(
+            renderer.wrap("the quick brown fox jumps over the lazy dog", 15),
+            "the quick brown fox jumps\n               over the lazy dog"
+        );
+        assert_eq!(Renderer::new(false, None).wrap("a b", 15), "a b");
+    }
+
+    #[test]
+    fn mark_wide_chars() {
+        let lines = ["name: é日本", ""];
+        let mark = mark(&lines, Span::new(8, 14), true, None);
+        assert_eq!((mark.line, mark.start, mark.end), (0, 7, 11));
+    }
+}
@@ -0,0 +1,341 @@
+use std::collections::{BTreeMap, HashMap};
+
+use act_core::report::Range;
+use act_core::{Diagnostic, Registry, Rule, Severity};
+use serde::Serialize;
+
+use crate::cmd::lint::LintedFile;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// The key of the fingerprints of results, versioned in case the fingerprint changes.
+///
+/// The value is the [fingerprint](Diagnostic::fingerprint) of the problem followed by `:` and
+/// its occurrence in the file, starting at 1, so identical problems get distinct values.
+const FINGERPRINT: &str = "actLint/v2";
+
+/// A SARIF 2.1.0 log, with the subset of properties code scanning uses.
+///
+/// <https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html>
+#[derive(Debug, Serialize)]
+pub struct Log {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    results: Vec<SarifResult>,
+    /// Columns count characters, like the columns of act-lint.
+    column_kind: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<ReportingDescriptor>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportingDescriptor {
+    id: &'static str,
+    name: &'static str,
+    short_description: Message,
+    full_description: Message,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<&'static str>,
+    default_configuration: Configuration,
+    properties: Properties,
+}
+
+#[derive(Debug, Serialize)]
+struct Properties {
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Configuration {
+    level: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<Location>,
+    partial_fingerprints: BTreeMap<&'static str, String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<Message>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Debug, Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+impl Log {
+    pub fn new(linted: &[LintedFile], registry: &Registry) -> Self {
+        let results = linted
+            .iter()
+            .flat_map(|file| {
+                let mut occurrences = HashMap::new();
+                file.diagnostics
+                    .iter()
+                    .map(move |diagnostic| result(file, diagnostic, registry, &mut occurrences))
+            })
+            .collect();
+        Log {
+            schema: SCHEMA,
+            version: "2.1.0",
+            runs: vec![Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: env!("CARGO_PKG_NAME"),
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: "https://github.com/actyoms/act-lint",
+                        rules: registry.rules().map(descriptor).collect(),
+                    },
+                },
+                results,
+                column_kind: "unicodeCodePoints",
+            }],
+        }
+    }
+}
+
+fn descriptor(rule: &dyn Rule) -> ReportingDescriptor {
+    ReportingDescriptor {
+        id: rule.id(),
+        name: rule.name(),
+        short_description: Message {
+            text: rule.description().to_string(),
+        },
+        full_description: Message {
+            text: rule.explanation().to_string(),
+        },
+        help_uri: rule.help_uri(),
+        default_configuration: Configuration {
+            level: level(rule.default_severity()),
+        },
+        properties: Properties {
+            tags: vec![rule.category().to_string()],
+        },
+    }
+}
+
+/// A result, `occurrences` counts the fingerprints of the results of the file so far.
+fn result(
+    file: &LintedFile,
+    diagnostic: &Diagnostic,
+    registry: &Registry,
+    occurrences: &mut HashMap<String, usize>,
+) -> SarifResult {
+    let uri = uri(&file.name());
+    let fingerprint = diagnostic.fingerprint(&uri, &file.source);
+    let occurrence = occurrences.entry(fingerprint.clone()).or_insert(0);
+    *occurrence += 1;
+    let fingerprint = format!("{}:{}", fingerprint, occurrence);
+    let location = |span, message: Option<&str>| Location {
+        physical_location: PhysicalLocation {
+            artifact_location: ArtifactLocation { uri: uri.clone() },
+            region: region(Range::new(&file.source, span)),
+        },
+        message: message.map(|text| Message {
+            text: text.to_string(),
+        }),
+    };
+    let mut message = diagnostic.message.clone();
+    if let Some(help) = &diagnostic.help {
+        message = format!("{}\nhelp: {}", message, help);
+    }
+    SarifResult {
+        rule_id: diagnostic.rule,
+        rule_index: registry
+            .rules()
+            .position(|rule| rule.id() == diagnostic.rule),
+        level: level(diagnostic.severity),
+        message: Message { text: message },
+        locations: vec![location(diagnostic.span, None)],
+        related_locations: diagnostic
+            .labels
+            .iter()
+            .map(|label| location(label.span, Some(&label.message)))
+            .collect(),
+        partial_fingerprints: [(FINGERPRINT, fingerprint)].into(),
+    }
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+    }
+}
+
+/// SARIF regions have at least one column, a position is shown as its character.
+fn region(range: Range) -> Region {
+    let end_column = if range.end == range.start {
+        range.end.column + 1
+    } else {
+        range.end.column
+    };
+    Region {
+        start_line: range.start.line,
+        start_column: range.start.column,
+        end_line: range.end.line,
+        end_column,
+    }
+}
+
+/// A relative URI reference for a path, code scanning resolves it against the repository.
+fn uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.strip_prefix("./").unwrap_or(&path).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use act_core::Registry;
+
+    use super::*;
+    use crate::cmd::lint::lint_source;
+
+    #[test]
+    fn log() {
+        let linted = lint_source(
+            &Registry::builtin(),
+            Path::new("./.github/workflows/ci.yml"),
+            "name: [CI]\non: push\n".to_string(),
+        );
+        let log = serde_json::to_value(Log::new(&[linted], &Registry::builtin())).unwrap();
+        assert_eq!(log["version"], "2.1.0");
+        let run = &log["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "AL002");
+        assert_eq!(
+            run["tool"]["driver"]["rules"][1]["defaultConfiguration"]["level"],
+            "error"
+        );
+        assert_eq!(
+            run["tool"]["driver"]["rules"][1]["properties"]["tags"],
+            serde_json::json!(["syntax"])
+        );
+        let result = &run["results"][0];
+        assert_eq!(result["ruleId"], "AL002");
+        assert_eq!(result["ruleIndex"], 1);
+        assert_eq!(result["level"], "error");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"],
+            serde_json::json!({
+                "artifactLocation": {"uri": ".github/workflows/ci.yml"},
+                "region": {"startLine": 1, "startColumn": 7, "endLine": 1, "endColumn": 11}
+            })
+        );
+        assert_eq!(
+            result["partialFingerprints"]["actLint/v2"]
+                .as_str()
+                .unwrap()
+                .len(),
+            66
+        );
+    }
+
+    #[test]
+    fn stable_fingerprints() {
+        let fingerprint = |source: &str| {
+            let linted = lint_source(
+                &Registry::builtin(),
+                Path::new("ci.yml"),
+                source.to_string(),
+            );
+            let log = serde_json::to_value(Log::new(&[linted], &Registry::builtin())).unwrap();
+            log["runs"][0]["results"][0]["partialFingerprints"]["actLint/v2"].clone()
+        };
+        assert_eq!(
+            fingerprint("name: [CI]\non: push\n"),
+            fingerprint("on: push\n\nname: [CI]\n")
+        );
+    }
+
+    #[test]
+    fn identical_lines_fingerprints() {
+        let source = "on: push\njobs:\n  a:\n    runs-on: ubuntu-latest\n    env:\n      GITHUB_A: 1\n    steps:\n      - run: a\n  b:\n    runs-on: ubuntu-latest\n    env:\n      GITHUB_A: 1\n    steps:\n      - run: b\n";
+        let linted = lint_source(
+            &Registry::builtin(),
+            Path::new("ci.yml"),
+            source.to_string(),
+        );
+        let log = serde_json::to_value(Log::new(&[linted], &Registry::builtin())).unwrap();
+        let fingerprints = log["runs"][0]["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|result| {
+                result["partialFingerprints"]["actLint/v2"]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(fingerprints.len(), 2);
+        assert_ne!(fingerprints[0], fingerprints[1]);
+        assert!(fingerprints[0].ends_with(":1"));
+        assert!(fingerprints[1].ends_with(":2"));
+        assert_eq!(fingerprints[0][..64], fingerprints[1][..64]);
+    }
+
+    #[test]
+    fn empty_region() {
+        let source = "on: [push\n";
+        let region = region(Range::new(source, act_core::expression::Span::new(10, 10)));
+        assert_eq!((region.start_column, region.end_column), (1, 2));
+    }
+}
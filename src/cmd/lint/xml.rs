@@ -0,0 +1,35 @@
+/// Escape text for an XML attribute value or element content.
+///
+/// Whitespace other than spaces is escaped too, attribute values would normalize it.
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\n' => out.push_str("&#10;"),
+            '\r' => out.push_str("&#13;"),
+            '\t' => out.push_str("&#9;"),
+            // other control characters are not allowed in XML 1.0
+            c if c.is_control() => out.push('\u{fffd}'),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_special() {
+        assert_eq!(
+            escape("a < b && c > \"d\" 'e'\n\u{1}"),
+            "a &lt; b &amp;&amp; c &gt; &quot;d&quot; &apos;e&apos;&#10;\u{fffd}"
+        );
+    }
+}
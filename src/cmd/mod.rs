@@ -1 +1,3 @@
+pub(crate) mod config;
 pub(crate) mod lint;
+pub(crate) mod rules;
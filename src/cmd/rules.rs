@@ -0,0 +1,88 @@
+use std::process::ExitCode;
+
+use act_core::{Registry, Rule};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Explain the rule with this id or name instead of listing every rule
+    #[arg(value_name = "RULE")]
+    pub rule: Option<String>,
+}
+
+pub fn run(args: &Args) -> ExitCode {
+    let registry = Registry::builtin();
+    match &args.rule {
+        None => {
+            for rule in registry.rules() {
+                println!("{}", summary(rule));
+            }
+            ExitCode::SUCCESS
+        }
+        Some(id) => match registry
+            .rules()
+            .find(|rule| rule.id().eq_ignore_ascii_case(id) || rule.name() == id)
+        {
+            Some(rule) => {
+                println!("{}", explain(rule));
+                ExitCode::SUCCESS
+            }
+            None => {
+                eprintln!("error: unknown rule `{}`", id);
+                ExitCode::FAILURE
+            }
+        },
+    }
+}
+
+/// One line with the id, name, category, default severity and description of a rule.
+fn summary(rule: &dyn Rule) -> String {
+    format!(
+        "{} {:<20} {:<11} {:<7} {}",
+        rule.id(),
+        rule.name(),
+        rule.category().to_string(),
+        rule.default_severity().to_string(),
+        rule.description()
+    )
+}
+
+/// The documentation of a rule.
+fn explain(rule: &dyn Rule) -> String {
+    let mut out = format!(
+        "{} {}\n\ncategory: {}\ndefault severity: {}\n\n{}\n\n{}\n",
+        rule.id(),
+        rule.name(),
+        rule.category(),
+        rule.default_severity(),
+        rule.description(),
+        rule.explanation()
+    );
+    if let Some(uri) = rule.help_uri() {
+        out.push_str(&format!("\nSee {}\n", uri));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_line() {
+        let registry = Registry::builtin();
+        assert_eq!(
+            summary(registry.get("AL001").unwrap()),
+            "AL001 yaml-syntax          syntax      error   The workflow file must be valid YAML."
+        );
+    }
+
+    #[test]
+    fn explain_rule() {
+        let registry = Registry::builtin();
+        let text = explain(registry.get("AL005").unwrap());
+        assert!(text.starts_with(
+            "AL005 reserved-env-name\n\ncategory: style\ndefault severity: warning\n"
+        ));
+        assert!(text.ends_with("#naming-conventions-for-environment-variables\n"));
+    }
+}
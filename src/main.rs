@@ -1,6 +1,8 @@
-use clap::{arg, command, Parser, Subcommand};
+use std::process::ExitCode;
 
-use cmd::lint;
+use clap::{Parser, Subcommand};
+
+use cmd::{config, lint, rules};
 
 mod cmd;
 
@@ -8,7 +10,7 @@ mod cmd;
 #[command(author, version, about, long_about = None)]
 #[command(arg_required_else_help = true, subcommand_required = true)]
 #[command(
-help_template = "{usage-heading} {usage} \n {about-section} \n\n{all-args} {tab}\n\nVersion: {version} \nAuthor: {author-with-newline}"
+    help_template = "{usage-heading} {usage} \n {about-section} \n\n{all-args} {tab}\n\nVersion: {version} \nAuthor: {author-with-newline}"
 )]
 struct Cli {
     #[command(subcommand)]
@@ -25,13 +27,21 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Manage series of commands, execute on a git hook or manually
+    /// Lint workflow files, execute on a git hook or manually
     Lint(lint::Args),
+
+    /// List the rules, or explain one of them
+    Rules(rules::Args),
+
+    /// Check the `.act-lint.yml` configuration file
+    Config(config::Args),
 }
 
-fn main() {
+fn main() -> ExitCode {
     let cli = Cli::parse();
     match &cli.command {
         Commands::Lint(args) => lint::run(args),
+        Commands::Rules(args) => rules::run(args),
+        Commands::Config(args) => config::run(args),
     }
 }